@@ -0,0 +1,1813 @@
+//! BM1368 ASIC implementation.
+
+#![no_std]
+#![macro_use]
+pub(crate) mod fmt;
+
+use bm13xx_asic::{
+    core_register::*, pll::PLL_REG_ADDRS, register::*, Asic, BaudratePlan, CmdDelay, Error,
+    SequenceStep,
+};
+use bm13xx_protocol::command::{Command, Destination};
+
+use core::time::Duration;
+use fugit::HertzU64;
+use heapless::FnvIndexMap;
+
+pub const BM1368_CHIP_ID: u16 = 0x1368;
+pub const BM1368_CORE_CNT: usize = 80;
+pub const BM1368_SMALL_CORE_CNT: usize = 1276;
+pub const BM1368_CORE_SMALL_CORE_CNT: usize = 16;
+pub const BM1368_DOMAIN_CNT: usize = 4;
+pub const BM1368_PLL_CNT: usize = 2;
+pub const BM1368_PLL_ID_HASH: usize = 0; // PLL0 isused for Hashing
+pub const BM1368_PLL_OUT_HASH: usize = 0; // specifically PLL0_OUT0 can be used for Hashing
+pub const BM1368_PLL_ID_UART: usize = 1; // PLL1 can be used for UART Baudrate
+pub const BM1368_PLL_OUT_UART: usize = 4; // specifically PLL1_OUT4 can be used for UART Baudrate
+pub const BM1368_MAX_HASH_FREQ: HertzU64 = HertzU64::MHz(575);
+pub const BM1368_MIN_INPUT_CLK: HertzU64 = HertzU64::MHz(20); // CLKI is a 25 MHz crystal on all known boards
+pub const BM1368_MAX_INPUT_CLK: HertzU64 = HertzU64::MHz(30);
+pub const BM1368_NONCE_CORES_BITS: usize = 7; // Core ID is hardcoded on Nonce[31:25] -> 7 bits
+pub const BM1368_NONCE_CORES_MASK: u32 = 0b111_1111;
+pub const BM1368_NONCE_SMALL_CORES_BITS: usize = 4; // Small Core ID is hardcoded on Nonce[24:21] -> 4 bits
+pub const BM1368_NONCE_SMALL_CORES_MASK: u32 = 0b1111;
+
+const NONCE_BITS: usize = 32;
+const CHIP_ADDR_BITS: usize = 8;
+const CHIP_ADDR_MASK: u32 = 0b1111_1111;
+
+/// # BM1368
+#[derive(Debug)]
+pub struct BM1368 {
+    seq_step: SequenceStep,
+    pub sha: bm13xx_asic::sha::Sha<
+        BM1368_CORE_CNT,
+        BM1368_SMALL_CORE_CNT,
+        BM1368_CORE_SMALL_CORE_CNT,
+        BM1368_DOMAIN_CNT,
+    >,
+    pub input_clock_freq: HertzU64,
+    pub plls: [bm13xx_asic::pll::Pll; BM1368_PLL_CNT],
+    pub chip_addr: u8,
+    pub registers: FnvIndexMap<u8, u32, 64>,
+    pub core_registers: FnvIndexMap<u8, u8, 16>,
+    pub version_rolling_enabled: bool,
+    pub version_mask: u32,
+    /// Hash PLL of the chips set apart with `set_hash_freq_next_dest`, by chip address.
+    chip_hash_plls: FnvIndexMap<u8, bm13xx_asic::pll::Pll, 16>,
+    /// Hash frequency sequence step of the chips ramping on their own, by chip address.
+    chip_seq_steps: FnvIndexMap<u8, SequenceStep, 16>,
+}
+
+impl BM1368 {
+    /// ## Create a Chip in its default state, with a given input clock
+    ///
+    /// ### Panics
+    ///
+    /// If `clk` is outside `BM1368_MIN_INPUT_CLK..=BM1368_MAX_INPUT_CLK`.
+    pub fn new_with_clk(clk: HertzU64) -> Self {
+        // the default registers are known to fit the maps
+        Self::try_new_with_clk(clk).unwrap()
+    }
+
+    /// ## Reset the Chip to its default state
+    ///
+    /// Same as `Asic::reset`, reporting a full register map instead of panicking.
+    ///
+    /// ### Example
+    /// ```
+    /// use bm1368::BM1368;
+    ///
+    /// let mut bm1368 = BM1368::default();
+    /// bm1368.set_chip_addr(2);
+    /// bm1368.try_reset().unwrap();
+    /// assert_eq!(bm1368.chip_addr, 0);
+    /// assert_eq!(bm1368.registers, BM1368::default().registers);
+    /// ```
+    pub fn try_reset(&mut self) -> Result<(), Error> {
+        *self = Self::try_new_with_clk(self.input_clock_freq)?;
+        Ok(())
+    }
+
+    fn insert_checked(&mut self, reg_addr: u8, reg_val: u32) -> Result<(), Error> {
+        self.registers
+            .insert(reg_addr, reg_val)
+            .map_err(|_| Error::RegisterMapFull { reg_addr })?;
+        Ok(())
+    }
+
+    fn insert_core_checked(&mut self, core_reg_id: u8, core_reg_val: u8) -> Result<(), Error> {
+        self.core_registers
+            .insert(core_reg_id, core_reg_val)
+            .map_err(|_| Error::CoreRegisterMapFull { core_reg_id })?;
+        Ok(())
+    }
+
+    /// ## Create a Chip from a registers dump
+    ///
+    /// Starts from the default state and overlays the given `registers` and
+    /// `core_registers` values. The PLLs model is not updated.
+    ///
+    /// ### Errors
+    ///
+    /// - Unsupported input clock, see `try_new_with_clk`.
+    /// - Register map full, if the register addresses do not fit the map.
+    /// - Core register map full, if the core register IDs do not fit the map.
+    ///
+    /// ### Example
+    /// ```
+    /// use bm1368::BM1368;
+    /// use bm13xx_asic::{Asic, Error};
+    /// use fugit::HertzU64;
+    ///
+    /// let mut bm1368 = BM1368::default();
+    /// while bm1368.init_next(256).is_some() {}
+    /// let registers: Vec<(u8, u32)> = bm1368.registers.iter().map(|(a, v)| (*a, *v)).collect();
+    /// let core_registers: Vec<(u8, u8)> = bm1368.core_registers.iter().map(|(i, v)| (*i, *v)).collect();
+    /// let restored = BM1368::from_registers(HertzU64::MHz(25), &registers, &core_registers).unwrap();
+    /// assert_eq!(restored.registers, bm1368.registers);
+    /// assert_eq!(restored.core_registers, bm1368.core_registers);
+    ///
+    /// let too_many: Vec<(u8, u32)> = (0..=255).map(|a| (a, 0)).collect();
+    /// assert!(matches!(
+    ///     BM1368::from_registers(HertzU64::MHz(25), &too_many, &[]),
+    ///     Err(Error::RegisterMapFull { .. })
+    /// ));
+    /// ```
+    pub fn from_registers(
+        clk: HertzU64,
+        registers: &[(u8, u32)],
+        core_registers: &[(u8, u8)],
+    ) -> Result<Self, Error> {
+        let mut bm1368 = Self::try_new_with_clk(clk)?;
+        for &(reg_addr, reg_val) in registers {
+            bm1368.insert_checked(reg_addr, reg_val)?;
+        }
+        for &(core_reg_id, core_reg_val) in core_registers {
+            bm1368.insert_core_checked(core_reg_id, core_reg_val)?;
+        }
+        Ok(bm1368)
+    }
+
+    /// Hash PLL of the chip(s) at `dest`, the chain-wide one unless the chip was set apart.
+    fn hash_pll(&self, dest: Destination) -> bm13xx_asic::pll::Pll {
+        match dest {
+            Destination::Chip(chip_addr) => self.chip_hash_plls.get(&chip_addr).copied(),
+            Destination::All => None,
+        }
+        .unwrap_or(self.plls[BM1368_PLL_ID_HASH])
+    }
+
+    /// Save the hash PLL of the chip(s) at `dest`.
+    ///
+    /// A chip only keeps its own PLL while its frequency differs from the chain-wide one, up
+    /// to 16 chips.
+    /// The chain-wide one is also saved in the registers.
+    fn set_hash_pll(&mut self, dest: Destination, pll: bm13xx_asic::pll::Pll) {
+        match dest {
+            Destination::Chip(chip_addr) => {
+                if pll.frequency(self.input_clock_freq, BM1368_PLL_OUT_HASH) == self.hash_freq() {
+                    self.chip_hash_plls.remove(&chip_addr);
+                } else if self.chip_hash_plls.insert(chip_addr, pll).is_err() {
+                    warn!(
+                        "Too many chips set apart, chip @{} hash PLL not tracked",
+                        chip_addr
+                    );
+                }
+            }
+            Destination::All => {
+                self.plls[BM1368_PLL_ID_HASH] = pll;
+                self.chip_hash_plls.clear();
+                self.sync_plls_to_registers();
+            }
+        }
+    }
+
+    /// Baudrate command list of a chain of `chain_domain_cnt` voltage domains, domain `dom`
+    /// holding `domain_asic_cnt(dom)` chips.
+    fn baudrate_next(
+        &mut self,
+        baudrate: u32,
+        chain_domain_cnt: usize,
+        domain_asic_cnt: impl Fn(usize) -> u8,
+        asic_addr_interval: u16,
+    ) -> Option<CmdDelay> {
+        // index of the first chip of a voltage domain
+        let first_asic = |dom: usize| -> u32 { (0..dom).map(|d| domain_asic_cnt(d) as u32).sum() };
+        let asic_addr = |asic: u32| (asic * asic_addr_interval as u32) as u8;
+        let chain_asic_cnt = first_asic(chain_domain_cnt);
+        // UART relay gap of a voltage domain, saturated to the GAP_CNT field width
+        let gap_cnt = |dom: usize| -> u16 {
+            let gap = chain_asic_cnt - first_asic(dom) + 14;
+            u16::try_from(gap).unwrap_or_else(|_| {
+                warn!("UART relay gap count {} saturated to {}", gap, u16::MAX);
+                u16::MAX
+            })
+        };
+        let sub_seq1_start = 0;
+        let sub_seq2_start = sub_seq1_start + chain_domain_cnt;
+        let sub_seq3_start = sub_seq2_start + chain_domain_cnt;
+        let sub_seq4_start = sub_seq3_start + chain_domain_cnt;
+        let sub_seq5_start = sub_seq4_start + 1;
+        let end = sub_seq5_start + 1;
+        let pll1_div4 = 6;
+        match self.seq_step {
+            SequenceStep::Baudrate(step) => {
+                if (sub_seq1_start..sub_seq2_start).contains(&step) {
+                    self.seq_step = SequenceStep::Baudrate(step + 1);
+                    // last chip of each voltage domain should have IoDriverStrenghtConfiguration set to 0x0211_f111
+                    // (iterating voltage domain in decreasing chip address order)
+                    let dom = sub_seq2_start - step - 1;
+                    let io_drv_st_cfg = IoDriverStrenghtConfiguration(
+                        *self
+                            .registers
+                            .get(&IoDriverStrenghtConfiguration::ADDR)
+                            .unwrap(),
+                    )
+                    .set_strenght(DriverSelect::CLKO, 15)
+                    .val();
+                    // do not save any chip-specific value
+                    Some(CmdDelay {
+                        cmd: Command::write_reg(
+                            IoDriverStrenghtConfiguration::ADDR,
+                            io_drv_st_cfg,
+                            Destination::Chip(asic_addr(first_asic(dom + 1) - 1)),
+                        ),
+                        delay_ms: 0,
+                    })
+                } else if (sub_seq2_start..sub_seq3_start).contains(&step) {
+                    // first and last chip of each voltage domain should have UARTRelay with
+                    // GAP_CNT=(chips from domain_i to the end of the chain)+14
+                    // RO_REL_EN=CO_REL_EN=1
+                    // (iterating voltage domain in decreasing chip address order)
+                    self.seq_step = SequenceStep::Baudrate(step + chain_domain_cnt);
+                    // jump to next sub-seq to alternate
+                    let dom = sub_seq3_start - step - 1;
+                    let uart_delay = UARTRelay(*self.registers.get(&UARTRelay::ADDR).unwrap())
+                        .set_gap_cnt(gap_cnt(dom))
+                        .enable_ro_relay()
+                        .enable_co_relay()
+                        .val();
+                    // do not save any chip-specific value
+                    Some(CmdDelay {
+                        cmd: Command::write_reg(
+                            UARTRelay::ADDR,
+                            uart_delay,
+                            Destination::Chip(asic_addr(first_asic(dom))),
+                        ),
+                        delay_ms: 0,
+                    })
+                } else if (sub_seq3_start..sub_seq4_start).contains(&step) {
+                    // same for last chip of each voltage domain
+                    self.seq_step = SequenceStep::Baudrate(if step == sub_seq4_start - 1 {
+                        sub_seq4_start
+                    } else {
+                        step - chain_domain_cnt + 1
+                    });
+                    // jump back to previous sub-seq to alternate
+                    let dom = sub_seq4_start - step - 1;
+                    let uart_delay = UARTRelay(*self.registers.get(&UARTRelay::ADDR).unwrap())
+                        .set_gap_cnt(gap_cnt(dom))
+                        .enable_ro_relay()
+                        .enable_co_relay()
+                        .val();
+                    // do not save any chip-specific value
+                    Some(CmdDelay {
+                        cmd: Command::write_reg(
+                            UARTRelay::ADDR,
+                            uart_delay,
+                            Destination::Chip(asic_addr(first_asic(dom + 1) - 1)),
+                        ),
+                        delay_ms: if step == sub_seq4_start - 1 { 130 } else { 0 },
+                    })
+                } else if step == sub_seq4_start {
+                    if baudrate <= self.input_clock_freq.raw() as u32 / 8 {
+                        self.seq_step = SequenceStep::Baudrate(end);
+                        let fbase = self.input_clock_freq.raw() as u32;
+                        let bt8d = (fbase / (8 * baudrate)) - 1;
+                        let fast_uart_cfg = FastUARTConfigurationV2(
+                            *self.registers.get(&FastUARTConfigurationV2::ADDR).unwrap(),
+                        )
+                        .set_b28()
+                        // .set_b24()
+                        .set_bclk_sel(BaudrateClockSelectV2::Clki)
+                        .set_bt8d(bt8d as u8)
+                        .val();
+                        self.registers
+                            .insert(FastUARTConfigurationV2::ADDR, fast_uart_cfg)
+                            .unwrap();
+                        Some(CmdDelay {
+                            cmd: Command::write_reg(
+                                FastUARTConfigurationV2::ADDR,
+                                fast_uart_cfg,
+                                Destination::All,
+                            ),
+                            delay_ms: 200,
+                        })
+                    } else {
+                        self.seq_step = SequenceStep::Baudrate(sub_seq5_start);
+                        self.plls[BM1368_PLL_ID_UART]
+                            // .set_parameter(0xC070_0111)
+                            .lock()
+                            .enable()
+                            .set_fb_div(112)
+                            .set_ref_div(1)
+                            .set_post1_div(1)
+                            .set_post2_div(1)
+                            .set_out_div(BM1368_PLL_OUT_UART, pll1_div4);
+                        let pll1_param = self.plls[BM1368_PLL_ID_UART].parameter();
+                        self.registers
+                            .insert(PLL1Parameter::ADDR, pll1_param)
+                            .unwrap();
+                        Some(CmdDelay {
+                            cmd: Command::write_reg(
+                                PLL1Parameter::ADDR,
+                                pll1_param,
+                                Destination::All,
+                            ),
+                            delay_ms: 0,
+                        })
+                    }
+                } else if step == sub_seq5_start {
+                    self.seq_step = SequenceStep::Baudrate(end);
+                    if baudrate <= self.input_clock_freq.raw() as u32 / 8 {
+                        // should not be reached for 2 reasons:
+                        // - in step above we jump directly to end
+                        // - after setting the chip's FastUartConfiguration with bclk_sel(BaudrateClockSelectV2::Clki) in previous step
+                        //   the chip's baudrate should immediatly adapt and thus this new step with old baudrate from control side
+                        //   will be ignored by the chip.
+                        let pll1_param =
+                            self.plls[BM1368_PLL_ID_UART].disable().unlock().parameter();
+                        self.registers
+                            .insert(PLL1Parameter::ADDR, pll1_param)
+                            .unwrap();
+                        Some(CmdDelay {
+                            cmd: Command::write_reg(
+                                PLL1Parameter::ADDR,
+                                pll1_param,
+                                Destination::All,
+                            ),
+                            delay_ms: 0,
+                        })
+                    } else {
+                        let fbase = self.plls[BM1368_PLL_ID_UART]
+                            .frequency(self.input_clock_freq, BM1368_PLL_OUT_UART)
+                            .raw();
+                        let bt8d = (fbase as u32 / (2 * baudrate)) - 1;
+                        let fast_uart_cfg = FastUARTConfigurationV2(
+                            *self.registers.get(&FastUARTConfigurationV2::ADDR).unwrap(),
+                        )
+                        .set_b28()
+                        // .set_b24()
+                        .set_pll1_div4(pll1_div4)
+                        .set_bclk_sel(BaudrateClockSelectV2::Pll1)
+                        .set_bt8d(bt8d as u8)
+                        .val();
+                        self.registers
+                            .insert(FastUARTConfigurationV2::ADDR, fast_uart_cfg)
+                            .unwrap();
+                        Some(CmdDelay {
+                            cmd: Command::write_reg(
+                                FastUARTConfigurationV2::ADDR,
+                                fast_uart_cfg,
+                                Destination::All,
+                            ),
+                            delay_ms: 200,
+                        })
+                    }
+                } else if step == end {
+                    self.seq_step = SequenceStep::None;
+                    None
+                } else {
+                    unreachable!("step={}", step)
+                }
+            }
+            _ => {
+                // authorize a SetBaudrate sequence start whatever the current step was
+                self.seq_step = SequenceStep::Baudrate(sub_seq1_start);
+                let io_drv_st_cfg = IoDriverStrenghtConfiguration(
+                    *self
+                        .registers
+                        .get(&IoDriverStrenghtConfiguration::ADDR)
+                        .unwrap(),
+                )
+                .set_strenght(DriverSelect::RF, 2)
+                .disable(DriverRSelect::D3R)
+                .disable(DriverRSelect::D2R)
+                .disable(DriverRSelect::D1R)
+                .enable(DriverRSelect::D0R)
+                .set_strenght(DriverSelect::RO, 1)
+                .set_strenght(DriverSelect::CLKO, 1)
+                .set_strenght(DriverSelect::NRSTO, 1)
+                .set_strenght(DriverSelect::BO, 1)
+                .set_strenght(DriverSelect::CO, 1)
+                .val();
+                self.registers
+                    .insert(IoDriverStrenghtConfiguration::ADDR, io_drv_st_cfg)
+                    .unwrap();
+                Some(CmdDelay {
+                    cmd: Command::write_reg(
+                        IoDriverStrenghtConfiguration::ADDR,
+                        io_drv_st_cfg,
+                        Destination::All,
+                    ),
+                    delay_ms: 0,
+                })
+            }
+        }
+    }
+
+    /// Hash frequency sequence step of the chip(s) at `dest`.
+    ///
+    /// Each chip has its own step, so the sequences of several chips can be interleaved, up
+    /// to 16 chips at once. Beyond, the chips share the chain-wide step.
+    fn hash_seq_step(&self, dest: Destination) -> SequenceStep {
+        match dest {
+            Destination::Chip(chip_addr) => match self.chip_seq_steps.get(&chip_addr) {
+                Some(step) => step.clone(),
+                None if (self.chip_seq_steps.len() == self.chip_seq_steps.capacity()) => {
+                    self.seq_step.clone()
+                }
+                None => SequenceStep::None,
+            },
+            Destination::All => self.seq_step.clone(),
+        }
+    }
+
+    /// Save the hash frequency sequence step of the chip(s) at `dest`.
+    fn set_hash_seq_step(&mut self, dest: Destination, step: SequenceStep) {
+        match dest {
+            Destination::Chip(chip_addr) if self.chip_seq_steps.contains_key(&chip_addr) => {
+                if step == SequenceStep::None {
+                    self.chip_seq_steps.remove(&chip_addr);
+                } else {
+                    self.chip_seq_steps.insert(chip_addr, step).ok();
+                }
+            }
+            Destination::Chip(chip_addr)
+                if !(self.chip_seq_steps.len() == self.chip_seq_steps.capacity()) =>
+            {
+                if step != SequenceStep::None {
+                    self.chip_seq_steps.insert(chip_addr, step).ok();
+                }
+            }
+            Destination::Chip(chip_addr) => {
+                warn!(
+                    "Too many chips ramping on their own, chip @{} sequence shared",
+                    chip_addr
+                );
+                self.seq_step = step;
+            }
+            Destination::All => self.seq_step = step,
+        }
+    }
+
+    /// ## Save the PLLs model in the PLLnParameter and PLLnDivider registers
+    ///
+    /// ### Example
+    /// ```
+    /// use bm1368::{BM1368, BM1368_PLL_ID_HASH};
+    /// use bm13xx_asic::{register::{PLL0Divider, PLL0Parameter}, Asic};
+    /// use fugit::HertzU64;
+    ///
+    /// let mut bm1368 = BM1368::default();
+    /// while bm1368.set_hash_freq_next(HertzU64::MHz(75)).is_some() {}
+    /// let (parameter, divider) = bm1368.plls[BM1368_PLL_ID_HASH].to_registers();
+    /// assert_eq!(bm1368.registers.get(&PLL0Parameter::ADDR), Some(&parameter));
+    /// assert_eq!(bm1368.registers.get(&PLL0Divider::ADDR), Some(&divider));
+    /// ```
+    pub fn sync_plls_to_registers(&mut self) {
+        for (pll, &(param_addr, div_addr)) in self.plls.iter().zip(PLL_REG_ADDRS.iter()) {
+            let (parameter, divider) = pll.to_registers();
+            // the PLL registers are part of the default registers
+            self.registers.insert(param_addr, parameter).unwrap();
+            self.registers.insert(div_addr, divider).unwrap();
+        }
+    }
+
+    /// ## Set the Chip Address
+    ///
+    /// ### Example
+    /// ```
+    /// use bm1368::BM1368;
+    ///
+    /// let mut bm1368 = BM1368::default();
+    /// bm1368.set_chip_addr(2);
+    /// assert_eq!(bm1368.chip_addr, 2);
+    /// ```
+    pub fn set_chip_addr(&mut self, chip_addr: u8) {
+        self.chip_addr = chip_addr;
+    }
+
+    /// ## Enable the Hardware Version Rolling
+    ///
+    /// ### Example
+    /// ```
+    /// use bm1368::BM1368;
+    ///
+    /// let mut bm1368 = BM1368::default();
+    /// bm1368.enable_version_rolling(0x1fffe000);
+    /// assert!(bm1368.version_rolling_enabled);
+    /// assert_eq!(bm1368.version_mask, 0x1fffe000);
+    /// ```
+    pub fn enable_version_rolling(&mut self, version_mask: u32) {
+        self.version_rolling_enabled = true;
+        self.version_mask = version_mask;
+    }
+
+    fn version_mask_bits(&self) -> usize {
+        self.version_mask.count_ones() as usize
+    }
+
+    /// ## Get the SHA Hashing Frequency
+    ///
+    /// ### Example
+    /// ```
+    /// use bm1368::{BM1368, BM1368_PLL_ID_HASH};
+    /// use fugit::HertzU64;
+    ///
+    /// let mut bm1368 = BM1368::default();
+    /// assert_eq!(bm1368.hash_freq(), HertzU64::MHz(50));
+    /// assert_eq!(bm1368.set_hash_freq(HertzU64::MHz(200)).hash_freq(), HertzU64::MHz(200));
+    /// ```
+    pub fn hash_freq(&self) -> HertzU64 {
+        self.plls[BM1368_PLL_ID_HASH].frequency(self.input_clock_freq, BM1368_PLL_OUT_HASH)
+    }
+    pub fn set_hash_freq(&mut self, freq: HertzU64) -> &mut Self {
+        self.plls[BM1368_PLL_ID_HASH].set_frequency(
+            self.input_clock_freq,
+            BM1368_PLL_OUT_HASH,
+            freq,
+            false,
+        );
+        self
+    }
+
+    /// ## Get the SHA Hashing Frequency in MHz
+    ///
+    /// ### Example
+    /// ```
+    /// use bm1368::BM1368;
+    /// use fugit::HertzU64;
+    ///
+    /// let mut bm1368 = BM1368::default();
+    /// assert_eq!(bm1368.hash_freq_mhz(), 50.0);
+    /// assert_eq!(bm1368.set_hash_freq(HertzU64::MHz(200)).hash_freq_mhz(), 200.0);
+    /// ```
+    pub fn hash_freq_mhz(&self) -> f32 {
+        self.plls[BM1368_PLL_ID_HASH].frequency_mhz(self.input_clock_freq, BM1368_PLL_OUT_HASH)
+    }
+
+    /// ## Get the theoretical Hashrate in GH/s
+    ///
+    /// ### Example
+    /// ```
+    /// use bm1368::BM1368;
+    /// use fugit::HertzU64;
+    ///
+    /// let bm1368 = BM1368::default();
+    /// assert_eq!(bm1368.theoretical_hashrate_ghs(), 63.8);
+    /// ```
+    pub fn theoretical_hashrate_ghs(&self) -> f32 {
+        self.hash_freq().raw() as f32 * self.sha.small_core_count() as f32 / 1_000_000_000.0
+    }
+
+    /// ## Get the rolling duration
+    ///
+    /// BM1368 can do Version Rolling in Hardware.
+    ///
+    /// If Hardware Version Rolling is not enabled, BM1368 only roll the Nonce Space (32 bits), but:
+    /// - Nonce\[31:25\] is used to hardcode the Core ID.
+    /// - Nonce\[24:21\] is used to hardcode the Small Core ID.
+    /// - Nonce\[20:13\] is used to hardcode the Chip Address.
+    ///
+    /// So only the Nonce\[12:0\] are rolled for each Chip Address.
+    ///
+    /// If Hardware Version Rolling is enabled, BM1368 roll the Nonce Space (32 bits) and
+    /// up to 16 bits in Version Space, but:
+    /// - Nonce\[31:25\] is used to hardcode the Core ID.
+    /// - Nonce\[24:17\] is used to hardcode the Chip Address.
+    /// - Version\[16:13\] is used to hardcode the Small Core ID (assuming the Version Mask is 0x1fffe000).
+    ///
+    /// So only the Nonce\[16:0\] and Version\[28:17\] are rolled for each Chip Address.
+    ///
+    /// ### Example
+    /// ```
+    /// use bm1368::BM1368;
+    /// use core::time::Duration;
+    ///
+    /// let mut bm1368 = BM1368::default();
+    /// assert_eq!(bm1368.rolling_duration(), Duration::from_secs_f32(0.00016384));
+    /// bm1368.enable_version_rolling(0x1fffe000);
+    /// assert_eq!(bm1368.rolling_duration(), Duration::from_secs_f32(10.737418));
+    /// ```
+    pub fn rolling_duration(&self) -> Duration {
+        let space = if self.version_rolling_enabled {
+            (1 << (NONCE_BITS - BM1368_NONCE_CORES_BITS - CHIP_ADDR_BITS
+                + self.version_mask_bits()
+                - BM1368_NONCE_SMALL_CORES_BITS)) as f32
+        } else {
+            (1 << (NONCE_BITS
+                - BM1368_NONCE_CORES_BITS
+                - BM1368_NONCE_SMALL_CORES_BITS
+                - CHIP_ADDR_BITS)) as f32
+        };
+        Duration::from_secs_f32(space / (self.hash_freq().raw() as f32))
+    }
+
+    /// ## Get the Core ID that produced a given Nonce
+    ///
+    /// Core ID is always hardcoded in Nonce\[31:25\].
+    ///
+    /// ### Example
+    /// ```
+    /// use bm1368::BM1368;
+    ///
+    /// let bm1368 = BM1368::default();
+    /// assert_eq!(bm1368.nonce2core_id(0x12345678), 0x09);
+    /// assert_eq!(bm1368.nonce2core_id(0x906732c8), 72);
+    /// ```
+    pub fn nonce2core_id(&self, nonce: u32) -> usize {
+        ((nonce >> (NONCE_BITS - BM1368_NONCE_CORES_BITS)) & BM1368_NONCE_CORES_MASK) as usize
+    }
+
+    /// ## Get the Small Core ID that produced a given Nonce
+    ///
+    /// If the Hardware Version Rolling is disabled, the Small Core ID is hardcoded in Nonce\[24:21\].
+    ///
+    /// ### Example
+    /// ```
+    /// use bm1368::BM1368;
+    ///
+    /// let bm1368 = BM1368::default();
+    /// assert_eq!(bm1368.nonce2small_core_id(0x12045678), 0);
+    /// assert_eq!(bm1368.nonce2small_core_id(0x12245678), 1);
+    /// assert_eq!(bm1368.nonce2small_core_id(0x12445678), 2);
+    /// assert_eq!(bm1368.nonce2small_core_id(0x12845678), 4);
+    /// assert_eq!(bm1368.nonce2small_core_id(0x13045678), 8);
+    /// assert_eq!(bm1368.nonce2small_core_id(0x13e45678), 15);
+    /// ```
+    pub fn nonce2small_core_id(&self, nonce: u32) -> usize {
+        ((nonce >> (NONCE_BITS - BM1368_NONCE_CORES_BITS - BM1368_NONCE_SMALL_CORES_BITS))
+            & BM1368_NONCE_SMALL_CORES_MASK) as usize
+    }
+
+    /// ## Get the Small Core ID that produced a given Version
+    ///
+    /// If the Hardware Version Rolling is enabled, the Small Core ID is hardcoded in Version\[16:13\]
+    /// (assuming the Version Mask is 0x1fffe000).
+    ///
+    /// ### Example
+    /// ```
+    /// use bm1368::BM1368;
+    ///
+    /// let mut bm1368 = BM1368::default();
+    /// bm1368.enable_version_rolling(0x1fffe000);
+    /// assert_eq!(bm1368.version2small_core_id(0x1ffe0000), 0);
+    /// assert_eq!(bm1368.version2small_core_id(0x1ffe2000), 1);
+    /// assert_eq!(bm1368.version2small_core_id(0x1ffe4000), 2);
+    /// assert_eq!(bm1368.version2small_core_id(0x1fff0000), 8);
+    /// assert_eq!(bm1368.version2small_core_id(0x1fffe000), 15);
+    /// ```
+    pub fn version2small_core_id(&self, version: u32) -> usize {
+        ((version >> self.version_mask.trailing_zeros()) & BM1368_NONCE_SMALL_CORES_MASK) as usize
+    }
+
+    /// ## Get the Chip Address that produced a given Nonce
+    ///
+    /// If the Hardware Version Rolling is enabled, the Chip Address is hardcoded in Nonce\[24:17\],
+    /// else it is hardcoded in Nonce\[20:13\].
+    ///
+    /// ### Example
+    /// ```
+    /// use bm1368::BM1368;
+    ///
+    /// let mut bm1368 = BM1368::default();
+    /// assert_eq!(bm1368.nonce2chip_addr(0x12345678), 0xA2);
+    /// bm1368.enable_version_rolling(0x1fffe000);
+    /// assert_eq!(bm1368.nonce2chip_addr(0x12345679), 0x1A);
+    /// ```
+    pub fn nonce2chip_addr(&self, nonce: u32) -> usize {
+        if self.version_rolling_enabled {
+            ((nonce >> (NONCE_BITS - BM1368_NONCE_CORES_BITS - CHIP_ADDR_BITS)) & CHIP_ADDR_MASK)
+                as usize
+        } else {
+            ((nonce
+                >> (NONCE_BITS
+                    - BM1368_NONCE_CORES_BITS
+                    - BM1368_NONCE_SMALL_CORES_BITS
+                    - CHIP_ADDR_BITS))
+                & CHIP_ADDR_MASK) as usize
+        }
+    }
+}
+
+impl BM1368 {
+    /// ## Create a Chip in its default state, with a given input clock
+    ///
+    /// Same as `new_with_clk`, reporting an unsupported input clock or a full register
+    /// map instead of panicking.
+    ///
+    /// ### Errors
+    ///
+    /// - Unsupported input clock, if `clk` is outside
+    ///   `BM1368_MIN_INPUT_CLK..=BM1368_MAX_INPUT_CLK`.
+    /// - Register map full, if the default registers do not fit the map.
+    /// - Core register map full, if the default core registers do not fit the map.
+    ///
+    /// ### Example
+    /// ```
+    /// use bm1368::BM1368;
+    /// use bm13xx_asic::Error;
+    /// use fugit::HertzU64;
+    ///
+    /// assert!(BM1368::try_new_with_clk(HertzU64::MHz(25)).is_ok());
+    /// assert_eq!(
+    ///     BM1368::try_new_with_clk(HertzU64::MHz(12)).err(),
+    ///     Some(Error::UnsupportedInputClock { clk: HertzU64::MHz(12) })
+    /// );
+    /// ```
+    pub fn try_new_with_clk(clk: HertzU64) -> Result<Self, Error> {
+        if clk < BM1368_MIN_INPUT_CLK || clk > BM1368_MAX_INPUT_CLK {
+            return Err(Error::UnsupportedInputClock { clk });
+        }
+        let mut bm1368 = Self {
+            seq_step: SequenceStep::default(),
+            sha: bm13xx_asic::sha::Sha::default(),
+            input_clock_freq: clk,
+            plls: [bm13xx_asic::pll::Pll::default(); BM1368_PLL_CNT],
+            chip_addr: 0,
+            registers: FnvIndexMap::<_, _, 64>::new(),
+            core_registers: FnvIndexMap::<_, _, 16>::new(),
+            version_rolling_enabled: false,
+            version_mask: 0x1fffe000,
+            chip_hash_plls: FnvIndexMap::new(),
+            chip_seq_steps: FnvIndexMap::new(),
+        };
+        // Default PLLs Parameter
+        bm1368.plls[0].set_parameter(0xC054_0165);
+        bm1368.plls[1].set_parameter(0x2050_0174);
+        // Default PLLs Divider
+        bm1368.plls[0].set_divider(0x0000_0000);
+        bm1368.plls[1].set_divider(0x0000_0000);
+        // Default Registers Value, assumed to be the BM1366 ones except the ChipIdentification
+        bm1368.insert_checked(ChipIdentification::ADDR, 0x1368_0000)?;
+        bm1368.insert_checked(HashRate::ADDR, 0x0001_2a89)?;
+        bm1368.insert_checked(PLL0Parameter::ADDR, 0xc054_0165)?;
+        bm1368.insert_checked(ChipNonceOffsetV2::ADDR, 0x0000_0000)?;
+        bm1368.insert_checked(HashCountingNumber::ADDR, 0x0000_0000)?;
+        bm1368.insert_checked(TicketMask::ADDR, 0x0000_0000)?;
+        bm1368.insert_checked(MiscControl::ADDR, 0x0000_c100)?;
+        bm1368.insert_checked(I2CControl::ADDR, 0x0000_0000)?;
+        bm1368.insert_checked(OrderedClockEnable::ADDR, 0x0000_0003)?;
+        bm1368.insert_checked(Reg24::ADDR, 0x0010_0000)?;
+        bm1368.insert_checked(FastUARTConfigurationV2::ADDR, 0x0130_1a00)?;
+        bm1368.insert_checked(UARTRelay::ADDR, 0x000f_0000)?;
+        bm1368.insert_checked(Reg30::ADDR, 0x0000_0070)?;
+        bm1368.insert_checked(Reg34::ADDR, 0x0000_0000)?;
+        bm1368.insert_checked(TicketMask2::ADDR, 0x0000_0000)?;
+        bm1368.insert_checked(CoreRegisterControl::ADDR, 0x0000_0000)?;
+        bm1368.insert_checked(CoreRegisterValue::ADDR, 0x1eaf_5fbe)?;
+        bm1368.insert_checked(ExternalTemperatureSensorRead::ADDR, 0x0000_0000)?;
+        bm1368.insert_checked(ErrorFlag::ADDR, 0x0000_0000)?;
+        bm1368.insert_checked(NonceErrorCounter::ADDR, 0x0000_0000)?;
+        bm1368.insert_checked(NonceOverflowCounter::ADDR, 0x0000_0000)?;
+        bm1368.insert_checked(AnalogMuxControlV2::ADDR, 0x0000_0000)?;
+        bm1368.insert_checked(IoDriverStrenghtConfiguration::ADDR, 0x0001_2111)?;
+        bm1368.insert_checked(TimeOut::ADDR, 0x0000_FFFF)?;
+        bm1368.insert_checked(PLL1Parameter::ADDR, 0x2050_0174)?;
+        bm1368.insert_checked(OrderedClockMonitor::ADDR, 0x0001_0200)?;
+        bm1368.insert_checked(PLL0Divider::ADDR, 0x0000_0000)?;
+        bm1368.insert_checked(PLL1Divider::ADDR, 0x0000_0000)?;
+        bm1368.insert_checked(ClockOrderControl0::ADDR, 0x0000_0000)?;
+        bm1368.insert_checked(ClockOrderControl1::ADDR, 0x0000_0000)?;
+        bm1368.insert_checked(ClockOrderStatus::ADDR, 0x0000_0000)?;
+        bm1368.insert_checked(FrequencySweepControl1::ADDR, 0x0000_0000)?;
+        bm1368.insert_checked(GoldenNonceForSweepReturn::ADDR, 0x0000_0000)?;
+        bm1368.insert_checked(ReturnedGroupPatternStatus::ADDR, 0x0000_0000)?;
+        bm1368.insert_checked(NonceReturnedTimeout::ADDR, 0x00fd_0077)?;
+        bm1368.insert_checked(ReturnedSinglePatternStatus::ADDR, 0x0000_0000)?;
+        bm1368.insert_checked(VersionRolling::ADDR, 0x0000_ffff)?;
+        bm1368.insert_checked(RegA8::ADDR, 0x0007_0000)?;
+        bm1368.insert_checked(RegAC::ADDR, 0x0000_0000)?;
+        bm1368.insert_checked(RegB0::ADDR, 0x0000_0000)?;
+        bm1368.insert_checked(RegB4::ADDR, 0x0000_0000)?;
+        bm1368.insert_checked(RegB8::ADDR, 0x0000_0000)?;
+        bm1368.insert_checked(RegBC::ADDR, 0x0000_3313)?;
+        bm1368.insert_checked(RegC0::ADDR, 0x0000_2000)?;
+        bm1368.insert_checked(RegC4::ADDR, 0x0000_0000)?;
+        bm1368.insert_checked(RegC8::ADDR, 0x0000_0000)?;
+        bm1368.insert_checked(RegCC::ADDR, 0x0000_0000)?;
+        bm1368.insert_checked(RegD0::ADDR, 0x0000_0070)?;
+        bm1368.insert_checked(RegD4::ADDR, 0x0037_6400)?;
+        bm1368.insert_checked(RegD8::ADDR, 0x3030_3030)?;
+        bm1368.insert_checked(RegDC::ADDR, 0x0000_ffff)?;
+        bm1368.insert_checked(RegE0::ADDR, 0x0000_0000)?;
+        bm1368.insert_checked(RegE4::ADDR, 0x0000_0000)?;
+        bm1368.insert_checked(RegE8::ADDR, 0x0000_0000)?;
+        bm1368.insert_checked(RegEC::ADDR, 0x0000_0008)?;
+        bm1368.insert_checked(RegF0::ADDR, 0x0000_0000)?;
+        bm1368.insert_checked(RegF4::ADDR, 0x0000_0000)?;
+        bm1368.insert_checked(RegF8::ADDR, 0x0000_0000)?;
+        bm1368.insert_checked(RegFC::ADDR, 0x0000_0000)?;
+        // Default Core Registers Value
+        bm1368.insert_core_checked(ClockDelayCtrlV2::ID, 0x98)?;
+        // bm1368.insert_core_checked(1, 0x00)?; // not used anywhere in official FW
+        bm1368.insert_core_checked(2, 0x55)?;
+        bm1368.insert_core_checked(3, 0x00)?;
+        bm1368.insert_core_checked(4, 0x00)?;
+        bm1368.insert_core_checked(HashClockCtrl::ID, 0x40)?;
+        bm1368.insert_core_checked(HashClockCounter::ID, 0x08)?;
+        bm1368.insert_core_checked(7, 0x11)?;
+        bm1368.insert_core_checked(CoreReg8::ID, 0x00)?;
+        bm1368.insert_core_checked(CoreReg11::ID, 0x00)?;
+        bm1368.insert_core_checked(15, 0x00)?;
+        bm1368.insert_core_checked(16, 0x00)?;
+        bm1368.insert_core_checked(CoreReg22::ID, 0x00)?;
+        Ok(bm1368)
+    }
+}
+
+impl Default for BM1368 {
+    fn default() -> Self {
+        Self::new_with_clk(HertzU64::MHz(25))
+    }
+}
+
+// FnvIndexMap doesn't implement defmt, so the register maps are printed as (addr, value) pairs
+#[cfg(feature = "defmt-03")]
+impl defmt::Format for BM1368 {
+    fn format(&self, fmt: defmt::Formatter) {
+        defmt::write!(
+            fmt,
+            "BM1368 {{ chip_id: {:#06x}, chip_addr: {}, input_clock_freq: {}, hash_freq: {}, plls: {}, version_rolling_enabled: {}, version_mask: {:#010x}, registers: [",
+            self.chip_id(),
+            self.chip_addr,
+            self.input_clock_freq,
+            self.hash_freq(),
+            self.plls,
+            self.version_rolling_enabled,
+            self.version_mask,
+        );
+        for (reg_addr, reg_val) in self.registers.iter() {
+            defmt::write!(fmt, "({:#04x}, {:#010x}), ", reg_addr, reg_val);
+        }
+        defmt::write!(fmt, "], core_registers: [");
+        for (core_reg_id, core_reg_val) in self.core_registers.iter() {
+            defmt::write!(fmt, "({}, {:#04x}), ", core_reg_id, core_reg_val);
+        }
+        defmt::write!(fmt, "] }}");
+    }
+}
+
+impl Asic for BM1368 {
+    /// ## Get the Chip ID
+    ///
+    /// ### Example
+    /// ```
+    /// use bm1368::BM1368;
+    /// use bm13xx_asic::Asic;
+    ///
+    /// let bm1368 = BM1368::default();
+    /// assert_eq!(bm1368.chip_id(), 0x1368);
+    /// assert_eq!(bm1368.chip_name(), "BM1368");
+    /// ```
+    fn chip_id(&self) -> u16 {
+        BM1368_CHIP_ID
+    }
+
+    /// ## Get the number of Small Cores in each Core
+    ///
+    /// ### Example
+    /// ```
+    /// use bm1368::{BM1368, BM1368_CORE_SMALL_CORE_CNT};
+    /// use bm13xx_asic::Asic;
+    ///
+    /// let bm1368 = BM1368::default();
+    /// assert_eq!(bm1368.core_small_core_count(), BM1368_CORE_SMALL_CORE_CNT);
+    /// ```
+    fn core_small_core_count(&self) -> usize {
+        self.sha.core_small_core_count()
+    }
+
+    /// ## Get the number of Small Cores in the chip
+    ///
+    /// ### Example
+    /// ```
+    /// use bm1368::{BM1368, BM1368_SMALL_CORE_CNT};
+    /// use bm13xx_asic::Asic;
+    ///
+    /// let bm1368 = BM1368::default();
+    /// assert_eq!(bm1368.small_core_count(), BM1368_SMALL_CORE_CNT);
+    /// ```
+    fn small_core_count(&self) -> usize {
+        self.sha.small_core_count()
+    }
+
+    /// ## Get the number of Domains in the chip
+    ///
+    /// ### Example
+    /// ```
+    /// use bm1368::{BM1368, BM1368_DOMAIN_CNT};
+    /// use bm13xx_asic::Asic;
+    ///
+    /// let bm1368 = BM1368::default();
+    /// assert_eq!(bm1368.domain_count(), BM1368_DOMAIN_CNT);
+    /// ```
+    fn domain_count(&self) -> usize {
+        self.sha.domain_count()
+    }
+
+    /// ## Has Version Rolling in chip
+    ///
+    /// ### Example
+    /// ```
+    /// use bm1368::BM1368;
+    /// use bm13xx_asic::Asic;
+    ///
+    /// let bm1368 = BM1368::default();
+    /// assert!(bm1368.has_version_rolling());
+    /// ```
+    fn has_version_rolling(&self) -> bool {
+        true
+    }
+
+    /// ## Supports voltage domains of different sizes
+    ///
+    /// ### Example
+    /// ```
+    /// use bm1368::BM1368;
+    /// use bm13xx_asic::Asic;
+    ///
+    /// let bm1368 = BM1368::default();
+    /// assert!(bm1368.supports_mixed_domains());
+    /// ```
+    fn supports_mixed_domains(&self) -> bool {
+        true
+    }
+
+    /// ## Get the maximum hash frequency
+    ///
+    /// ### Example
+    /// ```
+    /// use bm1368::BM1368;
+    /// use bm13xx_asic::Asic;
+    /// use fugit::HertzU64;
+    ///
+    /// let bm1368 = BM1368::default();
+    /// assert_eq!(bm1368.max_hash_freq(), HertzU64::MHz(575));
+    /// ```
+    fn max_hash_freq(&self) -> HertzU64 {
+        BM1368_MAX_HASH_FREQ
+    }
+
+    /// ## Get the current hash frequency
+    ///
+    /// ### Example
+    /// ```
+    /// use bm1368::BM1368;
+    /// use bm13xx_asic::Asic;
+    /// use fugit::HertzU64;
+    ///
+    /// let bm1368 = BM1368::default();
+    /// assert_eq!(Asic::hash_freq(&bm1368), HertzU64::MHz(50));
+    /// ```
+    fn hash_freq(&self) -> HertzU64 {
+        BM1368::hash_freq(self)
+    }
+
+    /// ## Get the achievable hash frequency nearest to a target
+    ///
+    /// ### Example
+    /// ```
+    /// use bm1368::BM1368;
+    /// use bm13xx_asic::Asic;
+    /// use fugit::HertzU64;
+    ///
+    /// let bm1368 = BM1368::default();
+    /// assert_eq!(bm1368.nearest_hash_freq(HertzU64::MHz(425)), HertzU64::MHz(425));
+    /// assert_eq!(bm1368.nearest_hash_freq(HertzU64::Hz(487_654_321)), HertzU64::kHz(487_500));
+    /// assert_eq!(bm1368.nearest_hash_freq(HertzU64::MHz(5000)), HertzU64::MHz(0));
+    /// assert_eq!(Asic::hash_freq(&bm1368), BM1368::default().hash_freq());
+    /// ```
+    fn nearest_hash_freq(&self, target_freq: HertzU64) -> HertzU64 {
+        self.plls[BM1368_PLL_ID_HASH].nearest_achievable(
+            self.input_clock_freq,
+            BM1368_PLL_OUT_HASH,
+            target_freq,
+        )
+    }
+
+    /// ## Get the job rolling duration
+    ///
+    /// See [`BM1368::rolling_duration`].
+    fn rolling_duration(&self) -> Duration {
+        BM1368::rolling_duration(self)
+    }
+
+    /// ## Get the baudrate plan
+    ///
+    /// ### Example
+    /// ```
+    /// use bm1368::BM1368;
+    /// use bm13xx_asic::{Asic, BaudratePlan};
+    ///
+    /// let bm1368 = BM1368::default();
+    /// assert_eq!(
+    ///     bm1368.baudrate_plan(115_200),
+    ///     BaudratePlan {
+    ///         uses_pll: false,
+    ///         achievable_baud: 115_740
+    ///     }
+    /// );
+    /// assert_eq!(
+    ///     bm1368.baudrate_plan(1_000_000),
+    ///     BaudratePlan {
+    ///         uses_pll: false,
+    ///         achievable_baud: 1_041_666
+    ///     }
+    /// );
+    /// assert_eq!(
+    ///     bm1368.baudrate_plan(6_250_000),
+    ///     BaudratePlan {
+    ///         uses_pll: true,
+    ///         achievable_baud: 6_250_000
+    ///     }
+    /// );
+    /// ```
+    fn baudrate_plan(&self, baudrate: u32) -> BaudratePlan {
+        if baudrate <= self.input_clock_freq.raw() as u32 / 8 {
+            let fbase = self.input_clock_freq.raw() as u32;
+            let bt8d = (fbase / (8 * baudrate)) - 1;
+            BaudratePlan {
+                uses_pll: false,
+                achievable_baud: fbase / (8 * (bt8d + 1)),
+            }
+        } else {
+            // same UART PLL setting as set_baudrate_next
+            let mut pll1 = self.plls[BM1368_PLL_ID_UART];
+            pll1.lock()
+                .enable()
+                .set_fb_div(112)
+                .set_ref_div(1)
+                .set_post1_div(1)
+                .set_post2_div(1)
+                .set_out_div(BM1368_PLL_OUT_UART, 6);
+            let fbase = pll1
+                .frequency(self.input_clock_freq, BM1368_PLL_OUT_UART)
+                .raw() as u32;
+            let bt8d = (fbase / (2 * baudrate)) - 1;
+            BaudratePlan {
+                uses_pll: true,
+                achievable_baud: fbase / (2 * (bt8d + 1)),
+            }
+        }
+    }
+
+    /// ## Reset the Chip model to its power-on state
+    ///
+    /// The input clock frequency is kept as it is a property of the board.
+    ///
+    /// ### Example
+    /// ```
+    /// use bm1368::BM1368;
+    /// use bm13xx_asic::Asic;
+    /// use fugit::HertzU64;
+    ///
+    /// let mut bm1368 = BM1368::new_with_clk(HertzU64::MHz(26));
+    /// bm1368.set_chip_addr(2);
+    /// bm1368.enable_version_rolling(0x1fffe000);
+    /// bm1368.reset();
+    /// assert_eq!(bm1368.chip_addr, 0);
+    /// assert!(!bm1368.version_rolling_enabled);
+    /// assert_eq!(bm1368.input_clock_freq, HertzU64::MHz(26));
+    /// ```
+    fn reset(&mut self) {
+        *self = Self::new_with_clk(self.input_clock_freq);
+    }
+
+    /// ## Get the current sequence step
+    ///
+    /// ### Example
+    /// ```
+    /// use bm1368::BM1368;
+    /// use bm13xx_asic::{Asic, SequenceStep};
+    ///
+    /// let mut bm1368 = BM1368::default();
+    /// assert_eq!(bm1368.sequence_step(), SequenceStep::None);
+    /// bm1368.init_next(256);
+    /// assert!(bm1368.sequence_step().is_in_progress());
+    /// while bm1368.init_next(256).is_some() {}
+    /// assert_eq!(bm1368.sequence_step(), SequenceStep::None);
+    /// ```
+    fn sequence_step(&self) -> SequenceStep {
+        match self.chip_seq_steps.values().next() {
+            Some(step) if self.seq_step == SequenceStep::None => step.clone(),
+            _ => self.seq_step.clone(),
+        }
+    }
+
+    /// ## Get a register value from the model
+    ///
+    /// ### Example
+    /// ```
+    /// use bm1368::BM1368;
+    /// use bm13xx_asic::{register::ChipIdentification, Asic};
+    ///
+    /// let bm1368 = BM1368::default();
+    /// assert_eq!(ChipIdentification(bm1368.register(ChipIdentification::ADDR).unwrap()).chip_id(), 0x1368);
+    /// assert_eq!(bm1368.register(0xFF), None);
+    /// ```
+    fn register(&self, reg_addr: u8) -> Option<u32> {
+        self.registers.get(&reg_addr).copied()
+    }
+
+    /// ## Get a core register value from the chip model
+    ///
+    /// ### Example
+    /// ```
+    /// use bm1368::BM1368;
+    /// use bm13xx_asic::{core_register::HashClockCtrl, Asic};
+    ///
+    /// let bm1368 = BM1368::default();
+    /// assert_eq!(bm1368.core_register(HashClockCtrl::ID), Some(0x40));
+    /// assert_eq!(bm1368.core_register(0xFF), None);
+    /// ```
+    fn core_register(&self, core_reg_id: u8) -> Option<u8> {
+        self.core_registers.get(&core_reg_id).copied()
+    }
+
+    /// ## Write a register
+    ///
+    /// ### Example
+    /// ```
+    /// use bm1368::BM1368;
+    /// use bm13xx_asic::{register::*, Asic, CmdDelay};
+    /// use bm13xx_protocol::command::{Command, Destination};
+    ///
+    /// let mut bm1368 = BM1368::default();
+    /// let step = bm1368.write_reg_next(NonceReturnedTimeout::ADDR, 0x00fd_007d, Destination::All);
+    /// assert_eq!(step.cmd, Command::write_reg(NonceReturnedTimeout::ADDR, 0x00fd_007d, Destination::All));
+    /// assert_eq!(bm1368.register(NonceReturnedTimeout::ADDR), Some(0x00fd_007d));
+    /// ```
+    fn write_reg_next(&mut self, reg_addr: u8, reg_val: u32, dest: Destination) -> CmdDelay {
+        if let Some(val) = self.registers.get_mut(&reg_addr) {
+            *val = reg_val;
+        }
+        CmdDelay {
+            cmd: Command::write_reg(reg_addr, reg_val, dest),
+            delay_ms: 0,
+        }
+    }
+
+    /// ## Write a core register
+    ///
+    /// ### Example
+    /// ```
+    /// use bm1368::BM1368;
+    /// use bm13xx_asic::{core_register::HashClockCtrl, Asic};
+    /// use bm13xx_protocol::command::Destination;
+    ///
+    /// let mut bm1368 = BM1368::default();
+    /// let step = bm1368.write_core_reg_next(HashClockCtrl::ID, 0x40, Destination::All);
+    /// assert_eq!(step.cmd, [0x55, 0xAA, 0x51, 0x09, 0x00, 0x3C, 0x80, 0x00, 0x85, 0x40, 0x0C]);
+    /// assert_eq!(step.delay_ms, 0);
+    /// bm1368.write_core_reg_next(HashClockCtrl::ID, 0x41, Destination::All);
+    /// assert_eq!(bm1368.core_registers.get(&HashClockCtrl::ID).unwrap(), &0x41);
+    /// ```
+    fn write_core_reg_next(
+        &mut self,
+        core_reg_id: u8,
+        core_reg_val: u8,
+        dest: Destination,
+    ) -> CmdDelay {
+        if let Some(val) = self.core_registers.get_mut(&core_reg_id) {
+            *val = core_reg_val;
+        }
+        CmdDelay {
+            cmd: Command::write_reg(
+                CoreRegisterControl::ADDR,
+                CoreRegisterControl::write_core_reg_id(0, core_reg_id, core_reg_val),
+                dest,
+            ),
+            delay_ms: 0,
+        }
+    }
+
+    /// ## Init the Chip command list
+    ///
+    /// ### Example
+    /// ```
+    /// use bm1368::BM1368;
+    /// use bm13xx_asic::{core_register::*, register::*, Asic, CmdDelay};
+    ///
+    /// let mut bm1368 = BM1368::default();
+    /// // same as the esp-miner BM1368 init
+    /// assert_eq!(bm1368.init_next(256), Some(CmdDelay{cmd: [0x55, 0xaa, 0x51, 0x09, 0x00, 0x3c, 0x80, 0x00, 0x8B, 0x00, 0x12], delay_ms: 10}));
+    /// assert_eq!(bm1368.init_next(256), Some(CmdDelay{cmd: [0x55, 0xaa, 0x51, 0x09, 0x00, 0x3c, 0x80, 0x00, 0x80, 0x18, 0x1f], delay_ms: 10}));
+    /// assert_eq!(bm1368.init_next(256), Some(CmdDelay{cmd: [0x55, 0xaa, 0x51, 0x09, 0x00, 0x14, 0x00, 0x00, 0x00, 0xFF, 0x08], delay_ms: 10}));
+    /// assert_eq!(bm1368.init_next(256), Some(CmdDelay{cmd: [0x55, 0xaa, 0x51, 0x09, 0x00, 0x54, 0x00, 0x00, 0x00, 0x03, 0x1d], delay_ms: 0}));
+    /// assert_eq!(bm1368.init_next(256), None);
+    /// assert_eq!(bm1368.core_registers.get(&CoreReg11::ID).unwrap(), &0x00);
+    /// assert_eq!(bm1368.core_registers.get(&ClockDelayCtrlV2::ID).unwrap(), &0x18);
+    /// assert_eq!(bm1368.registers.get(&TicketMask::ADDR).unwrap(), &0x0000_00ff);
+    /// assert_eq!(bm1368.registers.get(&AnalogMuxControlV2::ADDR).unwrap(), &0x0000_0003);
+    /// ```
+    fn init_next(&mut self, diffculty: u32) -> Option<CmdDelay> {
+        match self.seq_step {
+            SequenceStep::Init(step) => match step {
+                0 => {
+                    self.seq_step = SequenceStep::Init(1);
+                    let clk_dly_ctrl =
+                        ClockDelayCtrlV2(*self.core_registers.get(&ClockDelayCtrlV2::ID).unwrap())
+                            .set_ccdly(0)
+                            .set_pwth(3)
+                            .disable_sweep_frequency_mode()
+                            .val();
+                    self.core_registers
+                        .insert(ClockDelayCtrlV2::ID, clk_dly_ctrl)
+                        .unwrap();
+                    Some(CmdDelay {
+                        cmd: Command::write_reg(
+                            CoreRegisterControl::ADDR,
+                            CoreRegisterControl::write_core_reg(0, ClockDelayCtrlV2(clk_dly_ctrl)),
+                            Destination::All,
+                        ),
+                        delay_ms: 10,
+                    })
+                }
+                1 => {
+                    self.seq_step = SequenceStep::Init(2);
+                    let tck_mask = TicketMask::from_difficulty(diffculty).val();
+                    self.registers.insert(TicketMask::ADDR, tck_mask).unwrap();
+                    Some(CmdDelay {
+                        cmd: Command::write_reg(TicketMask::ADDR, tck_mask, Destination::All),
+                        delay_ms: 10,
+                    })
+                }
+                2 => {
+                    self.seq_step = SequenceStep::Init(3);
+                    let ana_mux_ctrl =
+                        AnalogMuxControlV2(*self.registers.get(&AnalogMuxControlV2::ADDR).unwrap())
+                            .set_diode_vdd_mux_sel(3)
+                            .val();
+                    self.registers
+                        .insert(AnalogMuxControlV2::ADDR, ana_mux_ctrl)
+                        .unwrap();
+                    Some(CmdDelay {
+                        cmd: Command::write_reg(
+                            AnalogMuxControlV2::ADDR,
+                            ana_mux_ctrl,
+                            Destination::All,
+                        ),
+                        delay_ms: 0,
+                    })
+                }
+                3 => {
+                    self.seq_step = SequenceStep::None;
+                    None
+                }
+                _ => unreachable!(),
+            },
+            _ => {
+                // authorize an Init sequence start whatever the current step was
+                self.seq_step = SequenceStep::Init(0);
+                let reg11 = CoreReg11(*self.core_registers.get(&CoreReg11::ID).unwrap()).val();
+                self.core_registers.insert(CoreReg11::ID, reg11).unwrap();
+                Some(CmdDelay {
+                    cmd: Command::write_reg(
+                        CoreRegisterControl::ADDR,
+                        CoreRegisterControl::write_core_reg(0, CoreReg11(reg11)),
+                        Destination::All,
+                    ),
+                    delay_ms: 10,
+                })
+            }
+        }
+    }
+
+    /// ## Send Baudrate command list
+    ///
+    /// ### Example
+    /// ```
+    /// use bm1368::{BM1368, BM1368_PLL_ID_UART};
+    /// use bm13xx_asic::{register::*, Asic, CmdDelay};
+    ///
+    /// let mut bm1368 = BM1368::default();
+    /// // same sequence as the BM1366
+    /// assert_eq!(bm1368.set_baudrate_next(1_000_000, 11, 10, 2), Some(CmdDelay{cmd: [0x55, 0xaa, 0x51, 0x09, 0x00, 0x58, 0x02, 0x11, 0x11, 0x11, 0x06], delay_ms: 0}));
+    /// assert_eq!(bm1368.set_baudrate_next(1_000_000, 11, 10, 2), Some(CmdDelay{cmd: [0x55, 0xaa, 0x41, 0x09, 0xDA, 0x58, 0x02, 0x11, 0xf1, 0x11, 0x1c], delay_ms: 0}));
+    /// assert_eq!(bm1368.set_baudrate_next(1_000_000, 11, 10, 2), Some(CmdDelay{cmd: [0x55, 0xaa, 0x41, 0x09, 0xC6, 0x58, 0x02, 0x11, 0xf1, 0x11, 0x17], delay_ms: 0}));
+    /// assert_eq!(bm1368.set_baudrate_next(1_000_000, 11, 10, 2), Some(CmdDelay{cmd: [0x55, 0xaa, 0x41, 0x09, 0xB2, 0x58, 0x02, 0x11, 0xf1, 0x11, 0x05], delay_ms: 0}));
+    /// assert_eq!(bm1368.set_baudrate_next(1_000_000, 11, 10, 2), Some(CmdDelay{cmd: [0x55, 0xaa, 0x41, 0x09, 0x9E, 0x58, 0x02, 0x11, 0xf1, 0x11, 0x0b], delay_ms: 0}));
+    /// assert_eq!(bm1368.set_baudrate_next(1_000_000, 11, 10, 2), Some(CmdDelay{cmd: [0x55, 0xaa, 0x41, 0x09, 0x8A, 0x58, 0x02, 0x11, 0xf1, 0x11, 0x13], delay_ms: 0}));
+    /// assert_eq!(bm1368.set_baudrate_next(1_000_000, 11, 10, 2), Some(CmdDelay{cmd: [0x55, 0xaa, 0x41, 0x09, 0x76, 0x58, 0x02, 0x11, 0xf1, 0x11, 0x0a], delay_ms: 0}));
+    /// assert_eq!(bm1368.set_baudrate_next(1_000_000, 11, 10, 2), Some(CmdDelay{cmd: [0x55, 0xaa, 0x41, 0x09, 0x62, 0x58, 0x02, 0x11, 0xf1, 0x11, 0x12], delay_ms: 0}));
+    /// assert_eq!(bm1368.set_baudrate_next(1_000_000, 11, 10, 2), Some(CmdDelay{cmd: [0x55, 0xaa, 0x41, 0x09, 0x4E, 0x58, 0x02, 0x11, 0xf1, 0x11, 0x1c], delay_ms: 0}));
+    /// assert_eq!(bm1368.set_baudrate_next(1_000_000, 11, 10, 2), Some(CmdDelay{cmd: [0x55, 0xaa, 0x41, 0x09, 0x3A, 0x58, 0x02, 0x11, 0xf1, 0x11, 0x0e], delay_ms: 0}));
+    /// assert_eq!(bm1368.set_baudrate_next(1_000_000, 11, 10, 2), Some(CmdDelay{cmd: [0x55, 0xaa, 0x41, 0x09, 0x26, 0x58, 0x02, 0x11, 0xf1, 0x11, 0x05], delay_ms: 0}));
+    /// assert_eq!(bm1368.set_baudrate_next(1_000_000, 11, 10, 2), Some(CmdDelay{cmd: [0x55, 0xaa, 0x41, 0x09, 0x12, 0x58, 0x02, 0x11, 0xf1, 0x11, 0x1b], delay_ms: 0}));
+    /// assert_eq!(bm1368.set_baudrate_next(1_000_000, 11, 10, 2), Some(CmdDelay{cmd: [0x55, 0xaa, 0x41, 0x09, 0xC8, 0x2c, 0x00, 0x18, 0x00, 0x03, 0x17], delay_ms: 0}));
+    /// assert_eq!(bm1368.set_baudrate_next(1_000_000, 11, 10, 2), Some(CmdDelay{cmd: [0x55, 0xaa, 0x41, 0x09, 0xDA, 0x2c, 0x00, 0x18, 0x00, 0x03, 0x0b], delay_ms: 0}));
+    /// assert_eq!(bm1368.set_baudrate_next(1_000_000, 11, 10, 2), Some(CmdDelay{cmd: [0x55, 0xaa, 0x41, 0x09, 0xB4, 0x2c, 0x00, 0x22, 0x00, 0x03, 0x13], delay_ms: 0}));
+    /// assert_eq!(bm1368.set_baudrate_next(1_000_000, 11, 10, 2), Some(CmdDelay{cmd: [0x55, 0xaa, 0x41, 0x09, 0xC6, 0x2c, 0x00, 0x22, 0x00, 0x03, 0x05], delay_ms: 0}));
+    /// assert_eq!(bm1368.set_baudrate_next(1_000_000, 11, 10, 2), Some(CmdDelay{cmd: [0x55, 0xaa, 0x41, 0x09, 0xA0, 0x2c, 0x00, 0x2c, 0x00, 0x03, 0x0f], delay_ms: 0}));
+    /// assert_eq!(bm1368.set_baudrate_next(1_000_000, 11, 10, 2), Some(CmdDelay{cmd: [0x55, 0xaa, 0x41, 0x09, 0xB2, 0x2c, 0x00, 0x2c, 0x00, 0x03, 0x13], delay_ms: 0}));
+    /// assert_eq!(bm1368.set_baudrate_next(1_000_000, 11, 10, 2), Some(CmdDelay{cmd: [0x55, 0xaa, 0x41, 0x09, 0x8C, 0x2c, 0x00, 0x36, 0x00, 0x03, 0x13], delay_ms: 0}));
+    /// assert_eq!(bm1368.set_baudrate_next(1_000_000, 11, 10, 2), Some(CmdDelay{cmd: [0x55, 0xaa, 0x41, 0x09, 0x9E, 0x2c, 0x00, 0x36, 0x00, 0x03, 0x0f], delay_ms: 0}));
+    /// assert_eq!(bm1368.set_baudrate_next(1_000_000, 11, 10, 2), Some(CmdDelay{cmd: [0x55, 0xaa, 0x41, 0x09, 0x78, 0x2c, 0x00, 0x40, 0x00, 0x03, 0x06], delay_ms: 0}));
+    /// assert_eq!(bm1368.set_baudrate_next(1_000_000, 11, 10, 2), Some(CmdDelay{cmd: [0x55, 0xaa, 0x41, 0x09, 0x8A, 0x2c, 0x00, 0x40, 0x00, 0x03, 0x08], delay_ms: 0}));
+    /// assert_eq!(bm1368.set_baudrate_next(1_000_000, 11, 10, 2), Some(CmdDelay{cmd: [0x55, 0xaa, 0x41, 0x09, 0x64, 0x2c, 0x00, 0x4a, 0x00, 0x03, 0x06], delay_ms: 0}));
+    /// assert_eq!(bm1368.set_baudrate_next(1_000_000, 11, 10, 2), Some(CmdDelay{cmd: [0x55, 0xaa, 0x41, 0x09, 0x76, 0x2c, 0x00, 0x4a, 0x00, 0x03, 0x1a], delay_ms: 0}));
+    /// assert_eq!(bm1368.set_baudrate_next(1_000_000, 11, 10, 2), Some(CmdDelay{cmd: [0x55, 0xaa, 0x41, 0x09, 0x50, 0x2c, 0x00, 0x54, 0x00, 0x03, 0x05], delay_ms: 0}));
+    /// assert_eq!(bm1368.set_baudrate_next(1_000_000, 11, 10, 2), Some(CmdDelay{cmd: [0x55, 0xaa, 0x41, 0x09, 0x62, 0x2c, 0x00, 0x54, 0x00, 0x03, 0x1f], delay_ms: 0}));
+    /// assert_eq!(bm1368.set_baudrate_next(1_000_000, 11, 10, 2), Some(CmdDelay{cmd: [0x55, 0xaa, 0x41, 0x09, 0x3c, 0x2c, 0x00, 0x5e, 0x00, 0x03, 0x0c], delay_ms: 0}));
+    /// assert_eq!(bm1368.set_baudrate_next(1_000_000, 11, 10, 2), Some(CmdDelay{cmd: [0x55, 0xaa, 0x41, 0x09, 0x4e, 0x2c, 0x00, 0x5e, 0x00, 0x03, 0x1a], delay_ms: 0}));
+    /// assert_eq!(bm1368.set_baudrate_next(1_000_000, 11, 10, 2), Some(CmdDelay{cmd: [0x55, 0xaa, 0x41, 0x09, 0x28, 0x2c, 0x00, 0x68, 0x00, 0x03, 0x00], delay_ms: 0}));
+    /// assert_eq!(bm1368.set_baudrate_next(1_000_000, 11, 10, 2), Some(CmdDelay{cmd: [0x55, 0xaa, 0x41, 0x09, 0x3a, 0x2c, 0x00, 0x68, 0x00, 0x03, 0x1c], delay_ms: 0}));
+    /// assert_eq!(bm1368.set_baudrate_next(1_000_000, 11, 10, 2), Some(CmdDelay{cmd: [0x55, 0xaa, 0x41, 0x09, 0x14, 0x2c, 0x00, 0x72, 0x00, 0x03, 0x1f], delay_ms: 0}));
+    /// assert_eq!(bm1368.set_baudrate_next(1_000_000, 11, 10, 2), Some(CmdDelay{cmd: [0x55, 0xaa, 0x41, 0x09, 0x26, 0x2c, 0x00, 0x72, 0x00, 0x03, 0x05], delay_ms: 0}));
+    /// assert_eq!(bm1368.set_baudrate_next(1_000_000, 11, 10, 2), Some(CmdDelay{cmd: [0x55, 0xaa, 0x41, 0x09, 0x00, 0x2c, 0x00, 0x7c, 0x00, 0x03, 0x03], delay_ms: 0}));
+    /// assert_eq!(bm1368.set_baudrate_next(1_000_000, 11, 10, 2), Some(CmdDelay{cmd: [0x55, 0xaa, 0x41, 0x09, 0x12, 0x2c, 0x00, 0x7c, 0x00, 0x03, 0x1f], delay_ms: 130}));
+    /// assert_eq!(bm1368.set_baudrate_next(1_000_000, 11, 10, 2), Some(CmdDelay{cmd: [0x55, 0xaa, 0x51, 0x09, 0x00, 0x28, 0x11, 0x30, 0x02, 0x00, 0x03], delay_ms: 200}));
+    /// assert_eq!(bm1368.set_baudrate_next(1_000_000, 11, 10, 2), None);
+    /// assert!(!bm1368.plls[BM1368_PLL_ID_UART].enabled());
+    /// assert_eq!(bm1368.registers.get(&IoDriverStrenghtConfiguration::ADDR).unwrap(), &0x0211_1111);
+    /// assert_eq!(bm1368.registers.get(&FastUARTConfigurationV2::ADDR).unwrap(), &0x1130_0200);
+    /// assert_eq!(bm1368.set_baudrate_next(6_250_000, 1, 10, 2), Some(CmdDelay{cmd: [0x55, 0xaa, 0x51, 0x09, 0x00, 0x58, 0x02, 0x11, 0x11, 0x11, 0x06], delay_ms: 0}));
+    /// assert_eq!(bm1368.set_baudrate_next(6_250_000, 1, 10, 2), Some(CmdDelay{cmd: [0x55, 0xaa, 0x41, 0x09, 0x12, 0x58, 0x02, 0x11, 0xf1, 0x11, 0x1b], delay_ms: 0}));
+    /// assert_eq!(bm1368.set_baudrate_next(6_250_000, 1, 10, 2), Some(CmdDelay{cmd: [0x55, 0xaa, 0x41, 0x09, 0x00, 0x2c, 0x00, 0x18, 0x00, 0x03, 0x10], delay_ms: 0}));
+    /// assert_eq!(bm1368.set_baudrate_next(6_250_000, 1, 10, 2), Some(CmdDelay{cmd: [0x55, 0xaa, 0x41, 0x09, 0x12, 0x2c, 0x00, 0x18, 0x00, 0x03, 0x0c], delay_ms: 130}));
+    /// assert_eq!(bm1368.set_baudrate_next(6_250_000, 1, 10, 2), Some(CmdDelay{cmd: [0x55, 0xaa, 0x51, 0x09, 0x00, 0x60, 0xc0, 0x70, 0x01, 0x11, 26], delay_ms: 0}));
+    /// assert_eq!(bm1368.set_baudrate_next(6_250_000, 1, 10, 2), Some(CmdDelay{cmd: [0x55, 0xaa, 0x51, 0x09, 0x00, 0x28, 0x15, 0x60, 0x07, 0x00, 19], delay_ms: 200}));
+    /// assert_eq!(bm1368.set_baudrate_next(6_250_000, 1, 10, 2), None);
+    /// assert!(bm1368.plls[BM1368_PLL_ID_UART].enabled());
+    /// assert_eq!(bm1368.registers.get(&IoDriverStrenghtConfiguration::ADDR).unwrap(), &0x0211_1111);
+    /// assert_eq!(bm1368.registers.get(&PLL1Parameter::ADDR).unwrap(), &0xC070_0111);
+    /// assert_eq!(bm1368.registers.get(&FastUARTConfigurationV2::ADDR).unwrap(), &0x1560_0700);
+    /// ```
+    fn set_baudrate_next(
+        &mut self,
+        baudrate: u32,
+        chain_domain_cnt: u8,
+        domain_asic_cnt: u8,
+        asic_addr_interval: u16,
+    ) -> Option<CmdDelay> {
+        self.baudrate_next(
+            baudrate,
+            chain_domain_cnt as usize,
+            |_| domain_asic_cnt,
+            asic_addr_interval,
+        )
+    }
+
+    /// ## Send Baudrate command list for a chain with voltage domains of different sizes
+    ///
+    /// ### Example
+    /// ```
+    /// use bm1368::BM1368;
+    /// use bm13xx_asic::{register::*, Asic};
+    ///
+    /// let mut bm1368 = BM1368::default();
+    /// // 13 chips spread over 4 voltage domains, the last one holding 4 chips
+    /// let mut writes = Vec::new();
+    /// while let Some(step) = bm1368.set_baudrate_next_domains(1_000_000, &[3, 3, 3, 4], 2) {
+    ///     if step.cmd[2] == 0x41 {
+    ///         // (register, chip address)
+    ///         writes.push((step.cmd[5], step.cmd[4]));
+    ///     }
+    /// }
+    /// assert_eq!(writes[..5], [(0x58, 24), (0x58, 16), (0x58, 10), (0x58, 4), (0x2c, 18)]);
+    /// assert_eq!(writes[5..], [(0x2c, 24), (0x2c, 12), (0x2c, 16), (0x2c, 6), (0x2c, 10), (0x2c, 0), (0x2c, 4)]);
+    ///
+    /// // a chain too long for the UART relay GAP_CNT field saturates it
+    /// let mut gaps = Vec::new();
+    /// while let Some(step) = bm1368.set_baudrate_next_domains(1_000_000, &[255; 300], 1) {
+    ///     if step.cmd[2] == 0x41 && step.cmd[5] == UARTRelay::ADDR {
+    ///         gaps.push(u16::from_be_bytes([step.cmd[6], step.cmd[7]]));
+    ///     }
+    /// }
+    /// assert_eq!(gaps[0], 255 + 14); // last domain
+    /// assert!(gaps.contains(&0xffff)); // first domain, 300 * 255 + 14 overflows
+    /// ```
+    fn set_baudrate_next_domains(
+        &mut self,
+        baudrate: u32,
+        domain_asic_cnts: &[u8],
+        asic_addr_interval: u16,
+    ) -> Option<CmdDelay> {
+        self.baudrate_next(
+            baudrate,
+            domain_asic_cnts.len(),
+            |dom| domain_asic_cnts[dom],
+            asic_addr_interval,
+        )
+    }
+
+    /// ## Reset the Chip Cores command list
+    ///
+    /// ### Example
+    /// ```
+    /// use bm1368::BM1368;
+    /// use bm13xx_asic::{core_register::*, register::*, Asic, CmdDelay};
+    /// use bm13xx_protocol::command::Destination;
+    ///
+    /// let mut bm1368 = BM1368::default();
+    /// assert_eq!(bm1368.reset_core_next(Destination::All), Some(CmdDelay{cmd: [0x55, 0xaa, 0x51, 0x09, 0x00, 0xa8, 0x00, 0x07, 0x00, 0x00, 0x03], delay_ms: 0}));
+    /// assert_eq!(bm1368.reset_core_next(Destination::All), Some(CmdDelay{cmd: [0x55, 0xaa, 0x51, 0x09, 0x00, 0x18, 0xf0, 0x00, 0xc1, 0x00, 0x04], delay_ms: 100}));
+    /// assert_eq!(bm1368.reset_core_next(Destination::All), None);
+    /// let mut bm1368 = BM1368::default();
+    /// assert_eq!(bm1368.reset_core_next(Destination::Chip(0)), Some(CmdDelay{cmd: [0x55, 0xaa, 0x41, 0x09, 0x00, 0xa8, 0x00, 0x07, 0x01, 0xf0, 0x15], delay_ms: 10}));
+    /// assert_eq!(bm1368.reset_core_next(Destination::Chip(0)), Some(CmdDelay{cmd: [0x55, 0xaa, 0x41, 0x09, 0x00, 0x18, 0xf0, 0x00, 0xc1, 0x00, 0x0c], delay_ms: 10}));
+    /// assert_eq!(bm1368.reset_core_next(Destination::Chip(0)), Some(CmdDelay{cmd: [0x55, 0xaa, 0x41, 0x09, 0x00, 0x3c, 0x80, 0x00, 0x8B, 0x00, 0x1a], delay_ms: 10}));
+    /// assert_eq!(bm1368.reset_core_next(Destination::Chip(0)), Some(CmdDelay{cmd: [0x55, 0xaa, 0x41, 0x09, 0x00, 0x3c, 0x80, 0x00, 0x80, 0x18, 0x17], delay_ms: 10}));
+    /// assert_eq!(bm1368.reset_core_next(Destination::Chip(0)), Some(CmdDelay{cmd: [0x55, 0xaa, 0x41, 0x09, 0x00, 0x3c, 0x80, 0x00, 0x82, 0xaa, 0x05], delay_ms: 10}));
+    /// assert_eq!(bm1368.reset_core_next(Destination::Chip(0)), None);
+    /// ```
+    fn reset_core_next(&mut self, dest: Destination) -> Option<CmdDelay> {
+        if dest == Destination::All {
+            match self.seq_step {
+                SequenceStep::ResetCore(step) => match step {
+                    0 => {
+                        self.seq_step = SequenceStep::ResetCore(1);
+                        let misc =
+                            MiscControlV2(*self.registers.get(&MiscControlV2::ADDR).unwrap())
+                                .set_core_return_nonce(0xf)
+                                .set_b27_26(0)
+                                .set_b25_24(0)
+                                .set_b19_16(0)
+                                .val();
+                        self.registers.insert(MiscControlV2::ADDR, misc).unwrap();
+                        Some(CmdDelay {
+                            cmd: Command::write_reg(MiscControlV2::ADDR, misc, dest),
+                            delay_ms: 100,
+                        })
+                    }
+                    1 => {
+                        self.seq_step = SequenceStep::None;
+                        None
+                    }
+                    _ => unreachable!(),
+                },
+                _ => {
+                    // authorize a ResetCore sequence start whatever the current step was
+                    self.seq_step = SequenceStep::ResetCore(0);
+                    let reg_a8 = RegA8(*self.registers.get(&RegA8::ADDR).unwrap())
+                        .clr_b10()
+                        .disable_core_reset()
+                        .set_chip_core_sel(0)
+                        .set_all_core_sel(0)
+                        .val();
+                    self.registers.insert(RegA8::ADDR, reg_a8).unwrap();
+                    Some(CmdDelay {
+                        cmd: Command::write_reg(RegA8::ADDR, reg_a8, dest),
+                        delay_ms: 0,
+                    })
+                }
+            }
+        } else {
+            match self.seq_step {
+                SequenceStep::ResetCore(step) => match step {
+                    0 => {
+                        self.seq_step = SequenceStep::ResetCore(1);
+                        let misc =
+                            MiscControlV2(*self.registers.get(&MiscControlV2::ADDR).unwrap())
+                                .set_core_return_nonce(0xf)
+                                .set_b27_26(0)
+                                .set_b25_24(0)
+                                .set_b19_16(0)
+                                .val();
+                        self.registers.insert(MiscControlV2::ADDR, misc).unwrap();
+                        Some(CmdDelay {
+                            cmd: Command::write_reg(MiscControlV2::ADDR, misc, dest),
+                            delay_ms: 10,
+                        })
+                    }
+                    1 => {
+                        self.seq_step = SequenceStep::ResetCore(2);
+                        let core_reg_11 = 0x00;
+                        self.core_registers
+                            .insert(CoreReg11::ID, core_reg_11)
+                            .unwrap();
+                        Some(CmdDelay {
+                            cmd: Command::write_reg(
+                                CoreRegisterControl::ADDR,
+                                CoreRegisterControl::write_core_reg(0, CoreReg11(core_reg_11)),
+                                dest,
+                            ),
+                            delay_ms: 10,
+                        })
+                    }
+                    2 => {
+                        self.seq_step = SequenceStep::ResetCore(3);
+                        let clk_dly_ctrl = ClockDelayCtrlV2(
+                            *self.core_registers.get(&ClockDelayCtrlV2::ID).unwrap(),
+                        )
+                        .set_ccdly(0)
+                        .set_pwth(3)
+                        .disable_sweep_frequency_mode()
+                        .val();
+                        self.core_registers
+                            .insert(ClockDelayCtrlV2::ID, clk_dly_ctrl)
+                            .unwrap();
+                        Some(CmdDelay {
+                            cmd: Command::write_reg(
+                                CoreRegisterControl::ADDR,
+                                CoreRegisterControl::write_core_reg(
+                                    0,
+                                    ClockDelayCtrlV2(clk_dly_ctrl),
+                                ),
+                                dest,
+                            ),
+                            delay_ms: 10,
+                        })
+                    }
+                    3 => {
+                        self.seq_step = SequenceStep::ResetCore(4);
+                        let core_reg2 = 0xAA;
+                        self.core_registers.insert(CoreReg2::ID, core_reg2).unwrap();
+                        Some(CmdDelay {
+                            cmd: Command::write_reg(
+                                CoreRegisterControl::ADDR,
+                                CoreRegisterControl::write_core_reg(0, CoreReg2(core_reg2)),
+                                dest,
+                            ),
+                            delay_ms: 10,
+                        })
+                    }
+                    4 => {
+                        self.seq_step = SequenceStep::None;
+                        None
+                    }
+                    _ => unreachable!(),
+                },
+                _ => {
+                    // authorize a ResetCore sequence start whatever the current step was
+                    self.seq_step = SequenceStep::ResetCore(0);
+                    let reg_a8 = RegA8(*self.registers.get(&RegA8::ADDR).unwrap())
+                        .clr_b10()
+                        .enable_core_reset()
+                        .set_chip_core_sel(0xf)
+                        .set_all_core_sel(0)
+                        .val();
+                    self.registers.insert(RegA8::ADDR, reg_a8).unwrap();
+                    Some(CmdDelay {
+                        cmd: Command::write_reg(RegA8::ADDR, reg_a8, dest),
+                        delay_ms: 10,
+                    })
+                }
+            }
+        }
+    }
+
+    /// ## Send Hash Frequency command list
+    ///
+    /// ### Example
+    /// ```
+    /// use bm1368::{BM1368, BM1368_PLL_ID_HASH};
+    /// use bm13xx_asic::{register::*, Asic, CmdDelay};
+    /// use bm13xx_protocol::command::Destination;
+    /// use fugit::HertzU64;
+    ///
+    /// let mut bm1368 = BM1368::default();
+    /// assert_eq!(bm1368.set_hash_freq_next(HertzU64::MHz(75)), Some(CmdDelay{cmd: [0x55, 0xaa, 0x51, 0x09, 0x00, 0x70, 0x00, 0x00, 0x00, 0x00, 24], delay_ms: 2}));
+    /// assert_eq!(bm1368.set_hash_freq_next(HertzU64::MHz(75)), Some(CmdDelay{cmd: [0x55, 0xaa, 0x51, 0x09, 0x00, 0x08, 0xc0, 0x5f, 0x01, 0x65, 0x1a], delay_ms: 400}));
+    /// assert_eq!(bm1368.set_hash_freq_next(HertzU64::MHz(75)), Some(CmdDelay{cmd: [0x55, 0xaa, 0x51, 0x09, 0x00, 0x08, 0xd0, 0x69, 0x01, 0x65, 0x0a], delay_ms: 400}));
+    /// assert_eq!(bm1368.set_hash_freq_next(HertzU64::MHz(75)), Some(CmdDelay{cmd: [0x55, 0xaa, 0x51, 0x09, 0x00, 0x08, 0xd0, 0x74, 0x01, 0x65, 0x1a], delay_ms: 400}));
+    /// assert_eq!(bm1368.set_hash_freq_next(HertzU64::MHz(75)), Some(CmdDelay{cmd: [0x55, 0xaa, 0x51, 0x09, 0x00, 0x08, 0xc0, 0xa8, 0x02, 0x63, 0x14], delay_ms: 400}));
+    /// assert_eq!(bm1368.set_hash_freq_next(HertzU64::MHz(75)), None);
+    /// assert_eq!(bm1368.plls[BM1368_PLL_ID_HASH].parameter(), 0xc0a8_0263);
+    ///
+    /// // ramp a single chip back up
+    /// let mut bm1368 = BM1368::default();
+    /// let mut regs = Vec::new();
+    /// while let Some(step) = bm1368.set_hash_freq_next_dest(HertzU64::MHz(75), Destination::Chip(4)) {
+    ///     assert_eq!(step.cmd[2..5], [0x41, 0x09, 0x04]);
+    ///     regs.push(step.cmd[5]);
+    /// }
+    /// assert_eq!(regs, [PLL0Divider::ADDR, PLL0Parameter::ADDR, PLL0Parameter::ADDR, PLL0Parameter::ADDR, PLL0Parameter::ADDR]);
+    /// ```
+    fn set_hash_freq_next_dest(
+        &mut self,
+        target_freq: HertzU64,
+        dest: Destination,
+    ) -> Option<CmdDelay> {
+        match self.hash_seq_step(dest) {
+            SequenceStep::HashFreq(1) => {
+                self.set_hash_seq_step(dest, SequenceStep::None);
+                None
+            }
+            SequenceStep::HashFreq(_) => {
+                let mut pll = self.hash_pll(dest);
+                let current = pll.frequency(self.input_clock_freq, BM1368_PLL_OUT_HASH);
+                let ramp_step = HertzU64::kHz(6250);
+                // ramp up or down, the last step sets the target exactly, even if not
+                // aligned on the ramp
+                let last = if current < target_freq {
+                    current + ramp_step >= target_freq
+                } else {
+                    current <= target_freq + ramp_step
+                };
+                let freq = if last {
+                    // same dividers as a direct change, whatever the ramp went through
+                    pll.set_frequency(
+                        self.input_clock_freq,
+                        BM1368_PLL_OUT_HASH,
+                        target_freq,
+                        false,
+                    );
+                    target_freq
+                } else {
+                    let freq = if current < target_freq {
+                        current + ramp_step
+                    } else {
+                        current - ramp_step
+                    };
+                    // only adjust the feedback divider while the VCO stays in its window
+                    pll.bump_frequency(
+                        self.input_clock_freq,
+                        BM1368_PLL_OUT_HASH,
+                        freq.raw() as i64 - current.raw() as i64,
+                        false,
+                    );
+                    freq
+                };
+                self.set_hash_pll(dest, pll);
+                if last {
+                    self.set_hash_seq_step(dest, SequenceStep::HashFreq(1));
+                }
+                Some(CmdDelay {
+                    cmd: Command::write_reg(PLL0Parameter::ADDR, pll.parameter(), dest),
+                    delay_ms: if freq > HertzU64::MHz(380) { 2300 } else { 400 },
+                })
+            }
+            _ => {
+                // authorize a SetHashFreq sequence start whatever the current step was
+                self.set_hash_seq_step(dest, SequenceStep::HashFreq(0));
+                let mut pll = self.hash_pll(dest);
+                pll.set_out_div(BM1368_PLL_OUT_HASH, 0);
+                self.set_hash_pll(dest, pll);
+                Some(CmdDelay {
+                    cmd: Command::write_reg(PLL0Divider::ADDR, pll.divider(), dest),
+                    delay_ms: 2,
+                })
+            }
+        }
+    }
+
+    /// ## Send Hash Frequency command list, without ramping
+    ///
+    /// ### Example
+    /// ```
+    /// use bm1368::{BM1368, BM1368_PLL_ID_HASH, BM1368_PLL_OUT_HASH};
+    /// use bm13xx_asic::{register::*, Asic, CmdDelay};
+    /// use fugit::HertzU64;
+    ///
+    /// let mut bm1368 = BM1368::default();
+    /// assert_eq!(bm1368.set_hash_freq_direct_next(HertzU64::MHz(75)), Some(CmdDelay{cmd: [0x55, 0xaa, 0x51, 0x09, 0x00, 0x08, 0xc0, 0xa8, 0x02, 0x63, 0x14], delay_ms: 400}));
+    /// assert_eq!(bm1368.set_hash_freq_direct_next(HertzU64::MHz(75)), None);
+    /// assert_eq!(bm1368.plls[BM1368_PLL_ID_HASH].parameter(), 0xc0a8_0263);
+    /// // the hash PLL output divider is cleared first if needed
+    /// bm1368.plls[BM1368_PLL_ID_HASH].set_out_div(BM1368_PLL_OUT_HASH, 1);
+    /// assert_eq!(bm1368.set_hash_freq_direct_next(HertzU64::MHz(75)), Some(CmdDelay{cmd: [0x55, 0xaa, 0x51, 0x09, 0x00, 0x70, 0x00, 0x00, 0x00, 0x00, 24], delay_ms: 2}));
+    /// assert_eq!(bm1368.set_hash_freq_direct_next(HertzU64::MHz(75)), Some(CmdDelay{cmd: [0x55, 0xaa, 0x51, 0x09, 0x00, 0x08, 0xc0, 0xa8, 0x02, 0x63, 0x14], delay_ms: 400}));
+    /// assert_eq!(bm1368.set_hash_freq_direct_next(HertzU64::MHz(75)), None);
+    /// ```
+    fn set_hash_freq_direct_next(&mut self, target_freq: HertzU64) -> Option<CmdDelay> {
+        match self.seq_step {
+            SequenceStep::HashFreq(1) => {
+                self.seq_step = SequenceStep::None;
+                None
+            }
+            SequenceStep::HashFreq(_) => {
+                self.seq_step = SequenceStep::HashFreq(1);
+                self.set_hash_freq(target_freq);
+                self.chip_hash_plls.clear();
+                self.sync_plls_to_registers();
+                Some(CmdDelay {
+                    cmd: Command::write_reg(
+                        PLL0Parameter::ADDR,
+                        self.plls[BM1368_PLL_ID_HASH].parameter(),
+                        Destination::All,
+                    ),
+                    delay_ms: if target_freq > HertzU64::MHz(380) {
+                        2300
+                    } else {
+                        400
+                    },
+                })
+            }
+            _ => {
+                // authorize a SetHashFreq sequence start whatever the current step was
+                self.seq_step = SequenceStep::HashFreq(0);
+                if self.plls[BM1368_PLL_ID_HASH].out_div(BM1368_PLL_OUT_HASH) == 0 {
+                    return self.set_hash_freq_direct_next(target_freq);
+                }
+                self.plls[BM1368_PLL_ID_HASH].set_out_div(BM1368_PLL_OUT_HASH, 0);
+                self.sync_plls_to_registers();
+                Some(CmdDelay {
+                    cmd: Command::write_reg(
+                        PLL0Divider::ADDR,
+                        self.plls[BM1368_PLL_ID_HASH].divider(),
+                        Destination::All,
+                    ),
+                    delay_ms: 2,
+                })
+            }
+        }
+    }
+
+    /// ## Send Enable Version Rolling command list
+    ///
+    /// ### Example
+    /// ```
+    /// use bm1368::BM1368;
+    /// use bm13xx_asic::{Asic, CmdDelay};
+    ///
+    /// let mut bm1368 = BM1368::default();
+    /// assert_eq!(bm1368.set_version_rolling_next(0x1fff_e000), Some(CmdDelay{cmd: [0x55, 0xaa, 0x51, 0x09, 0x00, 0x10, 0x00, 0x00, 0x15, 0xa4, 0x0a], delay_ms: 1}));
+    /// assert_eq!(bm1368.set_version_rolling_next(0x1fff_e000), Some(CmdDelay{cmd: [0x55, 0xaa, 0x51, 0x09, 0x00, 0xa4, 0x90, 0x00, 0xff, 0xff, 0x1c], delay_ms: 1}));
+    /// assert_eq!(bm1368.set_version_rolling_next(0x1fff_e000), None);
+    /// ```
+    fn set_version_rolling_next(&mut self, mask: u32) -> Option<CmdDelay> {
+        match self.seq_step {
+            SequenceStep::VersionRolling(step) => match step {
+                0 => {
+                    self.seq_step = SequenceStep::VersionRolling(1);
+                    let vers_roll =
+                        VersionRolling(*self.registers.get(&VersionRolling::ADDR).unwrap())
+                            .enable()
+                            .set_mask(mask)
+                            .val();
+                    self.registers
+                        .insert(VersionRolling::ADDR, vers_roll)
+                        .unwrap();
+                    self.enable_version_rolling(mask);
+                    Some(CmdDelay {
+                        cmd: Command::write_reg(VersionRolling::ADDR, vers_roll, Destination::All),
+                        delay_ms: 1,
+                    })
+                }
+                1 => {
+                    self.seq_step = SequenceStep::None;
+                    None
+                }
+                _ => unreachable!(),
+            },
+            _ => {
+                // authorize a VersionRolling sequence start whatever the current step was
+                self.seq_step = SequenceStep::VersionRolling(0);
+                let hcn = 0x0000_15a4;
+                self.registers
+                    .insert(HashCountingNumber::ADDR, hcn)
+                    .unwrap();
+                Some(CmdDelay {
+                    cmd: Command::write_reg(HashCountingNumber::ADDR, hcn, Destination::All),
+                    delay_ms: 1,
+                })
+            }
+        }
+    }
+
+    /// ## Send Version Mask command list
+    ///
+    /// ### Example
+    /// ```
+    /// use bm1368::BM1368;
+    /// use bm13xx_asic::{register::VersionRolling, Asic};
+    ///
+    /// let mut bm1368 = BM1368::default();
+    /// while bm1368.set_version_rolling_next(0x1fff_e000).is_some() {}
+    /// assert!(bm1368.set_version_mask_next(0x1ffe_0000).is_some());
+    /// assert_eq!(bm1368.set_version_mask_next(0x1ffe_0000), None);
+    /// assert_eq!(bm1368.registers.get(&VersionRolling::ADDR).unwrap(), &0x9000_fff0);
+    /// assert_eq!(bm1368.version_mask, 0x1ffe_0000);
+    /// assert!(bm1368.version_rolling_enabled);
+    /// ```
+    fn set_version_mask_next(&mut self, mask: u32) -> Option<CmdDelay> {
+        match self.seq_step {
+            SequenceStep::VersionMask(step) => match step {
+                0 => {
+                    self.seq_step = SequenceStep::None;
+                    None
+                }
+                _ => unreachable!(),
+            },
+            _ => {
+                // authorize a VersionMask sequence start whatever the current step was
+                self.seq_step = SequenceStep::VersionMask(0);
+                let vers_roll = VersionRolling(*self.registers.get(&VersionRolling::ADDR).unwrap())
+                    .set_mask(mask)
+                    .val();
+                self.registers
+                    .insert(VersionRolling::ADDR, vers_roll)
+                    .unwrap();
+                self.version_mask = mask;
+                Some(CmdDelay {
+                    cmd: Command::write_reg(VersionRolling::ADDR, vers_roll, Destination::All),
+                    delay_ms: 1,
+                })
+            }
+        }
+    }
+}
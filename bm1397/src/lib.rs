@@ -4,7 +4,10 @@
 #![macro_use]
 pub(crate) mod fmt;
 
-use bm13xx_asic::{core_register::*, register::*, Asic, CmdDelay, SequenceStep};
+use bm13xx_asic::{
+    core_register::*, pll::PLL_REG_ADDRS, register::*, Asic, BaudratePlan, CmdDelay, Error,
+    SequenceStep,
+};
 use bm13xx_protocol::command::{Command, Destination};
 
 use core::time::Duration;
@@ -21,6 +24,9 @@ pub const BM1397_PLL_ID_HASH: usize = 0; // PLL0 isused for Hashing
 pub const BM1397_PLL_OUT_HASH: usize = 0; // specifically PLL0_OUT0 can be used for Hashing
 pub const BM1397_PLL_ID_UART: usize = 3; // PLL3 can be used for UART Baudrate
 pub const BM1397_PLL_OUT_UART: usize = 4; // specifically PLL3_OUT4 can be used for UART Baudrate
+pub const BM1397_MAX_HASH_FREQ: HertzU64 = HertzU64::MHz(675);
+pub const BM1397_MIN_INPUT_CLK: HertzU64 = HertzU64::MHz(20); // CLKI is a 25 MHz crystal on all known boards
+pub const BM1397_MAX_INPUT_CLK: HertzU64 = HertzU64::MHz(30);
 pub const BM1397_NONCE_CORES_BITS: usize = 8; // Core ID is hardcoded on Nonce[31:24] -> 8 bits
 pub const BM1397_NONCE_CORES_MASK: u32 = 0b1111_1111;
 pub const BM1397_NONCE_SMALL_CORES_BITS: usize = 2; // Small Core ID is hardcoded on Nonce[23:22] -> 2 bits
@@ -32,7 +38,6 @@ const CHIP_ADDR_MASK: u32 = 0b1111_1111;
 
 /// # BM1397
 #[derive(Debug)]
-// #[cfg_attr(feature = "defmt-03", derive(defmt::Format))] // FnvIndexMap doesn't implement defmt
 pub struct BM1397 {
     seq_step: SequenceStep,
     pub sha: bm13xx_asic::sha::Sha<
@@ -49,10 +54,115 @@ pub struct BM1397 {
 }
 
 impl BM1397 {
+    /// ## Create a Chip in its default state, with a given input clock
+    ///
+    /// ### Panics
+    ///
+    /// If `clk` is outside `BM1397_MIN_INPUT_CLK..=BM1397_MAX_INPUT_CLK`.
     pub fn new_with_clk(clk: HertzU64) -> Self {
-        BM1397 {
-            input_clock_freq: clk,
-            ..Default::default()
+        // the default registers are known to fit the maps
+        Self::try_new_with_clk(clk).unwrap()
+    }
+
+    /// ## Reset the Chip to its default state
+    ///
+    /// Same as `Asic::reset`, reporting a full register map instead of panicking.
+    ///
+    /// ### Example
+    /// ```
+    /// use bm1397::BM1397;
+    ///
+    /// let mut bm1397 = BM1397::default();
+    /// bm1397.set_chip_addr(2);
+    /// bm1397.try_reset().unwrap();
+    /// assert_eq!(bm1397.chip_addr, 0);
+    /// assert_eq!(bm1397.registers, BM1397::default().registers);
+    /// ```
+    pub fn try_reset(&mut self) -> Result<(), Error> {
+        *self = Self::try_new_with_clk(self.input_clock_freq)?;
+        Ok(())
+    }
+
+    fn insert_checked(&mut self, reg_addr: u8, reg_val: u32) -> Result<(), Error> {
+        self.registers
+            .insert(reg_addr, reg_val)
+            .map_err(|_| Error::RegisterMapFull { reg_addr })?;
+        Ok(())
+    }
+
+    fn insert_core_checked(&mut self, core_reg_id: u8, core_reg_val: u8) -> Result<(), Error> {
+        self.core_registers
+            .insert(core_reg_id, core_reg_val)
+            .map_err(|_| Error::CoreRegisterMapFull { core_reg_id })?;
+        Ok(())
+    }
+
+    /// ## Create a Chip from a registers dump
+    ///
+    /// Starts from the default state and overlays the given `registers` and
+    /// `core_registers` values. The PLLs model is not updated.
+    ///
+    /// ### Errors
+    ///
+    /// - Unsupported input clock, see `try_new_with_clk`.
+    /// - Register map full, if the register addresses do not fit the map.
+    /// - Core register map full, if the core register IDs do not fit the map.
+    ///
+    /// ### Example
+    /// ```
+    /// use bm1397::BM1397;
+    /// use bm13xx_asic::{Asic, Error};
+    /// use fugit::HertzU64;
+    ///
+    /// let mut bm1397 = BM1397::default();
+    /// while bm1397.init_next(256).is_some() {}
+    /// let registers: Vec<(u8, u32)> = bm1397.registers.iter().map(|(a, v)| (*a, *v)).collect();
+    /// let core_registers: Vec<(u8, u8)> = bm1397.core_registers.iter().map(|(i, v)| (*i, *v)).collect();
+    /// let restored = BM1397::from_registers(HertzU64::MHz(25), &registers, &core_registers).unwrap();
+    /// assert_eq!(restored.registers, bm1397.registers);
+    /// assert_eq!(restored.core_registers, bm1397.core_registers);
+    ///
+    /// let too_many: Vec<(u8, u32)> = (0..=255).map(|a| (a, 0)).collect();
+    /// assert!(matches!(
+    ///     BM1397::from_registers(HertzU64::MHz(25), &too_many, &[]),
+    ///     Err(Error::RegisterMapFull { .. })
+    /// ));
+    /// ```
+    pub fn from_registers(
+        clk: HertzU64,
+        registers: &[(u8, u32)],
+        core_registers: &[(u8, u8)],
+    ) -> Result<Self, Error> {
+        let mut bm1397 = Self::try_new_with_clk(clk)?;
+        for &(reg_addr, reg_val) in registers {
+            bm1397.insert_checked(reg_addr, reg_val)?;
+        }
+        for &(core_reg_id, core_reg_val) in core_registers {
+            bm1397.insert_core_checked(core_reg_id, core_reg_val)?;
+        }
+        Ok(bm1397)
+    }
+
+    /// ## Save the PLLs model in the PLLnParameter and PLLnDivider registers
+    ///
+    /// ### Example
+    /// ```
+    /// use bm1397::{BM1397, BM1397_PLL_ID_HASH};
+    /// use bm13xx_asic::register::{PLL0Divider, PLL0Parameter};
+    ///
+    /// let mut bm1397 = BM1397::default();
+    /// bm1397.plls[BM1397_PLL_ID_HASH].set_fb_div(112).set_out_div(0, 5);
+    /// bm1397.sync_plls_to_registers();
+    /// let (parameter, divider) = bm1397.plls[BM1397_PLL_ID_HASH].to_registers();
+    /// assert_eq!(bm1397.registers.get(&PLL0Parameter::ADDR), Some(&parameter));
+    /// assert_eq!(bm1397.registers.get(&PLL0Divider::ADDR), Some(&divider));
+    /// ```
+    pub fn sync_plls_to_registers(&mut self) {
+        for (pll, &(param_addr, div_addr)) in self.plls.iter().zip(PLL_REG_ADDRS.iter()) {
+            let (parameter, divider) = pll.to_registers();
+            // the PLL registers are part of the default registers
+            self.registers.insert(param_addr, parameter).unwrap();
+            self.registers.insert(div_addr, divider).unwrap();
         }
     }
 
@@ -94,6 +204,21 @@ impl BM1397 {
         self
     }
 
+    /// ## Get the SHA Hashing Frequency in MHz
+    ///
+    /// ### Example
+    /// ```
+    /// use bm1397::BM1397;
+    /// use fugit::HertzU64;
+    ///
+    /// let mut bm1397 = BM1397::default();
+    /// assert_eq!(bm1397.hash_freq_mhz(), 21.428571);
+    /// assert_eq!(bm1397.set_hash_freq(HertzU64::MHz(425)).hash_freq_mhz(), 425.0);
+    /// ```
+    pub fn hash_freq_mhz(&self) -> f32 {
+        self.plls[BM1397_PLL_ID_HASH].frequency_mhz(self.input_clock_freq, BM1397_PLL_OUT_HASH)
+    }
+
     /// ## Get the theoretical Hashrate in GH/s
     ///
     /// ### Example
@@ -183,12 +308,39 @@ impl BM1397 {
     }
 }
 
-impl Default for BM1397 {
-    fn default() -> Self {
+impl BM1397 {
+    /// ## Create a Chip in its default state, with a given input clock
+    ///
+    /// Same as `new_with_clk`, reporting an unsupported input clock or a full register
+    /// map instead of panicking.
+    ///
+    /// ### Errors
+    ///
+    /// - Unsupported input clock, if `clk` is outside
+    ///   `BM1397_MIN_INPUT_CLK..=BM1397_MAX_INPUT_CLK`.
+    /// - Register map full, if the default registers do not fit the map.
+    /// - Core register map full, if the default core registers do not fit the map.
+    ///
+    /// ### Example
+    /// ```
+    /// use bm1397::BM1397;
+    /// use bm13xx_asic::Error;
+    /// use fugit::HertzU64;
+    ///
+    /// assert!(BM1397::try_new_with_clk(HertzU64::MHz(25)).is_ok());
+    /// assert_eq!(
+    ///     BM1397::try_new_with_clk(HertzU64::MHz(12)).err(),
+    ///     Some(Error::UnsupportedInputClock { clk: HertzU64::MHz(12) })
+    /// );
+    /// ```
+    pub fn try_new_with_clk(clk: HertzU64) -> Result<Self, Error> {
+        if clk < BM1397_MIN_INPUT_CLK || clk > BM1397_MAX_INPUT_CLK {
+            return Err(Error::UnsupportedInputClock { clk });
+        }
         let mut bm1397 = Self {
             seq_step: SequenceStep::default(),
             sha: bm13xx_asic::sha::Sha::default(),
-            input_clock_freq: HertzU64::MHz(25),
+            input_clock_freq: clk,
             plls: [bm13xx_asic::pll::Pll::default(); BM1397_PLL_CNT],
             chip_addr: 0,
             registers: FnvIndexMap::<_, _, 64>::new(),
@@ -205,185 +357,83 @@ impl Default for BM1397 {
         bm1397.plls[2].set_divider(0x0304_0506);
         bm1397.plls[3].set_divider(0x0304_0506);
         // Default Registers Value
-        bm1397
-            .registers
-            .insert(ChipIdentification::ADDR, 0x1397_1800)
-            .unwrap();
-        bm1397
-            .registers
-            .insert(HashRate::ADDR, 0x8000_0000)
-            .unwrap();
-        bm1397
-            .registers
-            .insert(PLL0Parameter::ADDR, 0xC060_0161)
-            .unwrap();
-        bm1397
-            .registers
-            .insert(ChipNonceOffset::ADDR, 0x0000_0000)
-            .unwrap();
-        bm1397
-            .registers
-            .insert(HashCountingNumber::ADDR, 0x0000_0000)
-            .unwrap();
-        bm1397
-            .registers
-            .insert(TicketMask::ADDR, 0x0000_0000)
-            .unwrap();
-        bm1397
-            .registers
-            .insert(MiscControl::ADDR, 0x0000_3A01)
-            .unwrap();
-        bm1397
-            .registers
-            .insert(I2CControl::ADDR, 0x0100_0000)
-            .unwrap();
-        bm1397
-            .registers
-            .insert(OrderedClockEnable::ADDR, 0x0000_FFFF)
-            .unwrap();
-        bm1397
-            .registers
-            .insert(FastUARTConfiguration::ADDR, 0x0600_000F)
-            .unwrap();
-        bm1397
-            .registers
-            .insert(UARTRelay::ADDR, 0x000F_0000)
-            .unwrap();
-        bm1397
-            .registers
-            .insert(TicketMask2::ADDR, 0x0000_0000)
-            .unwrap();
-        bm1397
-            .registers
-            .insert(CoreRegisterControl::ADDR, 0x0000_4000)
-            .unwrap();
-        bm1397
-            .registers
-            .insert(CoreRegisterValue::ADDR, 0x0000_0000)
-            .unwrap();
-        bm1397
-            .registers
-            .insert(ExternalTemperatureSensorRead::ADDR, 0x0000_0100)
-            .unwrap();
-        bm1397
-            .registers
-            .insert(ErrorFlag::ADDR, 0xFF00_0000)
-            .unwrap();
-        bm1397
-            .registers
-            .insert(NonceErrorCounter::ADDR, 0x0000_0000)
-            .unwrap();
-        bm1397
-            .registers
-            .insert(NonceOverflowCounter::ADDR, 0x0000_0000)
-            .unwrap();
-        bm1397
-            .registers
-            .insert(AnalogMuxControl::ADDR, 0x0000_0000)
-            .unwrap();
-        bm1397
-            .registers
-            .insert(IoDriverStrenghtConfiguration::ADDR, 0x0211_2111)
-            .unwrap();
-        bm1397.registers.insert(TimeOut::ADDR, 0x0000_FFFF).unwrap();
-        bm1397
-            .registers
-            .insert(PLL1Parameter::ADDR, 0x0064_0111)
-            .unwrap();
-        bm1397
-            .registers
-            .insert(PLL2Parameter::ADDR, 0x0068_0111)
-            .unwrap();
-        bm1397
-            .registers
-            .insert(PLL3Parameter::ADDR, 0x0070_0111)
-            .unwrap();
-        bm1397
-            .registers
-            .insert(OrderedClockMonitor::ADDR, 0x0000_0000)
-            .unwrap();
-        bm1397
-            .registers
-            .insert(PLL0Divider::ADDR, 0x0304_0607)
-            .unwrap();
-        bm1397
-            .registers
-            .insert(PLL1Divider::ADDR, 0x0304_0506)
-            .unwrap();
-        bm1397
-            .registers
-            .insert(PLL2Divider::ADDR, 0x0304_0506)
-            .unwrap();
-        bm1397
-            .registers
-            .insert(PLL3Divider::ADDR, 0x0304_0506)
-            .unwrap();
-        bm1397
-            .registers
-            .insert(ClockOrderControl0::ADDR, 0xD95C_8410)
-            .unwrap();
-        bm1397
-            .registers
-            .insert(ClockOrderControl1::ADDR, 0xFB73_EA62)
-            .unwrap();
-        bm1397
-            .registers
-            .insert(ClockOrderStatus::ADDR, 0x0000_0000)
-            .unwrap();
-        bm1397
-            .registers
-            .insert(FrequencySweepControl1::ADDR, 0x0000_0070)
-            .unwrap();
-        bm1397
-            .registers
-            .insert(GoldenNonceForSweepReturn::ADDR, 0x0037_6400)
-            .unwrap();
-        bm1397
-            .registers
-            .insert(ReturnedGroupPatternStatus::ADDR, 0x3030_3030)
-            .unwrap();
-        bm1397
-            .registers
-            .insert(NonceReturnedTimeout::ADDR, 0x0000_FFFF)
-            .unwrap();
-        bm1397
-            .registers
-            .insert(ReturnedSinglePatternStatus::ADDR, 0x0000_0000)
-            .unwrap();
+        bm1397.insert_checked(ChipIdentification::ADDR, 0x1397_1800)?;
+        bm1397.insert_checked(HashRate::ADDR, 0x8000_0000)?;
+        bm1397.insert_checked(PLL0Parameter::ADDR, 0xC060_0161)?;
+        bm1397.insert_checked(ChipNonceOffset::ADDR, 0x0000_0000)?;
+        bm1397.insert_checked(HashCountingNumber::ADDR, 0x0000_0000)?;
+        bm1397.insert_checked(TicketMask::ADDR, 0x0000_0000)?;
+        bm1397.insert_checked(MiscControl::ADDR, 0x0000_3A01)?;
+        bm1397.insert_checked(I2CControl::ADDR, 0x0100_0000)?;
+        bm1397.insert_checked(OrderedClockEnable::ADDR, 0x0000_FFFF)?;
+        bm1397.insert_checked(FastUARTConfiguration::ADDR, 0x0600_000F)?;
+        bm1397.insert_checked(UARTRelay::ADDR, 0x000F_0000)?;
+        bm1397.insert_checked(TicketMask2::ADDR, 0x0000_0000)?;
+        bm1397.insert_checked(CoreRegisterControl::ADDR, 0x0000_4000)?;
+        bm1397.insert_checked(CoreRegisterValue::ADDR, 0x0000_0000)?;
+        bm1397.insert_checked(ExternalTemperatureSensorRead::ADDR, 0x0000_0100)?;
+        bm1397.insert_checked(ErrorFlag::ADDR, 0xFF00_0000)?;
+        bm1397.insert_checked(NonceErrorCounter::ADDR, 0x0000_0000)?;
+        bm1397.insert_checked(NonceOverflowCounter::ADDR, 0x0000_0000)?;
+        bm1397.insert_checked(AnalogMuxControl::ADDR, 0x0000_0000)?;
+        bm1397.insert_checked(IoDriverStrenghtConfiguration::ADDR, 0x0211_2111)?;
+        bm1397.insert_checked(TimeOut::ADDR, 0x0000_FFFF)?;
+        bm1397.insert_checked(PLL1Parameter::ADDR, 0x0064_0111)?;
+        bm1397.insert_checked(PLL2Parameter::ADDR, 0x0068_0111)?;
+        bm1397.insert_checked(PLL3Parameter::ADDR, 0x0070_0111)?;
+        bm1397.insert_checked(OrderedClockMonitor::ADDR, 0x0000_0000)?;
+        bm1397.insert_checked(PLL0Divider::ADDR, 0x0304_0607)?;
+        bm1397.insert_checked(PLL1Divider::ADDR, 0x0304_0506)?;
+        bm1397.insert_checked(PLL2Divider::ADDR, 0x0304_0506)?;
+        bm1397.insert_checked(PLL3Divider::ADDR, 0x0304_0506)?;
+        bm1397.insert_checked(ClockOrderControl0::ADDR, 0xD95C_8410)?;
+        bm1397.insert_checked(ClockOrderControl1::ADDR, 0xFB73_EA62)?;
+        bm1397.insert_checked(ClockOrderStatus::ADDR, 0x0000_0000)?;
+        bm1397.insert_checked(FrequencySweepControl1::ADDR, 0x0000_0070)?;
+        bm1397.insert_checked(GoldenNonceForSweepReturn::ADDR, 0x0037_6400)?;
+        bm1397.insert_checked(ReturnedGroupPatternStatus::ADDR, 0x3030_3030)?;
+        bm1397.insert_checked(NonceReturnedTimeout::ADDR, 0x0000_FFFF)?;
+        bm1397.insert_checked(ReturnedSinglePatternStatus::ADDR, 0x0000_0000)?;
         // Default Core Registers Value
-        bm1397
-            .core_registers
-            .insert(ClockDelayCtrl::ID, 0x00) // TODO: add the correct value from chip actual reading
-            .unwrap();
-        bm1397
-            .core_registers
-            .insert(ProcessMonitorCtrl::ID, 0x00) // TODO: add the correct value from chip actual reading
-            .unwrap();
-        bm1397
-            .core_registers
-            .insert(ProcessMonitorData::ID, 0x00) // TODO: add the correct value from chip actual reading
-            .unwrap();
-        bm1397
-            .core_registers
-            .insert(CoreError::ID, 0x00) // TODO: add the correct value from chip actual reading
-            .unwrap();
-        bm1397
-            .core_registers
-            .insert(CoreEnable::ID, 0x00) // TODO: add the correct value from chip actual reading
-            .unwrap();
-        bm1397
-            .core_registers
-            .insert(HashClockCtrl::ID, 0x00) // TODO: add the correct value from chip actual reading
-            .unwrap();
-        bm1397
-            .core_registers
-            .insert(HashClockCounter::ID, 0x00) // TODO: add the correct value from chip actual reading
-            .unwrap();
-        bm1397
-            .core_registers
-            .insert(SweepClockCtrl::ID, 0x00) // TODO: add the correct value from chip actual reading
-            .unwrap();
-        bm1397
+        bm1397.insert_core_checked(ClockDelayCtrl::ID, 0x00)?; // TODO: add the correct value from chip actual reading
+        bm1397.insert_core_checked(ProcessMonitorCtrl::ID, 0x00)?; // TODO: add the correct value from chip actual reading
+        bm1397.insert_core_checked(ProcessMonitorData::ID, 0x00)?; // TODO: add the correct value from chip actual reading
+        bm1397.insert_core_checked(CoreError::ID, 0x00)?; // TODO: add the correct value from chip actual reading
+        bm1397.insert_core_checked(CoreEnable::ID, 0x00)?; // TODO: add the correct value from chip actual reading
+        bm1397.insert_core_checked(HashClockCtrl::ID, 0x00)?; // TODO: add the correct value from chip actual reading
+        bm1397.insert_core_checked(HashClockCounter::ID, 0x00)?; // TODO: add the correct value from chip actual reading
+        bm1397.insert_core_checked(SweepClockCtrl::ID, 0x00)?; // TODO: add the correct value from chip actual reading
+        Ok(bm1397)
+    }
+}
+
+impl Default for BM1397 {
+    fn default() -> Self {
+        Self::new_with_clk(HertzU64::MHz(25))
+    }
+}
+
+// FnvIndexMap doesn't implement defmt, so the register maps are printed as (addr, value) pairs
+#[cfg(feature = "defmt-03")]
+impl defmt::Format for BM1397 {
+    fn format(&self, fmt: defmt::Formatter) {
+        defmt::write!(
+            fmt,
+            "BM1397 {{ chip_id: {:#06x}, chip_addr: {}, input_clock_freq: {}, hash_freq: {}, plls: {}, registers: [",
+            self.chip_id(),
+            self.chip_addr,
+            self.input_clock_freq,
+            self.hash_freq(),
+            self.plls,
+        );
+        for (reg_addr, reg_val) in self.registers.iter() {
+            defmt::write!(fmt, "({:#04x}, {:#010x}), ", reg_addr, reg_val);
+        }
+        defmt::write!(fmt, "], core_registers: [");
+        for (core_reg_id, core_reg_val) in self.core_registers.iter() {
+            defmt::write!(fmt, "({}, {:#04x}), ", core_reg_id, core_reg_val);
+        }
+        defmt::write!(fmt, "] }}");
     }
 }
 
@@ -397,11 +447,54 @@ impl Asic for BM1397 {
     ///
     /// let bm1397 = BM1397::default();
     /// assert_eq!(bm1397.chip_id(), 0x1397);
+    /// assert_eq!(bm1397.chip_name(), "BM1397");
     /// ```
     fn chip_id(&self) -> u16 {
         BM1397_CHIP_ID
     }
 
+    /// ## Get the number of Small Cores in each Core
+    ///
+    /// ### Example
+    /// ```
+    /// use bm1397::{BM1397, BM1397_CORE_SMALL_CORE_CNT};
+    /// use bm13xx_asic::Asic;
+    ///
+    /// let bm1397 = BM1397::default();
+    /// assert_eq!(bm1397.core_small_core_count(), BM1397_CORE_SMALL_CORE_CNT);
+    /// ```
+    fn core_small_core_count(&self) -> usize {
+        self.sha.core_small_core_count()
+    }
+
+    /// ## Get the number of Small Cores in the chip
+    ///
+    /// ### Example
+    /// ```
+    /// use bm1397::{BM1397, BM1397_SMALL_CORE_CNT};
+    /// use bm13xx_asic::Asic;
+    ///
+    /// let bm1397 = BM1397::default();
+    /// assert_eq!(bm1397.small_core_count(), BM1397_SMALL_CORE_CNT);
+    /// ```
+    fn small_core_count(&self) -> usize {
+        self.sha.small_core_count()
+    }
+
+    /// ## Get the number of Domains in the chip
+    ///
+    /// ### Example
+    /// ```
+    /// use bm1397::{BM1397, BM1397_DOMAIN_CNT};
+    /// use bm13xx_asic::Asic;
+    ///
+    /// let bm1397 = BM1397::default();
+    /// assert_eq!(bm1397.domain_count(), BM1397_DOMAIN_CNT);
+    /// ```
+    fn domain_count(&self) -> usize {
+        self.sha.domain_count()
+    }
+
     /// ## Has Version Rolling in chip
     ///
     /// ### Example
@@ -416,6 +509,265 @@ impl Asic for BM1397 {
         false
     }
 
+    /// ## Supports voltage domains of different sizes
+    ///
+    /// The baudrate sequence does not depend on the voltage domains.
+    ///
+    /// ### Example
+    /// ```
+    /// use bm1397::BM1397;
+    /// use bm13xx_asic::Asic;
+    ///
+    /// let bm1397 = BM1397::default();
+    /// assert!(bm1397.supports_mixed_domains());
+    /// ```
+    fn supports_mixed_domains(&self) -> bool {
+        true
+    }
+
+    /// ## Get the maximum hash frequency
+    ///
+    /// ### Example
+    /// ```
+    /// use bm1397::BM1397;
+    /// use bm13xx_asic::Asic;
+    /// use fugit::HertzU64;
+    ///
+    /// let bm1397 = BM1397::default();
+    /// assert_eq!(bm1397.max_hash_freq(), HertzU64::MHz(675));
+    /// ```
+    fn max_hash_freq(&self) -> HertzU64 {
+        BM1397_MAX_HASH_FREQ
+    }
+
+    /// ## Get the current hash frequency
+    ///
+    /// ### Example
+    /// ```
+    /// use bm1397::BM1397;
+    /// use bm13xx_asic::Asic;
+    /// use fugit::HertzU64;
+    ///
+    /// let bm1397 = BM1397::default();
+    /// assert_eq!(Asic::hash_freq(&bm1397), HertzU64::Hz(21428571));
+    /// ```
+    fn hash_freq(&self) -> HertzU64 {
+        BM1397::hash_freq(self)
+    }
+
+    /// ## Get the achievable hash frequency nearest to a target
+    ///
+    /// ### Example
+    /// ```
+    /// use bm1397::BM1397;
+    /// use bm13xx_asic::Asic;
+    /// use fugit::HertzU64;
+    ///
+    /// let bm1397 = BM1397::default();
+    /// assert_eq!(bm1397.nearest_hash_freq(HertzU64::MHz(425)), HertzU64::MHz(425));
+    /// assert_eq!(bm1397.nearest_hash_freq(HertzU64::Hz(487_654_321)), HertzU64::kHz(487_500));
+    /// assert_eq!(bm1397.nearest_hash_freq(HertzU64::MHz(5000)), HertzU64::MHz(0));
+    /// assert_eq!(Asic::hash_freq(&bm1397), BM1397::default().hash_freq());
+    /// ```
+    fn nearest_hash_freq(&self, target_freq: HertzU64) -> HertzU64 {
+        self.plls[BM1397_PLL_ID_HASH].nearest_achievable(
+            self.input_clock_freq,
+            BM1397_PLL_OUT_HASH,
+            target_freq,
+        )
+    }
+
+    /// ## Get the job rolling duration
+    ///
+    /// See [`BM1397::rolling_duration`].
+    fn rolling_duration(&self) -> Duration {
+        BM1397::rolling_duration(self)
+    }
+
+    /// ## Get the baudrate plan
+    ///
+    /// ### Example
+    /// ```
+    /// use bm1397::BM1397;
+    /// use bm13xx_asic::{Asic, BaudratePlan};
+    ///
+    /// let bm1397 = BM1397::default();
+    /// assert_eq!(
+    ///     bm1397.baudrate_plan(115_200),
+    ///     BaudratePlan {
+    ///         uses_pll: false,
+    ///         achievable_baud: 115_740
+    ///     }
+    /// );
+    /// assert_eq!(
+    ///     bm1397.baudrate_plan(1_000_000),
+    ///     BaudratePlan {
+    ///         uses_pll: false,
+    ///         achievable_baud: 1_041_666
+    ///     }
+    /// );
+    /// assert_eq!(
+    ///     bm1397.baudrate_plan(6_250_000),
+    ///     BaudratePlan {
+    ///         uses_pll: true,
+    ///         achievable_baud: 6_250_000
+    ///     }
+    /// );
+    /// ```
+    fn baudrate_plan(&self, baudrate: u32) -> BaudratePlan {
+        if baudrate <= self.input_clock_freq.raw() as u32 / 8 {
+            let fbase = self.input_clock_freq.raw() as u32;
+            let bt8d = (fbase / (8 * baudrate)) - 1;
+            BaudratePlan {
+                uses_pll: false,
+                achievable_baud: fbase / (8 * (bt8d + 1)),
+            }
+        } else {
+            // same UART PLL setting as set_baudrate_next
+            let mut pll3 = self.plls[BM1397_PLL_ID_UART];
+            pll3.lock()
+                .enable()
+                .set_fb_div(112)
+                .set_ref_div(1)
+                .set_post1_div(1)
+                .set_post2_div(1)
+                .set_out_div(BM1397_PLL_OUT_UART, 6);
+            let fbase = pll3
+                .frequency(self.input_clock_freq, BM1397_PLL_OUT_UART)
+                .raw() as u32;
+            let bt8d = (fbase / (2 * baudrate)) - 1;
+            BaudratePlan {
+                uses_pll: true,
+                achievable_baud: fbase / (2 * (bt8d + 1)),
+            }
+        }
+    }
+
+    /// ## Reset the Chip model to its power-on state
+    ///
+    /// The input clock frequency is kept as it is a property of the board.
+    ///
+    /// ### Example
+    /// ```
+    /// use bm1397::BM1397;
+    /// use bm13xx_asic::Asic;
+    /// use fugit::HertzU64;
+    ///
+    /// let mut bm1397 = BM1397::new_with_clk(HertzU64::MHz(26));
+    /// bm1397.set_chip_addr(2);
+    /// bm1397.reset();
+    /// assert_eq!(bm1397.chip_addr, 0);
+    /// assert_eq!(bm1397.input_clock_freq, HertzU64::MHz(26));
+    /// ```
+    fn reset(&mut self) {
+        *self = Self::new_with_clk(self.input_clock_freq);
+    }
+
+    /// ## Get the current sequence step
+    ///
+    /// ### Example
+    /// ```
+    /// use bm1397::BM1397;
+    /// use bm13xx_asic::{Asic, SequenceStep};
+    ///
+    /// let mut bm1397 = BM1397::default();
+    /// assert_eq!(bm1397.sequence_step(), SequenceStep::None);
+    /// bm1397.init_next(256);
+    /// assert!(bm1397.sequence_step().is_in_progress());
+    /// while bm1397.init_next(256).is_some() {}
+    /// assert_eq!(bm1397.sequence_step(), SequenceStep::None);
+    /// ```
+    fn sequence_step(&self) -> SequenceStep {
+        self.seq_step.clone()
+    }
+
+    /// ## Get a register value from the model
+    ///
+    /// ### Example
+    /// ```
+    /// use bm1397::BM1397;
+    /// use bm13xx_asic::{register::ChipIdentification, Asic};
+    ///
+    /// let bm1397 = BM1397::default();
+    /// assert_eq!(ChipIdentification(bm1397.register(ChipIdentification::ADDR).unwrap()).chip_id(), 0x1397);
+    /// assert_eq!(bm1397.register(0xFF), None);
+    /// ```
+    fn register(&self, reg_addr: u8) -> Option<u32> {
+        self.registers.get(&reg_addr).copied()
+    }
+
+    /// ## Get a core register value from the chip model
+    ///
+    /// ### Example
+    /// ```
+    /// use bm1397::BM1397;
+    /// use bm13xx_asic::{core_register::HashClockCtrl, Asic};
+    ///
+    /// let bm1397 = BM1397::default();
+    /// assert_eq!(bm1397.core_register(HashClockCtrl::ID), Some(0x00));
+    /// assert_eq!(bm1397.core_register(0xFF), None);
+    /// ```
+    fn core_register(&self, core_reg_id: u8) -> Option<u8> {
+        self.core_registers.get(&core_reg_id).copied()
+    }
+
+    /// ## Write a register
+    ///
+    /// ### Example
+    /// ```
+    /// use bm1397::BM1397;
+    /// use bm13xx_asic::{register::*, Asic, CmdDelay};
+    /// use bm13xx_protocol::command::{Command, Destination};
+    ///
+    /// let mut bm1397 = BM1397::default();
+    /// let step = bm1397.write_reg_next(NonceReturnedTimeout::ADDR, 0x00fd_007d, Destination::All);
+    /// assert_eq!(step.cmd, Command::write_reg(NonceReturnedTimeout::ADDR, 0x00fd_007d, Destination::All));
+    /// assert_eq!(bm1397.register(NonceReturnedTimeout::ADDR), Some(0x00fd_007d));
+    /// ```
+    fn write_reg_next(&mut self, reg_addr: u8, reg_val: u32, dest: Destination) -> CmdDelay {
+        if let Some(val) = self.registers.get_mut(&reg_addr) {
+            *val = reg_val;
+        }
+        CmdDelay {
+            cmd: Command::write_reg(reg_addr, reg_val, dest),
+            delay_ms: 0,
+        }
+    }
+
+    /// ## Write a core register
+    ///
+    /// ### Example
+    /// ```
+    /// use bm1397::BM1397;
+    /// use bm13xx_asic::{core_register::HashClockCtrl, Asic};
+    /// use bm13xx_protocol::command::Destination;
+    ///
+    /// let mut bm1397 = BM1397::default();
+    /// let step = bm1397.write_core_reg_next(HashClockCtrl::ID, 0x40, Destination::All);
+    /// assert_eq!(step.cmd, [0x55, 0xAA, 0x51, 0x09, 0x00, 0x3C, 0x80, 0x00, 0x85, 0x40, 0x0C]);
+    /// assert_eq!(step.delay_ms, 0);
+    /// bm1397.write_core_reg_next(HashClockCtrl::ID, 0x41, Destination::All);
+    /// assert_eq!(bm1397.core_registers.get(&HashClockCtrl::ID).unwrap(), &0x41);
+    /// ```
+    fn write_core_reg_next(
+        &mut self,
+        core_reg_id: u8,
+        core_reg_val: u8,
+        dest: Destination,
+    ) -> CmdDelay {
+        if let Some(val) = self.core_registers.get_mut(&core_reg_id) {
+            *val = core_reg_val;
+        }
+        CmdDelay {
+            cmd: Command::write_reg(
+                CoreRegisterControl::ADDR,
+                CoreRegisterControl::write_core_reg_id(0, core_reg_id, core_reg_val),
+                dest,
+            ),
+            delay_ms: 0,
+        }
+    }
+
     /// ## Init the Chip command list
     ///
     /// ### Example
@@ -431,11 +783,14 @@ impl Asic for BM1397 {
     /// assert_eq!(bm1397.init_next(64), Some(CmdDelay{cmd: [0x55, 0xaa, 0x51, 0x09, 0x00, 0x20, 0x00, 0x00, 0x00, 0xff, 0x13], delay_ms:10}));
     /// assert_eq!(bm1397.init_next(64), Some(CmdDelay{cmd: [0x55, 0xaa, 0x51, 0x09, 0x00, 0x3c, 0x80, 0x00, 0x80, 0xb4, 0x19], delay_ms:5}));
     /// assert_eq!(bm1397.init_next(64), Some(CmdDelay{cmd: [0x55, 0xaa, 0x51, 0x09, 0x00, 0x14, 0x00, 0x00, 0x00, 0xfc, 0x07], delay_ms:10}));
+    /// assert_eq!(bm1397.init_next(64), Some(CmdDelay{cmd: [0x55, 0xaa, 0x51, 0x09, 0x00, 0x54, 0x00, 0x00, 0x00, 0x03, 0x1d], delay_ms:0}));
+    /// assert_eq!(bm1397.init_next(64), None);
     /// assert_eq!(bm1397.registers.get(&ClockOrderControl0::ADDR).unwrap(), &0x0000_0000);
     /// assert_eq!(bm1397.registers.get(&ClockOrderControl1::ADDR).unwrap(), &0x0000_0000);
     /// assert_eq!(bm1397.registers.get(&OrderedClockEnable::ADDR).unwrap(), &0x0000_00ff);
     /// assert_eq!(bm1397.core_registers.get(&ClockDelayCtrl::ID).unwrap(), &0xb4);
     /// assert_eq!(bm1397.registers.get(&TicketMask::ADDR).unwrap(), &0x0000_00fc);
+    /// assert_eq!(bm1397.registers.get(&AnalogMuxControl::ADDR).unwrap(), &0x0000_0003);
     /// ```
     fn init_next(&mut self, diffculty: u32) -> Option<CmdDelay> {
         match self.seq_step {
@@ -491,15 +846,7 @@ impl Asic for BM1397 {
                         let clk_ord_en = OrderedClockEnable(
                             *self.registers.get(&OrderedClockEnable::ADDR).unwrap(),
                         )
-                        .disable_all()
-                        .enable(ClockSelect::CLK0)
-                        .enable(ClockSelect::CLK1)
-                        .enable(ClockSelect::CLK2)
-                        .enable(ClockSelect::CLK3)
-                        .enable(ClockSelect::CLK4)
-                        .enable(ClockSelect::CLK5)
-                        .enable(ClockSelect::CLK6)
-                        .enable(ClockSelect::CLK7)
+                        .enable_first_n(8)
                         .val();
                         self.registers
                             .insert(OrderedClockEnable::ADDR, clk_ord_en)
@@ -546,6 +893,24 @@ impl Asic for BM1397 {
                         })
                     }
                     5 => {
+                        self.seq_step = SequenceStep::Init(6);
+                        let ana_mux_ctrl =
+                            AnalogMuxControl(*self.registers.get(&AnalogMuxControl::ADDR).unwrap())
+                                .set_diode_vdd_mux_sel(3)
+                                .val();
+                        self.registers
+                            .insert(AnalogMuxControl::ADDR, ana_mux_ctrl)
+                            .unwrap();
+                        Some(CmdDelay {
+                            cmd: Command::write_reg(
+                                AnalogMuxControl::ADDR,
+                                ana_mux_ctrl,
+                                Destination::All,
+                            ),
+                            delay_ms: 0,
+                        })
+                    }
+                    6 => {
                         self.seq_step = SequenceStep::None;
                         None
                     }
@@ -757,17 +1122,57 @@ impl Asic for BM1397 {
 
     /// ## Reset the Chip Cores command list
     ///
+    /// BM1397 cores are soft reset by pulsing the `MiscControl` Core Soft Reset bit.
+    ///
     /// ### Example
     /// ```
     /// use bm1397::BM1397;
-    /// use bm13xx_asic::{core_register::*, register::*, Asic, CmdDelay};
+    /// use bm13xx_asic::{register::*, Asic, CmdDelay};
     /// use bm13xx_protocol::command::Destination;
     ///
     /// let mut bm1397 = BM1397::default();
+    /// assert_eq!(bm1397.reset_core_next(Destination::All), Some(CmdDelay{cmd: [0x55, 0xaa, 0x51, 0x09, 0x00, 0x18, 0x00, 0x40, 0x3a, 0x01, 0x13], delay_ms: 10}));
+    /// assert_eq!(bm1397.reset_core_next(Destination::All), Some(CmdDelay{cmd: [0x55, 0xaa, 0x51, 0x09, 0x00, 0x18, 0x00, 0x00, 0x3a, 0x01, 0x18], delay_ms: 10}));
     /// assert_eq!(bm1397.reset_core_next(Destination::All), None);
+    /// assert_eq!(bm1397.registers.get(&MiscControl::ADDR).unwrap(), &0x0000_3A01);
+    /// assert_eq!(bm1397.reset_core_next(Destination::Chip(8)), Some(CmdDelay{cmd: [0x55, 0xaa, 0x41, 0x09, 0x08, 0x18, 0x00, 0x40, 0x3a, 0x01, 0x08], delay_ms: 10}));
+    /// assert_eq!(bm1397.reset_core_next(Destination::Chip(8)), Some(CmdDelay{cmd: [0x55, 0xaa, 0x41, 0x09, 0x08, 0x18, 0x00, 0x00, 0x3a, 0x01, 0x03], delay_ms: 10}));
+    /// assert_eq!(bm1397.reset_core_next(Destination::Chip(8)), None);
     /// ```
-    fn reset_core_next(&mut self, _dest: Destination) -> Option<CmdDelay> {
-        None // TODO impl
+    fn reset_core_next(&mut self, dest: Destination) -> Option<CmdDelay> {
+        let mut misc_ctrl = MiscControl(*self.registers.get(&MiscControl::ADDR).unwrap());
+        match self.seq_step {
+            SequenceStep::ResetCore(step) => match step {
+                0 => {
+                    self.seq_step = SequenceStep::ResetCore(1);
+                    Some(CmdDelay {
+                        cmd: Command::write_reg(
+                            MiscControl::ADDR,
+                            misc_ctrl.release_core_soft_reset().val(),
+                            dest,
+                        ),
+                        delay_ms: 10,
+                    })
+                }
+                1 => {
+                    self.seq_step = SequenceStep::None;
+                    None
+                }
+                _ => unreachable!(),
+            },
+            _ => {
+                // authorize a ResetCore sequence start whatever the current step was
+                self.seq_step = SequenceStep::ResetCore(0);
+                Some(CmdDelay {
+                    cmd: Command::write_reg(
+                        MiscControl::ADDR,
+                        misc_ctrl.core_soft_reset().val(),
+                        dest,
+                    ),
+                    delay_ms: 10,
+                })
+            }
+        }
     }
 
     /// ## Send Hash Frequency command list
@@ -786,7 +1191,11 @@ impl Asic for BM1397 {
     /// assert_eq!(bm1397.set_hash_freq_next(HertzU64::MHz(700)), None);
     // assert_eq!(bm1397.plls[BM1397_PLL_ID_HASH].parameter(), 0xc070_0111);
     /// ```
-    fn set_hash_freq_next(&mut self, _target_freq: HertzU64) -> Option<CmdDelay> {
+    fn set_hash_freq_next_dest(
+        &mut self,
+        _target_freq: HertzU64,
+        _dest: Destination,
+    ) -> Option<CmdDelay> {
         /*
         self.plls[BM1397_PLL_ID_HASH].set_divider(0x0f0f_0f00);
         self.registers
@@ -887,4 +1296,84 @@ impl Asic for BM1397 {
     fn set_version_rolling_next(&mut self, _mask: u32) -> Option<CmdDelay> {
         None
     }
+
+    /// ## Send Clock Order command list
+    ///
+    /// ### Example
+    /// ```
+    /// use bm1397::BM1397;
+    /// use bm13xx_asic::{register::*, Asic};
+    ///
+    /// let mut bm1397 = BM1397::default();
+    /// while bm1397.init_next(256).is_some() {}
+    /// // back to the BM1397 reset order
+    /// let order = [0, 1, 4, 8, 12, 5, 9, 13, 2, 6, 10, 14, 3, 7, 11, 15];
+    /// assert_eq!(bm1397.set_clock_order_next(&order).unwrap().cmd[5], ClockOrderControl0::ADDR);
+    /// assert_eq!(bm1397.set_clock_order_next(&order).unwrap().cmd[5], ClockOrderControl1::ADDR);
+    /// assert_eq!(bm1397.set_clock_order_next(&order), None);
+    /// assert_eq!(bm1397.registers.get(&ClockOrderControl0::ADDR).unwrap(), &0xD95C_8410);
+    /// assert_eq!(bm1397.registers.get(&ClockOrderControl1::ADDR).unwrap(), &0xFB73_EA62);
+    /// ```
+    fn set_clock_order_next(&mut self, order: &[u8; 16]) -> Option<CmdDelay> {
+        match self.seq_step {
+            SequenceStep::ClockOrder(step) => match step {
+                0 => {
+                    self.seq_step = SequenceStep::ClockOrder(1);
+                    let clk_ord_ctrl1 =
+                        ClockOrderControl1(*self.registers.get(&ClockOrderControl1::ADDR).unwrap())
+                            .set_clock(ClockSelect::CLK8, order[8])
+                            .set_clock(ClockSelect::CLK9, order[9])
+                            .set_clock(ClockSelect::CLK10, order[10])
+                            .set_clock(ClockSelect::CLK11, order[11])
+                            .set_clock(ClockSelect::CLK12, order[12])
+                            .set_clock(ClockSelect::CLK13, order[13])
+                            .set_clock(ClockSelect::CLK14, order[14])
+                            .set_clock(ClockSelect::CLK15, order[15])
+                            .val();
+                    self.registers
+                        .insert(ClockOrderControl1::ADDR, clk_ord_ctrl1)
+                        .unwrap();
+                    Some(CmdDelay {
+                        cmd: Command::write_reg(
+                            ClockOrderControl1::ADDR,
+                            clk_ord_ctrl1,
+                            Destination::All,
+                        ),
+                        delay_ms: 10,
+                    })
+                }
+                1 => {
+                    self.seq_step = SequenceStep::None;
+                    None
+                }
+                _ => unreachable!(),
+            },
+            _ => {
+                // authorize a ClockOrder sequence start whatever the current step was
+                self.seq_step = SequenceStep::ClockOrder(0);
+                let clk_ord_ctrl0 =
+                    ClockOrderControl0(*self.registers.get(&ClockOrderControl0::ADDR).unwrap())
+                        .set_clock(ClockSelect::CLK0, order[0])
+                        .set_clock(ClockSelect::CLK1, order[1])
+                        .set_clock(ClockSelect::CLK2, order[2])
+                        .set_clock(ClockSelect::CLK3, order[3])
+                        .set_clock(ClockSelect::CLK4, order[4])
+                        .set_clock(ClockSelect::CLK5, order[5])
+                        .set_clock(ClockSelect::CLK6, order[6])
+                        .set_clock(ClockSelect::CLK7, order[7])
+                        .val();
+                self.registers
+                    .insert(ClockOrderControl0::ADDR, clk_ord_ctrl0)
+                    .unwrap();
+                Some(CmdDelay {
+                    cmd: Command::write_reg(
+                        ClockOrderControl0::ADDR,
+                        clk_ord_ctrl0,
+                        Destination::All,
+                    ),
+                    delay_ms: 10,
+                })
+            }
+        }
+    }
 }
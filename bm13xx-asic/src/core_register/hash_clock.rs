@@ -47,6 +47,7 @@ impl HashClockCtrl {
     /// assert_eq!(hash_clock_ctrl.pll_source(), 0);
     /// assert_eq!(hash_clock_ctrl.set_pll_source(1).pll_source(), 1); // max value
     /// assert_eq!(hash_clock_ctrl.set_pll_source(2).pll_source(), 0); // out of bound value
+    /// assert_eq!(hash_clock_ctrl.0, 0x40); // other bits untouched
     /// ```
     pub const fn pll_source(&self) -> u8 {
         (self.0 >> Self::PLL_SRC_OFFSET) & Self::PLL_SRC_MASK
@@ -87,20 +88,47 @@ impl_boilerplate_for_core_reg!(HashClockCounter);
 impl HashClockCounter {
     pub const ID: u8 = 6;
 
-    // const CLOCK_CNT_OFFSET: u8 = 0;
+    const CLOCK_CNT_OFFSET: u8 = 0;
 
-    // const CLOCK_CNT_MASK: u8 = 0xff;
+    const CLOCK_CNT_MASK: u8 = 0xff;
+
+    /// ## Handle the clock count field.
+    ///
+    /// ### Example
+    /// ```
+    /// use bm13xx_asic::core_register::HashClockCounter;
+    ///
+    /// let mut hash_clock_cnt = HashClockCounter(0x08); // BM1366 default value
+    /// assert_eq!(hash_clock_cnt.clock_cnt(), 0x08);
+    /// assert_eq!(hash_clock_cnt.set_clock_cnt(0).clock_cnt(), 0); // min value
+    /// assert_eq!(hash_clock_cnt.set_clock_cnt(0xff).clock_cnt(), 0xff); // max value
+    /// assert_eq!(hash_clock_cnt.set_clock_cnt(0x08).0, 0x08);
+    /// ```
+    pub const fn clock_cnt(&self) -> u8 {
+        (self.0 >> Self::CLOCK_CNT_OFFSET) & Self::CLOCK_CNT_MASK
+    }
+    pub fn set_clock_cnt(&mut self, clock_cnt: u8) -> &mut Self {
+        self.0 &= !(Self::CLOCK_CNT_MASK << Self::CLOCK_CNT_OFFSET);
+        self.0 |= (clock_cnt & Self::CLOCK_CNT_MASK) << Self::CLOCK_CNT_OFFSET;
+        self
+    }
 }
 
 impl ::core::fmt::Display for HashClockCounter {
     fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
-        f.debug_struct("HashClockCounter").finish()
+        f.debug_struct("HashClockCounter")
+            .field("clock_cnt", &self.clock_cnt())
+            .finish()
     }
 }
 
 #[cfg(feature = "defmt-03")]
 impl defmt::Format for HashClockCounter {
     fn format(&self, fmt: defmt::Formatter) {
-        defmt::write!(fmt, "HashClockCounter {{ }}",);
+        defmt::write!(
+            fmt,
+            "HashClockCounter {{ clock_cnt: {} }}",
+            self.clock_cnt(),
+        );
     }
 }
@@ -99,6 +99,11 @@ impl ProcessMonitorCtrl {
     /// let pmc = ProcessMonitorCtrl(ProcessMonitorCtrl::start(ProcessMonitorSelect::HVTDelayChain));
     /// assert!(pmc.started());
     /// assert_eq!(pmc.pm_sel(), ProcessMonitorSelect::HVTDelayChain);
+    /// assert_eq!(ProcessMonitorCtrl::start(ProcessMonitorSelect::LVTDelayChain), 0x04);
+    /// assert_eq!(ProcessMonitorCtrl::start(ProcessMonitorSelect::SVTDelayChain), 0x05);
+    /// assert_eq!(ProcessMonitorCtrl::start(ProcessMonitorSelect::HVTDelayChain), 0x06);
+    /// assert_eq!(ProcessMonitorCtrl::start(ProcessMonitorSelect::CriticalPathChain), 0x07);
+    /// assert!(!ProcessMonitorCtrl(0x02).started());
     /// ```
     pub const fn started(&self) -> bool {
         (self.0 >> Self::PM_START_OFFSET) & Self::PM_START_MASK == Self::PM_START_MASK
@@ -146,7 +151,10 @@ impl ProcessMonitorData {
 
     /// ## Get the Data.
     ///
-    /// This returns an `u8` with the Data.
+    /// This returns an `u8` with the Data, the result of the last measurement started
+    /// with `ProcessMonitorCtrl::start` on the selected delay chain. It is a relative
+    /// speed indication: its absolute meaning is undocumented, so it should only be
+    /// compared between chips or conditions for the same select.
     ///
     /// ### Example
     ///
@@ -8,22 +8,84 @@ impl_boilerplate_for_core_reg!(SweepClockCtrl);
 impl SweepClockCtrl {
     pub const ID: u8 = 7;
 
-    // const SWPF_MODE_OFFSET: u8 = 7;
-    // const CLK_SEL_OFFSET: u8 = 0;
+    const SWPF_MODE_OFFSET: u8 = 7;
+    const CLK_SEL_OFFSET: u8 = 0;
 
-    // const SWPF_MODE_MASK: u8 = 0b1;
-    // const CLK_SEL_MASK: u8 = 0b1111;
+    const SWPF_MODE_MASK: u8 = 0b1;
+    const CLK_SEL_MASK: u8 = 0b1111;
+
+    /// ## Handle the Sweep Frequency Mode field.
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// use bm13xx_asic::core_register::SweepClockCtrl;
+    ///
+    /// let mut scc = SweepClockCtrl(0x00); // BM1397 default value
+    /// assert!(!scc.sweep_frequency_mode_enabled());
+    /// assert!(scc.enable_sweep_frequency_mode().sweep_frequency_mode_enabled());
+    /// assert_eq!(scc.0, 0x80);
+    /// assert!(!scc.disable_sweep_frequency_mode().sweep_frequency_mode_enabled());
+    /// assert_eq!(scc.0, 0x00);
+    /// ```
+    pub const fn sweep_frequency_mode_enabled(&self) -> bool {
+        (self.0 >> Self::SWPF_MODE_OFFSET) & Self::SWPF_MODE_MASK == Self::SWPF_MODE_MASK
+    }
+    pub fn enable_sweep_frequency_mode(&mut self) -> &mut Self {
+        self.0 |= Self::SWPF_MODE_MASK << Self::SWPF_MODE_OFFSET;
+        self
+    }
+    pub fn disable_sweep_frequency_mode(&mut self) -> &mut Self {
+        self.0 &= !(Self::SWPF_MODE_MASK << Self::SWPF_MODE_OFFSET);
+        self
+    }
+
+    /// ## Handle the Clock Select field.
+    ///
+    /// Get and set the clock the cores are swept with.
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// use bm13xx_asic::core_register::SweepClockCtrl;
+    ///
+    /// let mut scc = SweepClockCtrl(0x80);
+    /// assert_eq!(scc.clk_sel(), 0);
+    /// assert_eq!(scc.set_clk_sel(5).clk_sel(), 5);
+    /// assert_eq!(scc.set_clk_sel(0xf).clk_sel(), 0xf); // max value
+    /// assert_eq!(scc.set_clk_sel(0x10).clk_sel(), 0); // out of bound value
+    /// assert!(scc.sweep_frequency_mode_enabled()); // other bits untouched
+    /// ```
+    pub const fn clk_sel(&self) -> u8 {
+        (self.0 >> Self::CLK_SEL_OFFSET) & Self::CLK_SEL_MASK
+    }
+    pub fn set_clk_sel(&mut self, clk_sel: u8) -> &mut Self {
+        self.0 &= !(Self::CLK_SEL_MASK << Self::CLK_SEL_OFFSET);
+        self.0 |= (clk_sel & Self::CLK_SEL_MASK) << Self::CLK_SEL_OFFSET;
+        self
+    }
 }
 
 impl ::core::fmt::Display for SweepClockCtrl {
     fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
-        f.debug_struct("SweepClockCtrl").finish()
+        f.debug_struct("SweepClockCtrl")
+            .field(
+                "sweep_frequency_mode_enabled",
+                &self.sweep_frequency_mode_enabled(),
+            )
+            .field("clk_sel", &self.clk_sel())
+            .finish()
     }
 }
 
 #[cfg(feature = "defmt-03")]
 impl defmt::Format for SweepClockCtrl {
     fn format(&self, fmt: defmt::Formatter) {
-        defmt::write!(fmt, "SweepClockCtrl {{ }}",);
+        defmt::write!(
+            fmt,
+            "SweepClockCtrl {{ sweep_frequency_mode_enabled: {}, clk_sel: {} }}",
+            self.sweep_frequency_mode_enabled(),
+            self.clk_sel(),
+        );
     }
 }
@@ -1,4 +1,5 @@
 use derive_more::From;
+use fugit::HertzU64;
 
 pub type Result<T> = core::result::Result<T, Error>;
 
@@ -6,7 +7,35 @@ pub type Result<T> = core::result::Result<T, Error>;
 #[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
 pub enum Error {
     // -- register
-    UnknownRegister { reg_addr: u8 },
+    UnknownRegister {
+        reg_addr: u8,
+    },
+    #[from(skip)]
+    RegisterMapFull {
+        reg_addr: u8,
+    },
+    // -- chip
+    /// The input clock is outside the reference range the chip PLL math was validated with
+    #[from(skip)]
+    UnsupportedInputClock {
+        clk: HertzU64,
+    },
+    // -- pll
+    /// The PLL output index is not below `PLL_OUT_MAX`
+    #[from(skip)]
+    InvalidPllOutput {
+        out: usize,
+    },
+    /// The PLL output divider does not fit in its 4 bits field
+    #[from(skip)]
+    InvalidPllDivider {
+        div: u8,
+    },
+    // -- core register
+    #[from(skip)]
+    CoreRegisterMapFull {
+        core_reg_id: u8,
+    },
 }
 
 #[rustversion::since(1.81)]
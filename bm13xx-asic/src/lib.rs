@@ -9,20 +9,90 @@ mod error;
 pub mod pll;
 pub mod register;
 pub mod sha;
+mod snapshot;
 
 pub use self::error::{Error, Result};
+pub use self::snapshot::RegisterSnapshot;
 
-use bm13xx_protocol::command::Destination;
+use bm13xx_protocol::command::{Command, Destination};
+use register::CoreRegisterControl;
 
+use core::time::Duration;
 use fugit::HertzU64;
 
+/// A command frame to send, and the delay to wait after it.
+///
+/// Most frames are register writes, `N` is only different for the shorter chain commands
+/// like in `bring_up_sequence`.
 #[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
-pub struct CmdDelay {
-    pub cmd: [u8; 11],
+pub struct CmdDelay<const N: usize = 11> {
+    pub cmd: [u8; N],
     pub delay_ms: u32,
 }
 
+/// ## Bring-up sequence of an inactive chain
+///
+/// Sends `inactive_cnt` ChainInactive commands, then gives `i * interval` as address to
+/// the i-th of the `asic_cnt` asics. Older asics need the ChainInactive command 3 times.
+///
+/// ### Example
+/// ```
+/// use bm13xx_asic::{bring_up_sequence, CmdDelay};
+/// use bm13xx_protocol::command::Command;
+///
+/// let steps: Vec<CmdDelay<7>> = bring_up_sequence(4, 64, 3).collect();
+/// assert_eq!(
+///     steps,
+///     [
+///         CmdDelay { cmd: Command::chain_inactive(), delay_ms: 2 },
+///         CmdDelay { cmd: Command::chain_inactive(), delay_ms: 2 },
+///         CmdDelay { cmd: Command::chain_inactive(), delay_ms: 30 },
+///         CmdDelay { cmd: Command::set_chip_addr(0), delay_ms: 10 },
+///         CmdDelay { cmd: Command::set_chip_addr(64), delay_ms: 10 },
+///         CmdDelay { cmd: Command::set_chip_addr(128), delay_ms: 10 },
+///         CmdDelay { cmd: Command::set_chip_addr(192), delay_ms: 110 },
+///     ]
+/// );
+/// assert_eq!(steps[6].cmd, [0x55, 0xaa, 0x40, 0x05, 0xc0, 0x00, 0x16]);
+///
+/// let steps: Vec<CmdDelay<7>> = bring_up_sequence(2, 2, 1).collect();
+/// assert_eq!(
+///     steps,
+///     [
+///         CmdDelay { cmd: Command::chain_inactive(), delay_ms: 30 },
+///         CmdDelay { cmd: Command::set_chip_addr(0), delay_ms: 10 },
+///         CmdDelay { cmd: Command::set_chip_addr(2), delay_ms: 110 },
+///     ]
+/// );
+/// ```
+pub fn bring_up_sequence(
+    asic_cnt: u8,
+    interval: u16,
+    inactive_cnt: usize,
+) -> impl Iterator<Item = CmdDelay<7>> {
+    let inactive = (0..inactive_cnt).map(move |i| CmdDelay {
+        cmd: Command::chain_inactive(),
+        delay_ms: if i + 1 == inactive_cnt { 30 } else { 2 },
+    });
+    let addressing = (0..asic_cnt as u16).map(move |i| CmdDelay {
+        cmd: Command::set_chip_addr((i * interval) as u8),
+        // the last asic also waits for the whole chain to be addressed
+        delay_ms: if i + 1 == asic_cnt as u16 { 110 } else { 10 },
+    });
+    inactive.chain(addressing)
+}
+
+/// Clock path and actual baudrate a chip would use for a requested baudrate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+pub struct BaudratePlan {
+    /// The UART PLL is used as baudrate base clock, instead of CLKI
+    pub uses_pll: bool,
+    /// Baudrate actually achievable, after BT8D divider rounding
+    pub achievable_baud: u32,
+}
+
 #[derive(Debug, Default, Clone, PartialEq)]
 #[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
 pub enum SequenceStep {
@@ -33,11 +103,86 @@ pub enum SequenceStep {
     ResetCore(usize),
     HashFreq(usize),
     VersionRolling(usize),
+    VersionMask(usize),
+    ClockOrder(usize),
+}
+
+impl SequenceStep {
+    /// Whether a sequence has been started and not yet completed.
+    ///
+    /// ### Example
+    /// ```
+    /// use bm13xx_asic::SequenceStep;
+    ///
+    /// assert!(!SequenceStep::None.is_in_progress());
+    /// assert!(SequenceStep::Init(0).is_in_progress());
+    /// assert!(SequenceStep::HashFreq(3).is_in_progress());
+    /// ```
+    pub const fn is_in_progress(&self) -> bool {
+        !matches!(self, SequenceStep::None)
+    }
 }
 
 pub trait Asic {
     fn chip_id(&self) -> u16;
+    /// Human readable chip name, derived from `chip_id`.
+    fn chip_name(&self) -> &'static str {
+        match self.chip_id() {
+            0x1366 => "BM1366",
+            0x1368 => "BM1368",
+            0x1370 => "BM1370",
+            0x1397 => "BM1397",
+            _ => "BM13xx",
+        }
+    }
+    fn core_small_core_count(&self) -> usize;
+    /// Total number of Small Cores in a chip.
+    fn small_core_count(&self) -> usize;
+    /// Number of Domains inside a chip.
+    ///
+    /// These are internal to the chip, not to be confused with the hashboard voltage domains.
+    fn domain_count(&self) -> usize;
     fn has_version_rolling(&self) -> bool;
+    /// Whether [`Asic::set_baudrate_next_domains`] supports hashboard voltage domains of
+    /// different sizes.
+    fn supports_mixed_domains(&self) -> bool {
+        false
+    }
+    /// Highest hash frequency the chip PLL can safely be programmed to.
+    fn max_hash_freq(&self) -> HertzU64;
+    /// Hash frequency the chip model PLL is currently programmed to.
+    fn hash_freq(&self) -> HertzU64;
+    /// Achievable hash frequency nearest to `target_freq`, without changing the chip model.
+    fn nearest_hash_freq(&self, target_freq: HertzU64) -> HertzU64;
+    /// Time the chips take to roll the whole space of a job at the current hash frequency.
+    fn rolling_duration(&self) -> Duration;
+    /// Clock path and actual baudrate `set_baudrate_next` would use for `baudrate`.
+    fn baudrate_plan(&self, baudrate: u32) -> BaudratePlan;
+    /// Reset the chip model state, to be called whenever the chip NRST pin is asserted.
+    fn reset(&mut self);
+    /// Current step of the resumable `*_next` sequences, `SequenceStep::None` when idle.
+    fn sequence_step(&self) -> SequenceStep;
+    /// Value of a register in the chip model, `None` if the chip does not have it.
+    fn register(&self, reg_addr: u8) -> Option<u32>;
+    /// Value of a core register in the chip model, `None` if the chip model does not track it.
+    fn core_register(&self, core_reg_id: u8) -> Option<u8>;
+    /// Write a register of `dest`, updating the chip model if it tracks it.
+    fn write_reg_next(&mut self, reg_addr: u8, reg_val: u32, dest: Destination) -> CmdDelay;
+    /// Write a core register of all the cores of `dest`, updating the chip model if it tracks it.
+    fn write_core_reg_next(
+        &mut self,
+        core_reg_id: u8,
+        core_reg_val: u8,
+        dest: Destination,
+    ) -> CmdDelay;
+    /// Command latching a core register of `dest` core 0 into `CoreRegisterValue`, to be read next.
+    fn read_core_reg_cmd(&self, core_reg_id: u8, dest: Destination) -> [u8; 11] {
+        Command::write_reg(
+            CoreRegisterControl::ADDR,
+            CoreRegisterControl::read_core_reg_id(0, core_reg_id),
+            dest,
+        )
+    }
     fn init_next(&mut self, diffculty: u32) -> Option<CmdDelay>;
     fn set_baudrate_next(
         &mut self,
@@ -46,7 +191,58 @@ pub trait Asic {
         domain_asic_cnt: u8,
         asic_addr_interval: u16,
     ) -> Option<CmdDelay>;
+    /// Same as [`Asic::set_baudrate_next`] for a chain whose voltage domains do not all hold
+    /// the same number of ASICs, `domain_asic_cnts` giving the ASIC count of each domain.
+    ///
+    /// The default implementation uses the first domain size, and sends nothing for domains
+    /// of different sizes unless [`Asic::supports_mixed_domains`].
+    fn set_baudrate_next_domains(
+        &mut self,
+        baudrate: u32,
+        domain_asic_cnts: &[u8],
+        asic_addr_interval: u16,
+    ) -> Option<CmdDelay> {
+        if !self.supports_mixed_domains() && domain_asic_cnts.windows(2).any(|w| w[0] != w[1]) {
+            return None;
+        }
+        self.set_baudrate_next(
+            baudrate,
+            domain_asic_cnts.len() as u8,
+            domain_asic_cnts.first().copied().unwrap_or(0),
+            asic_addr_interval,
+        )
+    }
     fn reset_core_next(&mut self, dest: Destination) -> Option<CmdDelay>;
-    fn set_hash_freq_next(&mut self, target_freq: HertzU64) -> Option<CmdDelay>;
+    fn set_hash_freq_next(&mut self, target_freq: HertzU64) -> Option<CmdDelay> {
+        self.set_hash_freq_next_dest(target_freq, Destination::All)
+    }
+    /// Same as [`Asic::set_hash_freq_next`] but only for the chip(s) at `dest`.
+    ///
+    /// Chip-specific values are not saved in the registers model. Chip models keeping a
+    /// sequence step per chip allow to interleave the steps of several chips.
+    fn set_hash_freq_next_dest(
+        &mut self,
+        target_freq: HertzU64,
+        dest: Destination,
+    ) -> Option<CmdDelay>;
+    /// Same as [`Asic::set_hash_freq_next`] but jumping to `target_freq` at once,
+    /// without the progressive ramp.
+    ///
+    /// The default implementation ramps anyway.
+    fn set_hash_freq_direct_next(&mut self, target_freq: HertzU64) -> Option<CmdDelay> {
+        self.set_hash_freq_next(target_freq)
+    }
     fn set_version_rolling_next(&mut self, mask: u32) -> Option<CmdDelay>;
+    /// Change the Version Rolling mask, whether Version Rolling is enabled or not.
+    ///
+    /// The default implementation does nothing, for chips without Version Rolling.
+    fn set_version_mask_next(&mut self, _mask: u32) -> Option<CmdDelay> {
+        None
+    }
+    /// Program the clock order, `order[x]` being the value of `CLK_SELx`.
+    ///
+    /// The default implementation does nothing, for chips without clock ordering.
+    fn set_clock_order_next(&mut self, _order: &[u8; 16]) -> Option<CmdDelay> {
+        None
+    }
 }
@@ -2,7 +2,22 @@ use core::f64;
 
 use fugit::HertzU64;
 
+use crate::{
+    register::{
+        PLL0Divider, PLL0Parameter, PLL1Divider, PLL1Parameter, PLL2Divider, PLL2Parameter,
+        PLL3Divider, PLL3Parameter,
+    },
+    Error, Result,
+};
+
 pub const PLL_OUT_MAX: usize = 5;
+/// Addresses of the PLLnParameter and PLLnDivider registers, indexed by PLL ID.
+pub const PLL_REG_ADDRS: [(u8, u8); 4] = [
+    (PLL0Parameter::ADDR, PLL0Divider::ADDR),
+    (PLL1Parameter::ADDR, PLL1Divider::ADDR),
+    (PLL2Parameter::ADDR, PLL2Divider::ADDR),
+    (PLL3Parameter::ADDR, PLL3Divider::ADDR),
+];
 const PLL_VCO_FREQ_MAX: HertzU64 = HertzU64::MHz(3200);
 const PLL_VCO_FREQ_HIGH: HertzU64 = HertzU64::MHz(2400);
 const PLL_VCO_FREQ_MIN: HertzU64 = HertzU64::MHz(2000);
@@ -37,6 +52,60 @@ impl Pll {
     const POSTDIV1_MASK: u32 = 0x7;
     const POSTDIV2_MASK: u32 = 0x7;
 
+    /// ## Create an enabled and locked PLL from its dividers.
+    ///
+    /// The dividers are the raw register values, as given to `set_fb_div`, `set_ref_div`,
+    /// `set_post1_div` and `set_post2_div`. The output dividers are left to 0.
+    ///
+    /// ### Example
+    /// ```
+    /// use fugit::HertzU64;
+    /// use bm13xx_asic::pll::Pll;
+    ///
+    /// let clki = HertzU64::MHz(25);
+    /// let mut pll = Pll::from_dividers(96, 1, 6, 1);
+    /// assert_eq!(pll.parameter(), 0xC060_0161); // BM1397 PLL0 default parameter
+    /// let mut default = Pll::default();
+    /// default.set_parameter(0xC060_0161);
+    /// assert_eq!(pll.frequency(clki, 0), default.frequency(clki, 0));
+    /// pll.set_divider(0x0304_0607); // BM1397 PLL0 default divider
+    /// assert_eq!(pll.frequency(clki, 0), HertzU64::Hz(21428571));
+    /// ```
+    pub fn from_dividers(fb_div: u16, ref_div: u8, post1_div: u8, post2_div: u8) -> Self {
+        let mut pll = Self::default();
+        pll.enable()
+            .lock()
+            .set_fb_div(fb_div)
+            .set_ref_div(ref_div)
+            .set_post1_div(post1_div)
+            .set_post2_div(post2_div);
+        pll
+    }
+
+    /// ## Create a PLL from its PLLnParameter and PLLnDivider registers values.
+    ///
+    /// ### Example
+    /// ```
+    /// use bm13xx_asic::pll::Pll;
+    ///
+    /// let pll = Pll::from_registers(0xC060_0161, 0x0304_0607); // BM1397 PLL0 default values
+    /// assert_eq!(pll.to_registers(), (0xC060_0161, 0x0304_0607));
+    /// assert_eq!(pll.fb_div(), 96);
+    /// assert_eq!(pll.out_div(0), 7);
+    /// ```
+    pub fn from_registers(parameter: u32, divider: u32) -> Self {
+        let mut pll = Self::default();
+        pll.set_parameter(parameter).set_divider(divider);
+        pll
+    }
+
+    /// ## Get the PLLnParameter and PLLnDivider registers values.
+    ///
+    /// Same as `(self.parameter(), self.divider())`.
+    pub const fn to_registers(&self) -> (u32, u32) {
+        (self.parameter(), self.divider())
+    }
+
     /// ## Handle the PLL Parameter.
     ///
     /// ### Example
@@ -118,6 +187,45 @@ impl Pll {
         self
     }
 
+    /// ## Set a PLL output Divider, checking its arguments.
+    ///
+    /// Same as `set_out_div`, but returns an error instead of ignoring an out of bound
+    /// output index or masking an out of bound divider. The PLL is left untouched on error.
+    ///
+    /// ### Example
+    /// ```
+    /// use bm13xx_asic::{pll::Pll, Error};
+    ///
+    /// let mut pll = Pll::default();
+    /// assert_eq!(pll.try_set_out_div(4, 15).unwrap().out_div(4), 15); // max value
+    /// assert_eq!(pll.try_set_out_div(5, 10).unwrap_err(), Error::InvalidPllOutput { out: 5 });
+    /// assert_eq!(pll.try_set_out_div(2, 16).unwrap_err(), Error::InvalidPllDivider { div: 16 });
+    /// assert_eq!(pll.out_dividers(), [0, 0, 0, 0, 15]);
+    /// ```
+    pub fn try_set_out_div(&mut self, out: usize, div: u8) -> Result<&mut Self> {
+        if out >= PLL_OUT_MAX {
+            return Err(Error::InvalidPllOutput { out });
+        }
+        if div > 0xf {
+            return Err(Error::InvalidPllDivider { div });
+        }
+        Ok(self.set_out_div(out, div))
+    }
+
+    /// ## Get all the PLL output Dividers.
+    ///
+    /// ### Example
+    /// ```
+    /// use bm13xx_asic::pll::Pll;
+    ///
+    /// let mut pll = Pll::default();
+    /// pll.set_divider(0x0304_0607).set_out_div(4, 6); // BM1397 PLL0 default divider
+    /// assert_eq!(pll.out_dividers(), [7, 6, 4, 3, 6]);
+    /// ```
+    pub const fn out_dividers(&self) -> [u8; PLL_OUT_MAX] {
+        self.out_div
+    }
+
     /// ## Get the PLL VCO Frequency.
     ///
     /// ### Example
@@ -197,15 +305,9 @@ impl Pll {
                             }
                             let vco_freq = pll.vco_freq(in_clk_freq);
                             pll.vco_high_freq = vco_freq > PLL_VCO_FREQ_HIGH;
-                            if (pll.ref_div > 1 || vco_freq <= HertzU64::MHz(3125))
-                                && (vco_freq <= PLL_VCO_FREQ_MAX)
-                                && (vco_freq > PLL_VCO_FREQ_MIN)
-                            {
-                                let freq_diff = if target_freq > pll.frequency(in_clk_freq, out) {
-                                    target_freq - pll.frequency(in_clk_freq, out)
-                                } else {
-                                    pll.frequency(in_clk_freq, out) - target_freq
-                                };
+                            if pll.vco_in_window(vco_freq) {
+                                let freq_diff =
+                                    abs_diff(pll.frequency(in_clk_freq, out), target_freq);
                                 if freq_diff < HertzU64::MHz(1) {
                                     *self = pll;
                                     return self;
@@ -219,6 +321,195 @@ impl Pll {
         self
     }
 
+    /// ## Get the achievable PLL Frequency nearest to a target, for a given output.
+    ///
+    /// Searches every divider setting keeping the VCO in its window, without changing the
+    /// PLL, and stops early on an exact match. `set_frequency` keeps the first setting
+    /// within 1 MHz of the target instead, so it may program another one.
+    /// Returns 0 Hz if no setting keeps the VCO in its window.
+    ///
+    /// ### Example
+    /// ```
+    /// use fugit::HertzU64;
+    /// use bm13xx_asic::pll::Pll;
+    ///
+    /// let clki = HertzU64::MHz(25);
+    /// let pll = Pll::default();
+    /// assert_eq!(pll.nearest_achievable(clki, 0, HertzU64::MHz(425)), HertzU64::MHz(425));
+    /// assert_eq!(pll.nearest_achievable(clki, 0, HertzU64::Hz(487_654_321)), HertzU64::kHz(487_500));
+    /// assert_eq!(pll.nearest_achievable(clki, 0, HertzU64::MHz(5000)), HertzU64::MHz(0));
+    /// assert_eq!(pll.nearest_achievable(clki, 5, HertzU64::MHz(425)), HertzU64::MHz(0));
+    /// assert_eq!(pll.parameter(), Pll::default().parameter()); // unchanged
+    /// ```
+    pub fn nearest_achievable(
+        &self,
+        in_clk_freq: HertzU64,
+        out: usize,
+        target_freq: HertzU64,
+    ) -> HertzU64 {
+        self.search(in_clk_freq, out, target_freq)
+            .map_or(HertzU64::MHz(0), |pll| pll.frequency(in_clk_freq, out))
+    }
+
+    /// Find the dividers giving the frequency nearest to `target_freq` on output `out`.
+    ///
+    /// The PLL is considered locked, the first setting found is kept on equal distance.
+    fn search(&self, in_clk_freq: HertzU64, out: usize, target_freq: HertzU64) -> Option<Self> {
+        if out >= PLL_OUT_MAX {
+            return None;
+        }
+        let mut best: Option<(Self, HertzU64)> = None;
+        let mut pll = *self;
+        pll.out_div[out] = 0;
+        for ref_div in (1..=2).rev() {
+            pll.ref_div = ref_div;
+            for post2_div in 0..=7 {
+                pll.post2_div = post2_div;
+                for post1_div in post2_div..=7 {
+                    pll.post1_div = post1_div;
+                    let fb_div = (((post1_div + 1) as f64
+                        * (post2_div + 1) as f64
+                        * target_freq.raw() as f64
+                        * ref_div as f64
+                        / in_clk_freq.raw() as f64)
+                        + 0.5) as u16;
+                    if fb_div < 251 {
+                        pll.fb_div = fb_div;
+                        pll.enable();
+                        pll.lock();
+                        let vco_freq = pll.vco_freq(in_clk_freq);
+                        pll.vco_high_freq = vco_freq > PLL_VCO_FREQ_HIGH;
+                        if pll.vco_in_window(vco_freq) {
+                            let freq_diff = abs_diff(pll.frequency(in_clk_freq, out), target_freq);
+                            if freq_diff == HertzU64::Hz(0) {
+                                return Some(pll);
+                            }
+                            if best.map_or(true, |(_, best_diff)| freq_diff < best_diff) {
+                                best = Some((pll, freq_diff));
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        best.map(|(pll, _)| pll)
+    }
+
+    /// ## Move the PLL Frequency for a given output by `delta_hz`.
+    ///
+    /// Lighter than `set_frequency` for the small steps of a frequency ramp: only `fb_div`
+    /// is adjusted, keeping the current reference, post and output dividers, as long as
+    /// the VCO stays in its window and the frequency within 1 MHz of the target.
+    /// Otherwise, this falls back to the full search of `set_frequency`, so the
+    /// dividers may differ from the ones `set_frequency` would choose for the same target.
+    ///
+    /// ### Example
+    /// ```
+    /// use fugit::HertzU64;
+    /// use bm13xx_asic::pll::Pll;
+    ///
+    /// let clki = HertzU64::MHz(25);
+    /// let mut pll = Pll::default();
+    /// pll.set_frequency(clki, 0, HertzU64::MHz(400), true);
+    /// let dividers = (pll.ref_div(), pll.post1_div(), pll.post2_div());
+    /// for _ in 0..8 {
+    ///     pll.bump_frequency(clki, 0, 6_250_000, false);
+    ///     assert_eq!((pll.ref_div(), pll.post1_div(), pll.post2_div()), dividers);
+    /// }
+    /// assert_eq!(pll.frequency(clki, 0), HertzU64::MHz(450));
+    /// // out of the VCO window with the current dividers
+    /// pll.bump_frequency(clki, 0, 450_000_000, false);
+    /// assert_ne!((pll.ref_div(), pll.post1_div(), pll.post2_div()), dividers);
+    /// assert_eq!(pll.frequency(clki, 0), HertzU64::MHz(900));
+    /// ```
+    pub fn bump_frequency(
+        &mut self,
+        in_clk_freq: HertzU64,
+        out: usize,
+        delta_hz: i64,
+        lock: bool,
+    ) -> &mut Self {
+        if out >= PLL_OUT_MAX {
+            return self;
+        }
+        let target_freq =
+            HertzU64::Hz((self.frequency(in_clk_freq, out).raw() as i64 + delta_hz).max(0) as u64);
+        if self.enabled && self.ref_div > 0 && in_clk_freq.raw() > 0 {
+            let mut pll = *self;
+            let fb_div = (((pll.post1_div + 1) as f64
+                * (pll.post2_div + 1) as f64
+                * (pll.out_div[out] + 1) as f64
+                * target_freq.raw() as f64
+                * pll.ref_div as f64
+                / in_clk_freq.raw() as f64)
+                + 0.5) as u16;
+            if fb_div < 251 {
+                pll.fb_div = fb_div;
+                if lock {
+                    pll.lock();
+                }
+                let vco_freq = pll.vco_freq(in_clk_freq);
+                pll.vco_high_freq = vco_freq > PLL_VCO_FREQ_HIGH;
+                let freq_diff = abs_diff(pll.frequency(in_clk_freq, out), target_freq);
+                if pll.vco_in_window(vco_freq) && freq_diff < HertzU64::MHz(1) {
+                    *self = pll;
+                    return self;
+                }
+            }
+        }
+        self.set_frequency(in_clk_freq, out, target_freq, lock)
+    }
+
+    /// Whether the VCO can run at `vco_freq` with the current reference divider.
+    fn vco_in_window(&self, vco_freq: HertzU64) -> bool {
+        (self.ref_div > 1 || vco_freq <= HertzU64::MHz(3125))
+            && (vco_freq <= PLL_VCO_FREQ_MAX)
+            && (vco_freq > PLL_VCO_FREQ_MIN)
+    }
+
+    /// ## Get the PLL Frequency of every output.
+    ///
+    /// Same as calling `frequency` for each output, in output order.
+    ///
+    /// ### Example
+    /// ```
+    /// use fugit::HertzU64;
+    /// use bm13xx_asic::pll::Pll;
+    ///
+    /// let pll = Pll::from_registers(0xC060_0161, 0x0304_0607); // BM1397 PLL0 default values
+    /// assert_eq!(
+    ///     pll.all_frequencies(HertzU64::MHz(25)),
+    ///     [
+    ///         HertzU64::Hz(21428571),
+    ///         HertzU64::Hz(24489795),
+    ///         HertzU64::Hz(34285714),
+    ///         HertzU64::Hz(42857142),
+    ///         HertzU64::Hz(171428571),
+    ///     ]
+    /// );
+    /// ```
+    pub fn all_frequencies(&self, in_clk_freq: HertzU64) -> [HertzU64; PLL_OUT_MAX] {
+        core::array::from_fn(|out| self.frequency(in_clk_freq, out))
+    }
+
+    /// ## Get the PLL Frequency for a given output, in MHz.
+    ///
+    /// ### Example
+    /// ```
+    /// use fugit::HertzU64;
+    /// use bm13xx_asic::pll::Pll;
+    ///
+    /// let clki = HertzU64::MHz(25);
+    /// let mut pll = Pll::default();
+    /// pll.set_parameter(0xC060_0161); // BM1397 PLL0 default value
+    /// pll.set_divider(0x0304_0607); // BM1397 PLL0 default divider
+    /// assert_eq!(pll.frequency_mhz(clki, 0), 21.428571);
+    /// assert_eq!(pll.frequency_mhz(clki, 5), 0.0);
+    /// ```
+    pub fn frequency_mhz(&self, in_clk_freq: HertzU64, out: usize) -> f32 {
+        self.frequency(in_clk_freq, out).raw() as f32 / 1_000_000.0
+    }
+
     /// ## Handle the PLL locked field.
     ///
     /// ### Example
@@ -384,3 +675,11 @@ impl Pll {
         self
     }
 }
+
+fn abs_diff(a: HertzU64, b: HertzU64) -> HertzU64 {
+    if a > b {
+        a - b
+    } else {
+        b - a
+    }
+}
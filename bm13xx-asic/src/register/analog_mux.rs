@@ -14,17 +14,25 @@ impl AnalogMuxControl {
 
     /// ## Handle the `DIODE_VDD_MUX_SEL` field.
     ///
-    /// Get and set the `DIODE_VDD_MUX_SEL` value.
+    /// Get and set the `DIODE_VDD_MUX_SEL` value, selecting which on-die sensing point
+    /// is routed to the analog mux output.
+    ///
+    /// BM1397 `init()` selects `3`, like BM1366 and BM1370 do with `AnalogMuxControlV2`.
+    /// The setter keeps only the 3 bits of the field, other bits of the register are
+    /// left untouched.
     ///
     /// ### Example
     /// ```
-    /// use bm13xx_asic::register::AnalogMuxControl;
+    /// use bm13xx_asic::register::{AnalogMuxControl, Register};
     ///
     /// let mut ana_mux = AnalogMuxControl(0x0000_0000); // BM1397 default value
     /// assert_eq!(ana_mux.diode_vdd_mux_sel(), 0);
-    /// assert_eq!(ana_mux.set_diode_vdd_mux_sel(3).diode_vdd_mux_sel(), 3);
+    /// assert_eq!(ana_mux.set_diode_vdd_mux_sel(3).diode_vdd_mux_sel(), 3); // BM1397 init() value
     /// assert_eq!(ana_mux.set_diode_vdd_mux_sel(0x7).diode_vdd_mux_sel(), 0x7); // max value
     /// assert_eq!(ana_mux.set_diode_vdd_mux_sel(0x8).diode_vdd_mux_sel(), 0); // out of bound value
+    ///
+    /// let mut ana_mux = AnalogMuxControl(0xFFFF_FFF8);
+    /// assert_eq!(ana_mux.set_diode_vdd_mux_sel(0xb).val(), 0xFFFF_FFFB); // other bits preserved
     /// ```
     pub const fn diode_vdd_mux_sel(&self) -> u8 {
         ((self.0 >> Self::DIODE_VDD_MUX_SEL_OFFSET) & Self::DIODE_VDD_MUX_SEL_MASK) as u8
@@ -50,7 +58,7 @@ impl defmt::Format for AnalogMuxControl {
     fn format(&self, fmt: defmt::Formatter) {
         defmt::write!(
             fmt,
-            "AnalogMuxControlV2 {{ diode_vdd_mux_sel: {} }}",
+            "AnalogMuxControl {{ diode_vdd_mux_sel: {} }}",
             self.diode_vdd_mux_sel(),
         );
     }
@@ -70,17 +78,27 @@ impl AnalogMuxControlV2 {
 
     /// ## Handle the `DIODE_VDD_MUX_SEL` field.
     ///
-    /// Get and set the `DIODE_VDD_MUX_SEL` value.
+    /// Get and set the `DIODE_VDD_MUX_SEL` value, selecting which on-die sensing point
+    /// is routed to the analog mux output.
+    ///
+    /// The meaning of each selector value is not documented. Both BM1366 and BM1370
+    /// `init()` select `3`, as the vendor firmware does, `0` being the reset value.
+    ///
+    /// The setter keeps only the 4 bits of the field, other bits of the register are
+    /// left untouched.
     ///
     /// ### Example
     /// ```
-    /// use bm13xx_asic::register::AnalogMuxControlV2;
+    /// use bm13xx_asic::register::{AnalogMuxControlV2, Register};
     ///
     /// let mut ana_mux = AnalogMuxControlV2(0x0000_0000); // BM1366 default value
     /// assert_eq!(ana_mux.diode_vdd_mux_sel(), 0);
     /// assert_eq!(ana_mux.set_diode_vdd_mux_sel(3).diode_vdd_mux_sel(), 3); // BM1366 init() value
     /// assert_eq!(ana_mux.set_diode_vdd_mux_sel(0xf).diode_vdd_mux_sel(), 0xf); // max value
     /// assert_eq!(ana_mux.set_diode_vdd_mux_sel(0x10).diode_vdd_mux_sel(), 0); // out of bound value
+    ///
+    /// let mut ana_mux = AnalogMuxControlV2(0xFFFF_FFF0);
+    /// assert_eq!(ana_mux.set_diode_vdd_mux_sel(0x13).val(), 0xFFFF_FFF3); // other bits preserved
     /// ```
     pub const fn diode_vdd_mux_sel(&self) -> u8 {
         ((self.0 >> Self::DIODE_VDD_MUX_SEL_OFFSET) & Self::DIODE_VDD_MUX_SEL_MASK) as u8
@@ -3,6 +3,21 @@ use crate::register::Register;
 /// # Chip Identification register
 ///
 /// Used to identify a chip.
+///
+/// The whole register is made of `CHIP_ID[31:16]`, `CORE_NUM[15:8]` and `ADDR[7:0]`:
+/// it does not hold any silicon revision. For example, the `0x1800` low half of the
+/// BM1397 `0x1397_1800` value is 24 cores, at address 0.
+///
+/// ### Example
+///
+/// ```
+/// use bm13xx_asic::register::ChipIdentification;
+///
+/// let bm1397 = ChipIdentification(0x1397_1800);
+/// assert_eq!((bm1397.chip_id(), bm1397.core_num(), bm1397.chip_addr()), (0x1397, 24, 0));
+/// let bm1370 = ChipIdentification(0x1370_0000);
+/// assert_eq!((bm1370.chip_id(), bm1370.core_num(), bm1370.chip_addr()), (0x1370, 0, 0));
+/// ```
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
 pub struct ChipIdentification(pub u32);
 impl_boilerplate_for!(ChipIdentification);
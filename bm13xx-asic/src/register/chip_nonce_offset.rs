@@ -36,22 +36,156 @@ impl_boilerplate_for!(ChipNonceOffsetV2);
 impl ChipNonceOffsetV2 {
     pub const ADDR: u8 = 0x0C;
 
-    // const CNOV_OFFSET: u8 = 31;
-    // const CNO_OFFSET: u8 = 0;
+    const CNOV_OFFSET: u8 = 31;
+    const CNO_OFFSET: u8 = 0;
 
-    // const CNOV_MASK: u32 = 0b1;
-    // const CNO_MASK: u32 = 0xffff;
+    const CNOV_MASK: u32 = 0b1;
+    const CNO_MASK: u32 = 0xffff;
+
+    /// ## Build the Chip Nonce Offset of a chip in the chain
+    ///
+    /// The 16 bits offset space is evenly split between the `chain_asic_num` chips,
+    /// the chip at index `chip_index` getting the start of its slice, as done by the
+    /// S21Pro firmware. `chain_asic_num` is clamped to `1..=65536`, the size of the
+    /// offset space.
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// use bm13xx_asic::register::ChipNonceOffsetV2;
+    ///
+    /// assert_eq!(ChipNonceOffsetV2::new(0, 65), ChipNonceOffsetV2(0x8000_0000));
+    /// assert_eq!(ChipNonceOffsetV2::new(1, 65), ChipNonceOffsetV2(0x8000_03F0));
+    /// assert_eq!(ChipNonceOffsetV2::new(64, 65), ChipNonceOffsetV2(0x8000_FC00));
+    /// assert_eq!(ChipNonceOffsetV2::new(0, 0), ChipNonceOffsetV2(0x8000_0000));
+    /// assert_eq!(ChipNonceOffsetV2::new(1, 100_000), ChipNonceOffsetV2(0x8000_0001));
+    /// ```
+    pub const fn new(chip_index: usize, chain_asic_num: usize) -> Self {
+        Self(
+            (Self::CNOV_MASK << Self::CNOV_OFFSET)
+                | ((Self::slice_len(chain_asic_num) * chip_index as u32) & Self::CNO_MASK)
+                    << Self::CNO_OFFSET,
+        )
+    }
+
+    /// Length of the slice of the offset space given to each of the `chain_asic_num` chips.
+    const fn slice_len(chain_asic_num: usize) -> u32 {
+        let space = Self::CNO_MASK as usize + 1;
+        if chain_asic_num == 0 {
+            space as u32
+        } else if chain_asic_num > space {
+            1
+        } else {
+            (space / chain_asic_num) as u32
+        }
+    }
+
+    /// ## Handle the CNOV field.
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// use bm13xx_asic::register::ChipNonceOffsetV2;
+    ///
+    /// let mut cno = ChipNonceOffsetV2(0x0000_0000); // BM1370 default value
+    /// assert!(!cno.valid());
+    /// assert!(cno.set_valid(true).valid());
+    /// assert!(!cno.set_valid(false).valid());
+    /// ```
+    pub const fn valid(&self) -> bool {
+        (self.0 >> Self::CNOV_OFFSET) & Self::CNOV_MASK == Self::CNOV_MASK
+    }
+    pub fn set_valid(&mut self, valid: bool) -> &mut Self {
+        self.0 &= !(Self::CNOV_MASK << Self::CNOV_OFFSET);
+        self.0 |= (valid as u32) << Self::CNOV_OFFSET;
+        self
+    }
+
+    /// ## Handle the CNO field.
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// use bm13xx_asic::register::ChipNonceOffsetV2;
+    ///
+    /// let mut cno = ChipNonceOffsetV2(0x0000_0000); // BM1370 default value
+    /// assert_eq!(cno.offset(), 0);
+    /// assert_eq!(cno.set_offset(0xffff).offset(), 0xffff); // max value
+    /// assert!(!cno.valid());
+    /// ```
+    pub const fn offset(&self) -> u16 {
+        ((self.0 >> Self::CNO_OFFSET) & Self::CNO_MASK) as u16
+    }
+    pub fn set_offset(&mut self, offset: u16) -> &mut Self {
+        self.0 &= !(Self::CNO_MASK << Self::CNO_OFFSET);
+        self.0 |= (offset as u32) << Self::CNO_OFFSET;
+        self
+    }
+
+    /// ## Get the index of the chip that produced a given Nonce
+    ///
+    /// Reverse of [`ChipNonceOffsetV2::new`], assuming the offset applies to Nonce\[31:16\].
+    /// The CNO field is 16 bits wide, so is the part of the Nonce it offsets.
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// use bm13xx_asic::register::ChipNonceOffsetV2;
+    ///
+    /// for chip_index in 0..65 {
+    ///     let cno = ChipNonceOffsetV2::new(chip_index, 65);
+    ///     let first_nonce = (cno.offset() as u32) << 16;
+    ///     assert_eq!(ChipNonceOffsetV2::chip_from_nonce(first_nonce, 65), chip_index);
+    ///     assert_eq!(ChipNonceOffsetV2::chip_from_nonce(first_nonce + 0x03EF_FFFF, 65), chip_index);
+    /// }
+    /// // the remainder of the offset space belongs to the last chip
+    /// assert_eq!(ChipNonceOffsetV2::chip_from_nonce(0xFFFF_FFFF, 65), 64);
+    /// assert_eq!(ChipNonceOffsetV2::chip_from_nonce(0xFFFF_FFFF, 0), 0);
+    /// assert_eq!(ChipNonceOffsetV2::chip_from_nonce(0x0001_0000, 100_000), 1);
+    ///
+    /// // cross-check against the offsets of the BM1370 S21Pro sequence, 13 domains of 5 asics
+    /// let (chain_domain_cnt, domain_asic_cnt) = (13, 5);
+    /// for i in 0..chain_domain_cnt {
+    ///     for j in 0..domain_asic_cnt {
+    ///         let nonce_offset = 0x8000_0000
+    ///             + (65_536 / (chain_domain_cnt * domain_asic_cnt) as u32)
+    ///                 * (i * domain_asic_cnt + j) as u32;
+    ///         let cno = ChipNonceOffsetV2::new(i * domain_asic_cnt + j, 65);
+    ///         assert_eq!(cno, ChipNonceOffsetV2(nonce_offset));
+    ///         let nonce = (nonce_offset << 16) | 0x1234;
+    ///         assert_eq!(ChipNonceOffsetV2::chip_from_nonce(nonce, 65), i * domain_asic_cnt + j);
+    ///     }
+    /// }
+    /// ```
+    pub const fn chip_from_nonce(nonce: u32, chain_asic_num: usize) -> usize {
+        let chip_index = (nonce >> 16) as usize / Self::slice_len(chain_asic_num) as usize;
+        if chip_index < chain_asic_num {
+            chip_index
+        } else if chain_asic_num == 0 {
+            0
+        } else {
+            chain_asic_num - 1
+        }
+    }
 }
 
 impl core::fmt::Display for ChipNonceOffsetV2 {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        f.debug_struct("ChipNonceOffsetV2").finish()
+        f.debug_struct("ChipNonceOffsetV2")
+            .field("valid", &self.valid())
+            .field("offset", &self.offset())
+            .finish()
     }
 }
 
 #[cfg(feature = "defmt-03")]
 impl defmt::Format for ChipNonceOffsetV2 {
     fn format(&self, fmt: defmt::Formatter) {
-        defmt::write!(fmt, "ChipNonceOffsetV2 {{  }}",);
+        defmt::write!(
+            fmt,
+            "ChipNonceOffsetV2 {{ valid: {}, offset: {} }}",
+            self.valid(),
+            self.offset(),
+        );
     }
 }
@@ -282,21 +282,45 @@ impl_boilerplate_for!(ClockOrderStatus);
 impl ClockOrderStatus {
     pub const ADDR: u8 = 0x8C;
 
-    // const CLOK_ORDER_STATUS_OFFSET: u8 = 0;
+    const CLOK_ORDER_STATUS_OFFSET: u8 = 0;
 
-    // const CLOK_ORDER_STATUS_MASK: u32 = 0xffff_ffff;
+    const CLOK_ORDER_STATUS_MASK: u32 = 0xffff_ffff;
+
+    /// ## Get the CLOK_ORDER_STATUS field.
+    ///
+    /// The meaning of the individual bits is not documented, the whole field is
+    /// returned as is.
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// use bm13xx_asic::register::ClockOrderStatus;
+    ///
+    /// assert_eq!(ClockOrderStatus(0x0000_0000).status(), 0); // BM1397 default value
+    /// assert_eq!(ClockOrderStatus(0x0000_FFFF).status(), 0x0000_FFFF);
+    /// assert_eq!(ClockOrderStatus(0xFFFF_FFFF).status(), 0xFFFF_FFFF);
+    /// ```
+    pub const fn status(&self) -> u32 {
+        (self.0 >> Self::CLOK_ORDER_STATUS_OFFSET) & Self::CLOK_ORDER_STATUS_MASK
+    }
 }
 
 impl core::fmt::Display for ClockOrderStatus {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        f.debug_struct("ClockOrderStatus").finish()
+        f.debug_struct("ClockOrderStatus")
+            .field("status", &format_args!("{:#010x}", self.status()))
+            .finish()
     }
 }
 
 #[cfg(feature = "defmt-03")]
 impl defmt::Format for ClockOrderStatus {
     fn format(&self, fmt: defmt::Formatter) {
-        defmt::write!(fmt, "ClockOrderStatus {{  }}",);
+        defmt::write!(
+            fmt,
+            "ClockOrderStatus {{ status: {:#010x} }}",
+            self.status()
+        );
     }
 }
 
@@ -309,6 +333,8 @@ impl OrderedClockEnable {
     pub const ADDR: u8 = 0x20;
 
     const EN_MASK: u32 = 0b1;
+    const CLK_CNT: u8 = 16;
+    const ALL_EN_MASK: u32 = 0xffff;
 
     /// ## Handle the enabled fields.
     ///
@@ -346,6 +372,50 @@ impl OrderedClockEnable {
         self.0 = 0x0000_0000;
         self
     }
+
+    /// ## Get the number of enabled clocks.
+    ///
+    /// Only the 16 CLK_ENx fields are counted.
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// use bm13xx_asic::register::{ClockSelect, OrderedClockEnable};
+    ///
+    /// assert_eq!(OrderedClockEnable(0x0000_FFFF).enabled_count(), 16); // BM1397 default value
+    /// assert_eq!(OrderedClockEnable(0x0000_0000).enabled_count(), 0);
+    /// assert_eq!(OrderedClockEnable(0xFFFF_0000).enabled_count(), 0);
+    /// assert_eq!(OrderedClockEnable(0x0000_0000).enable(ClockSelect::CLK3).enabled_count(), 1);
+    /// ```
+    pub const fn enabled_count(&self) -> u32 {
+        (self.0 & Self::ALL_EN_MASK).count_ones()
+    }
+
+    /// ## Enable the first `n` clocks.
+    ///
+    /// CLK0 to CLK`n-1` are enabled and the other ones disabled.
+    /// `n` above 16 enables all the clocks.
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// use bm13xx_asic::register::{ClockSelect, OrderedClockEnable, Register};
+    ///
+    /// let mut clk_ord_en = OrderedClockEnable(0x0000_FFFF); // BM1397 default value
+    /// assert_eq!(clk_ord_en.enable_first_n(8).val(), 0x0000_00FF); // BM1397 init
+    /// assert_eq!(clk_ord_en.enabled_count(), 8);
+    /// assert!(clk_ord_en.enabled(ClockSelect::CLK7));
+    /// assert!(!clk_ord_en.enabled(ClockSelect::CLK8));
+    /// assert_eq!(clk_ord_en.enable_first_n(0).val(), 0x0000_0000);
+    /// assert_eq!(clk_ord_en.enable_first_n(16).val(), 0x0000_FFFF);
+    /// assert_eq!(clk_ord_en.enable_first_n(17).val(), 0x0000_FFFF); // out of bound value
+    /// ```
+    pub fn enable_first_n(&mut self, n: u8) -> &mut Self {
+        let n = n.min(Self::CLK_CNT);
+        self.0 &= !Self::ALL_EN_MASK;
+        self.0 |= ((1u32 << n) - 1) & Self::ALL_EN_MASK;
+        self
+    }
 }
 
 impl core::fmt::Display for OrderedClockEnable {
@@ -31,9 +31,37 @@ impl CoreRegisterControl {
     /// assert_eq!(CoreRegisterControl::read_core_reg(0, ClockDelayCtrl(0x74)), 0x8000_00ff);
     /// ```
     pub fn read_core_reg(core_id: u8, core_reg: impl CoreRegister) -> u32 {
+        Self::read_core_reg_id(core_id, core_reg.id())
+    }
+    /// ## Set CoreRegisterControl for a Core Register Read, given the Core Register ID.
+    ///
+    /// Writing this value to `CoreRegisterControl` latches the core register into
+    /// `CoreRegisterValue`, which is then read back with a regular register read and
+    /// decoded with `CoreRegisterValue::core_reg_val`. `Chain::read_core_register` does both.
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// use bm13xx_asic::register::{CoreRegisterControl, CoreRegisterValue};
+    /// use bm13xx_asic::core_register::{ClockDelayCtrl, HashClockCtrl};
+    /// use bm13xx_protocol::command::{Command, Destination};
+    ///
+    /// assert_eq!(CoreRegisterControl::read_core_reg_id(0, ClockDelayCtrl::ID), 0x8000_00ff);
+    ///
+    /// let read = CoreRegisterControl::read_core_reg_id(0, HashClockCtrl::ID);
+    /// assert_eq!(
+    ///     Command::write_reg(CoreRegisterControl::ADDR, read, Destination::Chip(0)),
+    ///     [0x55, 0xAA, 0x41, 0x09, 0x00, 0x3C, 0x80, 0x00, 0x05, 0xFF, 0x1B]
+    /// );
+    /// assert_eq!(
+    ///     Command::read_reg(CoreRegisterValue::ADDR, Destination::Chip(0)),
+    ///     [0x55, 0xAA, 0x42, 0x05, 0x00, 0x40, 0x09]
+    /// );
+    /// ```
+    pub const fn read_core_reg_id(core_id: u8, core_reg_id: u8) -> u32 {
         (Self::DO_CMD_MASK << Self::DO_CMD_OFFSET)
             | (((core_id as u32) & Self::CORE_ID_MASK) << Self::CORE_ID_OFFSET)
-            | (((core_reg.id() as u32) & Self::CORE_REG_ID_MASK) << Self::CORE_REG_ID_OFFSET)
+            | (((core_reg_id as u32) & Self::CORE_REG_ID_MASK) << Self::CORE_REG_ID_OFFSET)
             | Self::CORE_REG_VAL_MASK
     }
     /// ## Set CoreRegisterControl for a Core Register Write.
@@ -46,11 +74,24 @@ impl CoreRegisterControl {
     ///
     /// assert_eq!(CoreRegisterControl::write_core_reg(0, ClockDelayCtrl(0x74)), 0x8000_8074);
     pub fn write_core_reg(core_id: u8, core_reg: impl CoreRegister) -> u32 {
+        Self::write_core_reg_id(core_id, core_reg.id(), core_reg.val())
+    }
+    /// ## Set CoreRegisterControl for a Core Register Write, given the Core Register ID and value.
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// use bm13xx_asic::register::CoreRegisterControl;
+    /// use bm13xx_asic::core_register::ClockDelayCtrl;
+    ///
+    /// assert_eq!(CoreRegisterControl::write_core_reg_id(0, ClockDelayCtrl::ID, 0x74), 0x8000_8074);
+    /// ```
+    pub const fn write_core_reg_id(core_id: u8, core_reg_id: u8, core_reg_val: u8) -> u32 {
         (Self::DO_CMD_MASK << Self::DO_CMD_OFFSET)
             | (Self::RD_WR_MASK << Self::RD_WR_OFFSET)
             | (((core_id as u32) & Self::CORE_ID_MASK) << Self::CORE_ID_OFFSET)
-            | (((core_reg.id() as u32) & Self::CORE_REG_ID_MASK) << Self::CORE_REG_ID_OFFSET)
-            | (((core_reg.val() as u32) & Self::CORE_REG_VAL_MASK) << Self::CORE_REG_VAL_OFFSET)
+            | (((core_reg_id as u32) & Self::CORE_REG_ID_MASK) << Self::CORE_REG_ID_OFFSET)
+            | (((core_reg_val as u32) & Self::CORE_REG_VAL_MASK) << Self::CORE_REG_VAL_OFFSET)
     }
 }
 
@@ -1,4 +1,5 @@
 use crate::register::Register;
+use fugit::HertzU64;
 
 /// # Fast UART Configuration register
 ///
@@ -76,6 +77,21 @@ impl defmt::Format for FastUARTConfiguration {
 ///
 /// This is used by [`FastUARTConfigurationV2::bclk_sel`] method.
 ///
+/// Only the CLKI and PLL1 sources are known, converting any other value from an `u8` panics.
+///
+/// ### Example
+///
+/// ```
+/// use bm13xx_asic::register::{BaudrateClockSelectV2, FastUARTConfigurationV2};
+///
+/// for (bclk_sel, val) in [(BaudrateClockSelectV2::Clki, 0), (BaudrateClockSelectV2::Pll1, 1)] {
+///     assert_eq!(u8::from(bclk_sel), val);
+///     assert_eq!(BaudrateClockSelectV2::from(val), bclk_sel);
+///     let mut fast_uart_cfg = FastUARTConfigurationV2(0x0130_1A00); // BM1366 default value
+///     assert_eq!(fast_uart_cfg.set_bclk_sel(bclk_sel).bclk_sel(), bclk_sel);
+/// }
+/// ```
+///
 /// [`FastUARTConfigurationV2::bclk_sel`]: crate::register::FastUARTConfigurationV2::bclk_sel
 #[derive(Copy, Clone, Eq, PartialEq, Debug, PartialOrd, Ord, Hash, Default)]
 #[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
@@ -208,9 +224,9 @@ impl FastUARTConfigurationV2 {
     pub const fn pll1_div4(&self) -> u8 {
         ((self.0 >> Self::PLL1_DIV4_OFFSET) & Self::PLL1_DIV4_MASK) as u8
     }
-    pub fn set_pll1_div4(&mut self, pll3_div4: u8) -> &mut Self {
+    pub fn set_pll1_div4(&mut self, pll1_div4: u8) -> &mut Self {
         self.0 &= !(Self::PLL1_DIV4_MASK << Self::PLL1_DIV4_OFFSET);
-        self.0 |= ((pll3_div4 as u32) & Self::PLL1_DIV4_MASK) << Self::PLL1_DIV4_OFFSET;
+        self.0 |= ((pll1_div4 as u32) & Self::PLL1_DIV4_MASK) << Self::PLL1_DIV4_OFFSET;
         self
     }
     /// ## Handle the BT8D field.
@@ -235,12 +251,44 @@ impl FastUARTConfigurationV2 {
         self.0 |= (bt8d as u32 & Self::BT8D_MASK) << Self::BT8D_OFFSET;
         self
     }
+
+    /// ## Get the effective baudrate.
+    ///
+    /// Computes the baudrate resulting from the current BCLK_SEL and BT8D fields,
+    /// given the chip input clock frequency and the UART PLL output frequency.
+    /// This is usefull to check the host and the chip agree on the baudrate.
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// use bm13xx_asic::register::FastUARTConfigurationV2;
+    /// use fugit::HertzU64;
+    ///
+    /// let clki = HertzU64::MHz(25);
+    /// let pll1 = HertzU64::MHz(100);
+    /// let fast_uart_cfg = FastUARTConfigurationV2(0x0130_1A00); // BM1366 default value
+    /// assert_eq!(fast_uart_cfg.computed_baudrate(clki, pll1), 115_740);
+    /// let fast_uart_cfg = FastUARTConfigurationV2(0x1130_0200); // BM1366 at 1 Mbps
+    /// assert_eq!(fast_uart_cfg.computed_baudrate(clki, pll1), 1_041_666);
+    /// let fast_uart_cfg = FastUARTConfigurationV2(0x1560_0700); // BM1366 at 6.25 Mbps
+    /// assert_eq!(fast_uart_cfg.computed_baudrate(clki, pll1), 6_250_000);
+    /// ```
+    pub fn computed_baudrate(&self, input_clk: HertzU64, pll_freq: HertzU64) -> u32 {
+        let divider = self.bt8d() as u64 + 1;
+        (match self.bclk_sel() {
+            BaudrateClockSelectV2::Clki => input_clk.raw() / (8 * divider),
+            BaudrateClockSelectV2::Pll1 => pll_freq.raw() / (2 * divider),
+        }) as u32
+    }
 }
 
 impl core::fmt::Display for FastUARTConfigurationV2 {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         f.debug_struct("FastUARTConfigurationV2")
+            .field("b28", &self.is_b28())
+            .field("bclk_sel", &self.bclk_sel())
             .field("pll1_div4", &self.pll1_div4())
+            .field("bt8d", &self.bt8d())
             .finish()
     }
 }
@@ -250,8 +298,11 @@ impl defmt::Format for FastUARTConfigurationV2 {
     fn format(&self, fmt: defmt::Formatter) {
         defmt::write!(
             fmt,
-            "FastUARTConfigurationV2 {{ pll1_div4: {} }}",
+            "FastUARTConfigurationV2 {{ b28: {}, bclk_sel: {}, pll1_div4: {}, bt8d: {} }}",
+            self.is_b28(),
+            self.bclk_sel(),
             self.pll1_div4(),
+            self.bt8d(),
         );
     }
 }
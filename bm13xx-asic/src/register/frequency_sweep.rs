@@ -8,21 +8,44 @@ impl_boilerplate_for!(FrequencySweepControl1);
 impl FrequencySweepControl1 {
     pub const ADDR: u8 = 0x90;
 
-    // const SWEEP_STATE_OFFSET: u8 = 24;
+    const SWEEP_STATE_OFFSET: u8 = 24;
 
-    // const SWEEP_STATE_MASK: u32 = 0b111;
+    const SWEEP_STATE_MASK: u32 = 0b111;
+
+    /// ## Get the SWEEP_STATE field.
+    ///
+    /// This returns an `u8` with the SWEEP_STATE value.
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// use bm13xx_asic::register::FrequencySweepControl1;
+    ///
+    /// assert_eq!(FrequencySweepControl1(0x0000_0070).sweep_state(), 0); // BM1397 default value
+    /// assert_eq!(FrequencySweepControl1(0x0500_0070).sweep_state(), 5);
+    /// assert_eq!(FrequencySweepControl1(0x0F00_0070).sweep_state(), 7);
+    /// ```
+    pub const fn sweep_state(&self) -> u8 {
+        ((self.0 >> Self::SWEEP_STATE_OFFSET) & Self::SWEEP_STATE_MASK) as u8
+    }
 }
 
 impl core::fmt::Display for FrequencySweepControl1 {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        f.debug_struct("FrequencySweepControl1").finish()
+        f.debug_struct("FrequencySweepControl1")
+            .field("sweep_state", &self.sweep_state())
+            .finish()
     }
 }
 
 #[cfg(feature = "defmt-03")]
 impl defmt::Format for FrequencySweepControl1 {
     fn format(&self, fmt: defmt::Formatter) {
-        defmt::write!(fmt, "FrequencySweepControl1 {{  }}",);
+        defmt::write!(
+            fmt,
+            "FrequencySweepControl1 {{ sweep_state: {} }}",
+            self.sweep_state()
+        );
     }
 }
 
@@ -34,20 +57,41 @@ impl_boilerplate_for!(GoldenNonceForSweepReturn);
 impl GoldenNonceForSweepReturn {
     pub const ADDR: u8 = 0x94;
 
-    // const GNOSWR_OFFSET: u8 = 0;
+    const GNOSWR_OFFSET: u8 = 0;
 
-    // const GNOSWR_MASK: u32 = 0xffff_ffff;
+    const GNOSWR_MASK: u32 = 0xffff_ffff;
+
+    /// ## Get the GNOSWR field.
+    ///
+    /// This returns an `u32` with the Golden Nonce expected during the sweep.
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// use bm13xx_asic::register::GoldenNonceForSweepReturn;
+    ///
+    /// assert_eq!(GoldenNonceForSweepReturn(0x0037_6400).gnoswr(), 0x0037_6400); // BM1397 default value
+    /// ```
+    pub const fn gnoswr(&self) -> u32 {
+        (self.0 >> Self::GNOSWR_OFFSET) & Self::GNOSWR_MASK
+    }
 }
 
 impl core::fmt::Display for GoldenNonceForSweepReturn {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        f.debug_struct("GoldenNonceForSweepReturn").finish()
+        f.debug_struct("GoldenNonceForSweepReturn")
+            .field("gnoswr", &format_args!("{:#010x}", self.gnoswr()))
+            .finish()
     }
 }
 
 #[cfg(feature = "defmt-03")]
 impl defmt::Format for GoldenNonceForSweepReturn {
     fn format(&self, fmt: defmt::Formatter) {
-        defmt::write!(fmt, "GoldenNonceForSweepReturn {{  }}",);
+        defmt::write!(
+            fmt,
+            "GoldenNonceForSweepReturn {{ gnoswr: {:#010x} }}",
+            self.gnoswr()
+        );
     }
 }
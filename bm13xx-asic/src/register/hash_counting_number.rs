@@ -1,6 +1,19 @@
 use crate::register::Register;
 
 /// # Hash Counting Number register
+///
+/// Programmed when enabling Version Rolling. How the vendor firmwares derive it is not
+/// documented: it is not a function of the chip alone, as boards with the same chip
+/// use different values, presumably depending on their hash frequency and on the
+/// number of chips sharing the nonce space. The known values, captured from stock
+/// firmwares, are:
+///
+/// | Chip   | Board  | HCN      |
+/// |--------|--------|----------|
+/// | BM1366 | S19XP  | `0x151c` |
+/// | BM1368 | ?      | `0x15a4` |
+/// | BM1370 | S21Pro | `0x1eb5` |
+/// | BM1370 | S21XP  | `0x1a44` |
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
 pub struct HashCountingNumber(pub u32);
 impl_boilerplate_for!(HashCountingNumber);
@@ -8,20 +21,45 @@ impl_boilerplate_for!(HashCountingNumber);
 impl HashCountingNumber {
     pub const ADDR: u8 = 0x10;
 
-    // const HCN_OFFSET: u8 = 0;
+    const HCN_OFFSET: u8 = 0;
 
-    // const HCN_MASK: u32 = 0xffff_ffff;
+    const HCN_MASK: u32 = 0xffff_ffff;
+
+    /// ## Handle the HCN field.
+    ///
+    /// Get and set the Hash Counting Number value.
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// use bm13xx_asic::register::{HashCountingNumber, Register};
+    ///
+    /// let mut hcn = HashCountingNumber(0x0000_0000); // BM1366 default value
+    /// assert_eq!(hcn.hcn(), 0);
+    /// assert_eq!(hcn.set_hcn(0x0000_1eb5).hcn(), 0x1eb5); // BM1370 S21Pro value
+    /// assert_eq!(hcn.set_hcn(0xffff_ffff).val(), 0xffff_ffff); // max value
+    /// ```
+    pub const fn hcn(&self) -> u32 {
+        (self.0 >> Self::HCN_OFFSET) & Self::HCN_MASK
+    }
+    pub fn set_hcn(&mut self, hcn: u32) -> &mut Self {
+        self.0 &= !(Self::HCN_MASK << Self::HCN_OFFSET);
+        self.0 |= (hcn & Self::HCN_MASK) << Self::HCN_OFFSET;
+        self
+    }
 }
 
 impl core::fmt::Display for HashCountingNumber {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        f.debug_struct("HashCountingNumber").finish()
+        f.debug_struct("HashCountingNumber")
+            .field("hcn", &format_args!("{:#010x}", self.hcn()))
+            .finish()
     }
 }
 
 #[cfg(feature = "defmt-03")]
 impl defmt::Format for HashCountingNumber {
     fn format(&self, fmt: defmt::Formatter) {
-        defmt::write!(fmt, "HashCountingNumber {{  }}",);
+        defmt::write!(fmt, "HashCountingNumber {{ hcn: {:#010x} }}", self.hcn());
     }
 }
@@ -5,7 +5,7 @@ use crate::register::Register;
 /// This is used by [`IoDriverStrenghtConfiguration::strenght`], [`IoDriverStrenghtConfiguration::set_strenght`] method
 ///
 /// [`IoDriverStrenghtConfiguration::strenght`]: crate::register::IoDriverStrenghtConfiguration::strenght
-// [`IoDriverStrenghtConfiguration::set_strenght`]: crate::register::IoDriverStrenghtConfiguration::set_strenght
+/// [`IoDriverStrenghtConfiguration::set_strenght`]: crate::register::IoDriverStrenghtConfiguration::set_strenght
 #[derive(Copy, Clone, Eq, PartialEq, Debug, PartialOrd, Ord, Hash)]
 #[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
 pub enum DriverSelect {
@@ -17,12 +17,14 @@ pub enum DriverSelect {
     CO,
 }
 
-/// Driver Select.
+/// Driver R Select.
 ///
-/// This is used by [`IoDriverStrenghtConfiguration::strenght`], [`IoDriverStrenghtConfiguration::set_strenght`] method
+/// This is used by [`IoDriverStrenghtConfiguration::enabled`], [`IoDriverStrenghtConfiguration::enable`]
+/// and [`IoDriverStrenghtConfiguration::disable`] methods
 ///
-/// [`IoDriverStrenghtConfiguration::strenght`]: crate::register::IoDriverStrenghtConfiguration::strenght
-// [`IoDriverStrenghtConfiguration::set_strenght`]: crate::register::IoDriverStrenghtConfiguration::set_strenght
+/// [`IoDriverStrenghtConfiguration::enabled`]: crate::register::IoDriverStrenghtConfiguration::enabled
+/// [`IoDriverStrenghtConfiguration::enable`]: crate::register::IoDriverStrenghtConfiguration::enable
+/// [`IoDriverStrenghtConfiguration::disable`]: crate::register::IoDriverStrenghtConfiguration::disable
 #[derive(Copy, Clone, Eq, PartialEq, Debug, PartialOrd, Ord, Hash)]
 #[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
 pub enum DriverRSelect {
@@ -81,6 +83,24 @@ impl IoDriverStrenghtConfiguration {
     /// assert_eq!(io_conf.set_strenght(DriverSelect::RF, 2).strenght(DriverSelect::RF), 2); // BM1366 init() value
     /// assert_eq!(io_conf.set_strenght(DriverSelect::RF, 0xf).strenght(DriverSelect::RF), 0xf); // max value
     /// assert_eq!(io_conf.set_strenght(DriverSelect::RF, 0x10).strenght(DriverSelect::RF), 0); // out of bound value
+    ///
+    /// // each driver select only handles its own field
+    /// let drvs = [
+    ///     DriverSelect::RF,
+    ///     DriverSelect::RO,
+    ///     DriverSelect::CLKO,
+    ///     DriverSelect::NRSTO,
+    ///     DriverSelect::BO,
+    ///     DriverSelect::CO,
+    /// ];
+    /// let mut io_conf = IoDriverStrenghtConfiguration(0);
+    /// for (i, drv) in drvs.iter().enumerate() {
+    ///     io_conf.set_strenght(*drv, i as u8 + 1);
+    /// }
+    /// for (i, drv) in drvs.iter().enumerate() {
+    ///     assert_eq!(io_conf.strenght(*drv), i as u8 + 1);
+    /// }
+    /// assert_eq!(io_conf.0, 0x0102_3456);
     /// ```
     pub const fn strenght(&self, drv: DriverSelect) -> u8 {
         ((self.0
@@ -139,6 +159,15 @@ impl IoDriverStrenghtConfiguration {
     /// assert!(!io_conf.enabled(DriverRSelect::D3R));
     /// assert!(io_conf.enable(DriverRSelect::D0R).enabled(DriverRSelect::D0R));
     /// assert!(!io_conf.disable(DriverRSelect::D0R).enabled(DriverRSelect::D0R));
+    ///
+    /// // each driver R select only handles its own bit
+    /// let drvs = [DriverRSelect::D0R, DriverRSelect::D1R, DriverRSelect::D2R, DriverRSelect::D3R];
+    /// for drv in drvs {
+    ///     let mut io_conf = IoDriverStrenghtConfiguration(0x0001_2111);
+    ///     assert!(io_conf.enable(drv).enabled(drv));
+    ///     assert_eq!(drvs.iter().filter(|d| io_conf.enabled(**d)).count(), 1);
+    ///     assert_eq!(io_conf.disable(drv).0, 0x0001_2111);
+    /// }
     /// ```
     pub const fn enabled(&self, drv: DriverRSelect) -> bool {
         let offset = match drv {
@@ -193,6 +222,10 @@ impl core::fmt::Display for IoDriverStrenghtConfiguration {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         f.debug_struct("IoDriverStrenghtConfiguration")
             .field("RF_DS", &self.strenght(DriverSelect::RF))
+            .field("D3RS_EN", &self.enabled(DriverRSelect::D3R))
+            .field("D2RS_EN", &self.enabled(DriverRSelect::D2R))
+            .field("D1RS_EN", &self.enabled(DriverRSelect::D1R))
+            .field("D0RS_EN", &self.enabled(DriverRSelect::D0R))
             .field("RO_DS", &self.strenght(DriverSelect::RO))
             .field("CLKO_DS", &self.strenght(DriverSelect::CLKO))
             .field("NRSTO_DS", &self.strenght(DriverSelect::NRSTO))
@@ -207,8 +240,12 @@ impl defmt::Format for IoDriverStrenghtConfiguration {
     fn format(&self, fmt: defmt::Formatter) {
         defmt::write!(
             fmt,
-            "IoDriverStrenghtConfiguration {{ RF_DS: {}, RO_DS: {}, CLKO_DS: {}, NRSTO_DS: {}, BO_DS: {}, CO_DS: {} }}",
+            "IoDriverStrenghtConfiguration {{ RF_DS: {}, D3RS_EN: {}, D2RS_EN: {}, D1RS_EN: {}, D0RS_EN: {}, RO_DS: {}, CLKO_DS: {}, NRSTO_DS: {}, BO_DS: {}, CO_DS: {} }}",
             self.strenght(DriverSelect::RF),
+            self.enabled(DriverRSelect::D3R),
+            self.enabled(DriverRSelect::D2R),
+            self.enabled(DriverRSelect::D1R),
+            self.enabled(DriverRSelect::D0R),
             self.strenght(DriverSelect::RO),
             self.strenght(DriverSelect::CLKO),
             self.strenght(DriverSelect::NRSTO),
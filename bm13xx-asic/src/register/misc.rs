@@ -1,4 +1,5 @@
 use crate::register::Register;
+use fugit::HertzU64;
 
 /// Baudrate CLocK SELect.
 ///
@@ -99,21 +100,34 @@ impl MiscControl {
         self
     }
 
-    /// ## Reset the Core.
+    /// ## Handle the Core Soft Reset field.
     ///
-    /// This returns an `bool` with the Core Reset state.
+    /// Get the Core Soft Reset state, set it to hold the cores in reset and release it
+    /// to let them run again: a soft reset of the cores is a pulse of this bit.
     ///
     /// ### Example
     ///
     /// ```
-    /// use bm13xx_asic::register::MiscControl;
+    /// use bm13xx_asic::register::{MiscControl, Register};
     ///
-    /// let misc = MiscControl(0x0000_3A01);
+    /// let mut misc = MiscControl(0x0000_3A01); // BM1397 default value
     /// assert!(!misc.core_srst());
+    /// assert!(misc.core_soft_reset().core_srst());
+    /// assert_eq!(misc.val(), 0x0040_3A01);
+    /// assert!(!misc.release_core_soft_reset().core_srst());
+    /// assert_eq!(misc.val(), 0x0000_3A01);
     /// ```
     pub const fn core_srst(&self) -> bool {
         (self.0 >> Self::CORE_SRST_OFFSET) & Self::CORE_SRST_MASK == Self::CORE_SRST_MASK
     }
+    pub fn core_soft_reset(&mut self) -> &mut Self {
+        self.0 |= Self::CORE_SRST_MASK << Self::CORE_SRST_OFFSET;
+        self
+    }
+    pub fn release_core_soft_reset(&mut self) -> &mut Self {
+        self.0 &= !(Self::CORE_SRST_MASK << Self::CORE_SRST_OFFSET);
+        self
+    }
 
     /// ## Handle the Baudrate Clock Select field.
     ///
@@ -139,6 +153,35 @@ impl MiscControl {
         self.0 |= (bclk_sel as u32) << Self::BCK_SEL_OFFSET;
         self
     }
+
+    /// ## Get the effective baudrate.
+    ///
+    /// Computes the baudrate resulting from the current BCLK_SEL and BT8D fields,
+    /// given the chip input clock frequency and the PLL3 output frequency.
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// use bm13xx_asic::register::{BaudrateClockSelect, MiscControl, Register};
+    /// use fugit::HertzU64;
+    ///
+    /// let clki = HertzU64::MHz(25);
+    /// let pll3 = HertzU64::MHz(400);
+    /// let mut misc = MiscControl(0x0000_3A01); // BM1397 default value
+    /// assert_eq!(misc.computed_baudrate(clki, pll3), 115_740);
+    /// misc.set_bclk_sel(BaudrateClockSelect::Pll3).set_bt8d(7);
+    /// assert_eq!(misc.val(), 0x0001_2701);
+    /// assert_eq!(misc.computed_baudrate(clki, pll3), 25_000_000);
+    /// misc.set_bclk_sel(BaudrateClockSelect::Clki).set_bt8d(26);
+    /// assert_eq!(misc.val(), 0x0000_3A01); // back to default value
+    /// ```
+    pub fn computed_baudrate(&self, input_clk: HertzU64, pll3_freq: HertzU64) -> u32 {
+        let divider = self.bt8d() as u64 + 1;
+        (match self.bclk_sel() {
+            BaudrateClockSelect::Clki => input_clk.raw() / (8 * divider),
+            BaudrateClockSelect::Pll3 => pll3_freq.raw() / (2 * divider),
+        }) as u32
+    }
 }
 
 impl core::fmt::Display for MiscControl {
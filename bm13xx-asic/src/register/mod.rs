@@ -87,3 +87,90 @@ pub use unknown::{
     RegD4, RegD8, RegDC, RegE0, RegE4, RegE8, RegEC, RegF0, RegF4, RegF8, RegFC,
 };
 pub use version_rolling::VersionRolling;
+
+/// ## Get the name of the register at a given address
+///
+/// Registers sharing an address between chip generations (e.g. `MiscControl` and
+/// `MiscControlV2`) are given their first generation name.
+///
+/// ### Example
+/// ```
+/// use bm13xx_asic::register::{register_name, MiscControlV2};
+///
+/// assert_eq!(register_name(0x00), Some("ChipIdentification"));
+/// assert_eq!(register_name(0x14), Some("TicketMask"));
+/// assert_eq!(register_name(MiscControlV2::ADDR), Some("MiscControl"));
+/// assert_eq!(register_name(0x70), Some("PLL0Divider"));
+/// assert_eq!(register_name(0xA4), Some("VersionRolling"));
+/// assert_eq!(register_name(0xFC), Some("RegFC"));
+/// assert_eq!(register_name(0x88), None); // no register known at this address
+/// assert_eq!(register_name(0x01), None); // not aligned
+/// ```
+pub const fn register_name(addr: u8) -> Option<&'static str> {
+    Some(match addr {
+        ChipIdentification::ADDR => "ChipIdentification",
+        HashRate::ADDR => "HashRate",
+        PLL0Parameter::ADDR => "PLL0Parameter",
+        ChipNonceOffset::ADDR => "ChipNonceOffset",
+        HashCountingNumber::ADDR => "HashCountingNumber",
+        TicketMask::ADDR => "TicketMask",
+        MiscControl::ADDR => "MiscControl",
+        I2CControl::ADDR => "I2CControl",
+        OrderedClockEnable::ADDR => "OrderedClockEnable",
+        Reg24::ADDR => "Reg24",
+        FastUARTConfiguration::ADDR => "FastUARTConfiguration",
+        UARTRelay::ADDR => "UARTRelay",
+        Reg30::ADDR => "Reg30",
+        Reg34::ADDR => "Reg34",
+        TicketMask2::ADDR => "TicketMask2",
+        CoreRegisterControl::ADDR => "CoreRegisterControl",
+        CoreRegisterValue::ADDR => "CoreRegisterValue",
+        ExternalTemperatureSensorRead::ADDR => "ExternalTemperatureSensorRead",
+        ErrorFlag::ADDR => "ErrorFlag",
+        NonceErrorCounter::ADDR => "NonceErrorCounter",
+        NonceOverflowCounter::ADDR => "NonceOverflowCounter",
+        AnalogMuxControl::ADDR => "AnalogMuxControl",
+        IoDriverStrenghtConfiguration::ADDR => "IoDriverStrenghtConfiguration",
+        TimeOut::ADDR => "TimeOut",
+        PLL1Parameter::ADDR => "PLL1Parameter",
+        PLL2Parameter::ADDR => "PLL2Parameter",
+        PLL3Parameter::ADDR => "PLL3Parameter",
+        OrderedClockMonitor::ADDR => "OrderedClockMonitor",
+        PLL0Divider::ADDR => "PLL0Divider",
+        PLL1Divider::ADDR => "PLL1Divider",
+        PLL2Divider::ADDR => "PLL2Divider",
+        PLL3Divider::ADDR => "PLL3Divider",
+        ClockOrderControl0::ADDR => "ClockOrderControl0",
+        ClockOrderControl1::ADDR => "ClockOrderControl1",
+        ClockOrderStatus::ADDR => "ClockOrderStatus",
+        FrequencySweepControl1::ADDR => "FrequencySweepControl1",
+        GoldenNonceForSweepReturn::ADDR => "GoldenNonceForSweepReturn",
+        ReturnedGroupPatternStatus::ADDR => "ReturnedGroupPatternStatus",
+        NonceReturnedTimeout::ADDR => "NonceReturnedTimeout",
+        ReturnedSinglePatternStatus::ADDR => "ReturnedSinglePatternStatus",
+        VersionRolling::ADDR => "VersionRolling",
+        RegA8::ADDR => "RegA8",
+        RegAC::ADDR => "RegAC",
+        RegB0::ADDR => "RegB0",
+        RegB4::ADDR => "RegB4",
+        RegB8::ADDR => "RegB8",
+        RegBC::ADDR => "RegBC",
+        RegC0::ADDR => "RegC0",
+        RegC4::ADDR => "RegC4",
+        RegC8::ADDR => "RegC8",
+        RegCC::ADDR => "RegCC",
+        RegD0::ADDR => "RegD0",
+        RegD4::ADDR => "RegD4",
+        RegD8::ADDR => "RegD8",
+        RegDC::ADDR => "RegDC",
+        RegE0::ADDR => "RegE0",
+        RegE4::ADDR => "RegE4",
+        RegE8::ADDR => "RegE8",
+        RegEC::ADDR => "RegEC",
+        RegF0::ADDR => "RegF0",
+        RegF4::ADDR => "RegF4",
+        RegF8::ADDR => "RegF8",
+        RegFC::ADDR => "RegFC",
+        _ => return None,
+    })
+}
@@ -8,20 +8,78 @@ impl_boilerplate_for!(NonceReturnedTimeout);
 impl NonceReturnedTimeout {
     pub const ADDR: u8 = 0x9C;
 
-    // const SWEEP_TIMEOUT_OFFSET: u8 = 0;
+    const B31_16_OFFSET: u8 = 16;
+    const TIMEOUT_OFFSET: u8 = 0;
 
-    // const SWEEP_TIMEOUT_MASK: u32 = 0xffff;
+    const B31_16_MASK: u32 = 0xffff;
+    const TIMEOUT_MASK: u32 = 0xffff;
+
+    /// ## Handle the B\[31:16\] field.
+    ///
+    /// Get and set the B\[31:16\] value. Its meaning is unknown.
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// use bm13xx_asic::register::NonceReturnedTimeout;
+    ///
+    /// let mut nrt = NonceReturnedTimeout(0x00fd_0077); // BM1366 default value
+    /// assert_eq!(nrt.b31_16(), 0x00fd);
+    /// assert_eq!(nrt.set_b31_16(0xffff).b31_16(), 0xffff); // max value
+    /// assert_eq!(nrt.timeout(), 0x0077);
+    /// ```
+    pub const fn b31_16(&self) -> u16 {
+        ((self.0 >> Self::B31_16_OFFSET) & Self::B31_16_MASK) as u16
+    }
+    pub fn set_b31_16(&mut self, b31_16: u16) -> &mut Self {
+        self.0 &= !(Self::B31_16_MASK << Self::B31_16_OFFSET);
+        self.0 |= ((b31_16 as u32) & Self::B31_16_MASK) << Self::B31_16_OFFSET;
+        self
+    }
+
+    /// ## Handle the Timeout field.
+    ///
+    /// Get and set the Timeout value after which the chip flushes the nonces it found.
+    /// It is counted in ticks of the hash clock, whose length in cycles is not
+    /// documented.
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// use bm13xx_asic::register::NonceReturnedTimeout;
+    ///
+    /// let mut nrt = NonceReturnedTimeout(0x00fd_0077); // BM1366 default value
+    /// assert_eq!(nrt.timeout(), 0x0077);
+    /// assert_eq!(nrt.set_timeout(0xffff).timeout(), 0xffff); // max value
+    /// assert_eq!(nrt.b31_16(), 0x00fd);
+    /// ```
+    pub const fn timeout(&self) -> u16 {
+        ((self.0 >> Self::TIMEOUT_OFFSET) & Self::TIMEOUT_MASK) as u16
+    }
+    pub fn set_timeout(&mut self, timeout: u16) -> &mut Self {
+        self.0 &= !(Self::TIMEOUT_MASK << Self::TIMEOUT_OFFSET);
+        self.0 |= ((timeout as u32) & Self::TIMEOUT_MASK) << Self::TIMEOUT_OFFSET;
+        self
+    }
 }
 
 impl core::fmt::Display for NonceReturnedTimeout {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        f.debug_struct("NonceReturnedTimeout").finish()
+        f.debug_struct("NonceReturnedTimeout")
+            .field("b31_16", &self.b31_16())
+            .field("timeout", &self.timeout())
+            .finish()
     }
 }
 
 #[cfg(feature = "defmt-03")]
 impl defmt::Format for NonceReturnedTimeout {
     fn format(&self, fmt: defmt::Formatter) {
-        defmt::write!(fmt, "NonceReturnedTimeout {{  }}",);
+        defmt::write!(
+            fmt,
+            "NonceReturnedTimeout {{ b31_16: {}, timeout: {} }}",
+            self.b31_16(),
+            self.timeout(),
+        );
     }
 }
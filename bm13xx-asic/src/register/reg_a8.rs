@@ -9,23 +9,24 @@ impl RegA8 {
     pub const ADDR: u8 = 0xA8;
 
     const B10_OFFSET: u8 = 10;
-    const B8_OFFSET: u8 = 8;
-    const B7_4_OFFSET: u8 = 4;
-    const B3_0_OFFSET: u8 = 0;
+    const CORE_RESET_OFFSET: u8 = 8;
+    const CHIP_CORE_SEL_OFFSET: u8 = 4;
+    const ALL_CORE_SEL_OFFSET: u8 = 0;
 
     const B10_MASK: u32 = 0x1;
-    const B8_MASK: u32 = 0x1;
-    const B7_4_MASK: u32 = 0xf;
-    const B3_0_MASK: u32 = 0xf;
+    const CORE_RESET_MASK: u32 = 0x1;
+    const CHIP_CORE_SEL_MASK: u32 = 0xf;
+    const ALL_CORE_SEL_MASK: u32 = 0xf;
 
     /// ## Handle the B10 field.
     ///
-    /// Get and set the B10 state.
+    /// Get and set the B10 state. Its meaning is unknown, it is only cleared by the
+    /// BM1370 reset sequence.
     ///
     /// ### Example
     ///
     /// ```
-    /// use bm13xx_asic::register::RegA8;
+    /// use bm13xx_asic::register::{RegA8, Register};
     ///
     /// let mut reg_a8 = RegA8(0x0007_0000); // BM1366 default value
     /// assert!(!reg_a8.is_b10());
@@ -44,88 +45,137 @@ impl RegA8 {
         self
     }
 
-    /// ## Handle the B8 field.
+    /// ## Handle the Core Reset Enable field (B8).
     ///
-    /// Get and set the B8 state.
+    /// Get and set the Core Reset Enable state. The vendor firmware sets it
+    /// ("Soft Open Core") before selecting the cores to reset, and keeps it set
+    /// while a single chip cores are being reset.
     ///
     /// ### Example
     ///
     /// ```
-    /// use bm13xx_asic::register::RegA8;
+    /// use bm13xx_asic::register::{RegA8, Register};
     ///
     /// let mut reg_a8 = RegA8(0x0007_0000); // BM1366 default value
-    /// assert!(!reg_a8.is_b8());
-    /// assert!(reg_a8.set_b8().is_b8());
-    /// assert!(!reg_a8.clr_b8().is_b8());
+    /// assert!(!reg_a8.core_reset_enabled());
+    /// assert!(reg_a8.enable_core_reset().core_reset_enabled());
+    /// assert_eq!(reg_a8.val(), 0x0007_0100);
+    /// assert!(!reg_a8.disable_core_reset().core_reset_enabled());
+    /// assert_eq!(reg_a8.val(), 0x0007_0000);
     /// ```
-    pub const fn is_b8(&self) -> bool {
-        (self.0 >> Self::B8_OFFSET) & Self::B8_MASK == Self::B8_MASK
+    pub const fn core_reset_enabled(&self) -> bool {
+        (self.0 >> Self::CORE_RESET_OFFSET) & Self::CORE_RESET_MASK == Self::CORE_RESET_MASK
     }
-    pub fn set_b8(&mut self) -> &mut Self {
-        self.0 |= Self::B8_MASK << Self::B8_OFFSET;
+    pub fn enable_core_reset(&mut self) -> &mut Self {
+        self.0 |= Self::CORE_RESET_MASK << Self::CORE_RESET_OFFSET;
         self
     }
-    pub fn clr_b8(&mut self) -> &mut Self {
-        self.0 &= !(Self::B8_MASK << Self::B8_OFFSET);
+    pub fn disable_core_reset(&mut self) -> &mut Self {
+        self.0 &= !(Self::CORE_RESET_MASK << Self::CORE_RESET_OFFSET);
         self
     }
 
-    /// ## Handle the B\[7:4\] field.
+    /// ## Handle the Chip Core Select field (B\[7:4\]).
     ///
-    /// Get and set the B\[7:4\] value.
+    /// Get and set the Chip Core Select value. The vendor firmware writes `0xf` to a
+    /// single chip when resetting its cores alone.
     ///
     /// ### Example
     ///
     /// ```
-    /// use bm13xx_asic::register::RegA8;
+    /// use bm13xx_asic::register::{RegA8, Register};
     ///
     /// let mut reg_a8 = RegA8(0x0007_0000); // BM1366 default value
-    /// assert_eq!(reg_a8.b7_4(), 0);
-    /// assert_eq!(reg_a8.set_b7_4(0xf).b7_4(), 0xf); // max value
-    /// assert_eq!(reg_a8.set_b7_4(0x10).b7_4(), 0); // out of bound value
+    /// assert_eq!(reg_a8.chip_core_sel(), 0);
+    /// assert_eq!(reg_a8.set_chip_core_sel(0xf).chip_core_sel(), 0xf); // max value
+    /// assert_eq!(reg_a8.val(), 0x0007_00f0);
+    /// assert_eq!(reg_a8.set_chip_core_sel(0x10).chip_core_sel(), 0); // out of bound value
     /// ```
-    pub const fn b7_4(&self) -> u8 {
-        ((self.0 >> Self::B7_4_OFFSET) & Self::B7_4_MASK) as u8
+    pub const fn chip_core_sel(&self) -> u8 {
+        ((self.0 >> Self::CHIP_CORE_SEL_OFFSET) & Self::CHIP_CORE_SEL_MASK) as u8
     }
-    pub fn set_b7_4(&mut self, b7_4: u8) -> &mut Self {
-        self.0 &= !(Self::B7_4_MASK << Self::B7_4_OFFSET);
-        self.0 |= ((b7_4 as u32) & Self::B7_4_MASK) << Self::B7_4_OFFSET;
+    pub fn set_chip_core_sel(&mut self, sel: u8) -> &mut Self {
+        self.0 &= !(Self::CHIP_CORE_SEL_MASK << Self::CHIP_CORE_SEL_OFFSET);
+        self.0 |= ((sel as u32) & Self::CHIP_CORE_SEL_MASK) << Self::CHIP_CORE_SEL_OFFSET;
         self
     }
 
-    /// ## Handle the B\[3:0\] field.
+    /// ## Handle the All Core Select field (B\[3:0\]).
     ///
-    /// Get and set the B\[3:0\] value.
+    /// Get and set the All Core Select value. The vendor firmware broadcasts `0xf`
+    /// to start resetting the cores of the whole chain, then `0` to release them.
     ///
     /// ### Example
     ///
     /// ```
-    /// use bm13xx_asic::register::RegA8;
+    /// use bm13xx_asic::register::{RegA8, Register};
     ///
     /// let mut reg_a8 = RegA8(0x0007_0000); // BM1366 default value
-    /// assert_eq!(reg_a8.b3_0(), 0);
-    /// assert_eq!(reg_a8.set_b3_0(0xf).b3_0(), 0xf); // max value
-    /// assert_eq!(reg_a8.set_b3_0(0x10).b3_0(), 0); // out of bound value
+    /// assert_eq!(reg_a8.all_core_sel(), 0);
+    /// assert_eq!(reg_a8.set_all_core_sel(0xf).all_core_sel(), 0xf); // max value
+    /// assert_eq!(reg_a8.val(), 0x0007_000f);
+    /// assert_eq!(reg_a8.set_all_core_sel(0x10).all_core_sel(), 0); // out of bound value
     /// ```
+    pub const fn all_core_sel(&self) -> u8 {
+        ((self.0 >> Self::ALL_CORE_SEL_OFFSET) & Self::ALL_CORE_SEL_MASK) as u8
+    }
+    pub fn set_all_core_sel(&mut self, sel: u8) -> &mut Self {
+        self.0 &= !(Self::ALL_CORE_SEL_MASK << Self::ALL_CORE_SEL_OFFSET);
+        self.0 |= ((sel as u32) & Self::ALL_CORE_SEL_MASK) << Self::ALL_CORE_SEL_OFFSET;
+        self
+    }
+
+    #[deprecated(note = "use `core_reset_enabled` instead")]
+    pub const fn is_b8(&self) -> bool {
+        self.core_reset_enabled()
+    }
+    #[deprecated(note = "use `enable_core_reset` instead")]
+    pub fn set_b8(&mut self) -> &mut Self {
+        self.enable_core_reset()
+    }
+    #[deprecated(note = "use `disable_core_reset` instead")]
+    pub fn clr_b8(&mut self) -> &mut Self {
+        self.disable_core_reset()
+    }
+    #[deprecated(note = "use `chip_core_sel` instead")]
+    pub const fn b7_4(&self) -> u8 {
+        self.chip_core_sel()
+    }
+    #[deprecated(note = "use `set_chip_core_sel` instead")]
+    pub fn set_b7_4(&mut self, b7_4: u8) -> &mut Self {
+        self.set_chip_core_sel(b7_4)
+    }
+    #[deprecated(note = "use `all_core_sel` instead")]
     pub const fn b3_0(&self) -> u8 {
-        ((self.0 >> Self::B3_0_OFFSET) & Self::B3_0_MASK) as u8
+        self.all_core_sel()
     }
+    #[deprecated(note = "use `set_all_core_sel` instead")]
     pub fn set_b3_0(&mut self, b3_0: u8) -> &mut Self {
-        self.0 &= !(Self::B3_0_MASK << Self::B3_0_OFFSET);
-        self.0 |= ((b3_0 as u32) & Self::B3_0_MASK) << Self::B3_0_OFFSET;
-        self
+        self.set_all_core_sel(b3_0)
     }
 }
 
 impl core::fmt::Display for RegA8 {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        f.debug_struct("RegA8").finish()
+        f.debug_struct("RegA8")
+            .field("b10", &self.is_b10())
+            .field("core_reset_enabled", &self.core_reset_enabled())
+            .field("chip_core_sel", &self.chip_core_sel())
+            .field("all_core_sel", &self.all_core_sel())
+            .finish()
     }
 }
 
 #[cfg(feature = "defmt-03")]
 impl defmt::Format for RegA8 {
     fn format(&self, fmt: defmt::Formatter) {
-        defmt::write!(fmt, "RegA8 {{  }}",);
+        defmt::write!(
+            fmt,
+            "RegA8 {{ b10: {}, core_reset_enabled: {}, chip_core_sel: {}, all_core_sel: {} }}",
+            self.is_b10(),
+            self.core_reset_enabled(),
+            self.chip_core_sel(),
+            self.all_core_sel(),
+        );
     }
 }
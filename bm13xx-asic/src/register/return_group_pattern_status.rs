@@ -8,26 +8,96 @@ impl_boilerplate_for!(ReturnedGroupPatternStatus);
 impl ReturnedGroupPatternStatus {
     pub const ADDR: u8 = 0x98;
 
-    // const RGPS3_OFFSET: u8 = 24;
-    // const RGPS2_OFFSET: u8 = 16;
-    // const RGPS1_OFFSET: u8 = 8;
-    // const RGPS0_OFFSET: u8 = 0;
-
-    // const RGPS3_MASK: u32 = 0b1111;
-    // const RGPS2_MASK: u32 = 0b1111;
-    // const RGPS1_MASK: u32 = 0b1111;
-    // const RGPS0_MASK: u32 = 0b1111;
+    const RGPS3_OFFSET: u8 = 24;
+    const RGPS2_OFFSET: u8 = 16;
+    const RGPS1_OFFSET: u8 = 8;
+    const RGPS0_OFFSET: u8 = 0;
+
+    const RGPS_MASK: u32 = 0b1111;
+
+    /// ## Get the RGPSx field.
+    ///
+    /// This returns an `u8` with the Returned Group Pattern Status of the group `x`,
+    /// or `None` if there is no such group.
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// use bm13xx_asic::register::ReturnedGroupPatternStatus;
+    ///
+    /// let rgps = ReturnedGroupPatternStatus(0x3030_3030); // BM1397 default value
+    /// assert_eq!(rgps.rgps(0), Some(0));
+    /// assert_eq!(rgps.rgps(3), Some(0));
+    /// let rgps = ReturnedGroupPatternStatus(0x0F0A_0501);
+    /// assert_eq!(rgps.rgps(0), Some(0x1));
+    /// assert_eq!(rgps.rgps(1), Some(0x5));
+    /// assert_eq!(rgps.rgps(2), Some(0xA));
+    /// assert_eq!(rgps.rgps(3), Some(0xF));
+    /// assert_eq!(rgps.rgps(4), None);
+    /// ```
+    pub const fn rgps(&self, group: u8) -> Option<u8> {
+        let offset = match group {
+            0 => Self::RGPS0_OFFSET,
+            1 => Self::RGPS1_OFFSET,
+            2 => Self::RGPS2_OFFSET,
+            3 => Self::RGPS3_OFFSET,
+            _ => return None,
+        };
+        Some(((self.0 >> offset) & Self::RGPS_MASK) as u8)
+    }
+
+    /// ## Get the pattern status of a core in the RGPSx field.
+    ///
+    /// This returns `true` if the bit of `core` is set in the Returned Group Pattern Status
+    /// of the group `x`, or `None` if there is no such group or core.
+    /// A set bit is assumed to be a passing core, this is not documented by the vendor.
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// use bm13xx_asic::register::ReturnedGroupPatternStatus;
+    ///
+    /// let rgps = ReturnedGroupPatternStatus(0x0F0A_0501);
+    /// assert_eq!(rgps.core_passed(0, 0), Some(true));
+    /// assert_eq!(rgps.core_passed(0, 1), Some(false));
+    /// assert_eq!(rgps.core_passed(1, 2), Some(true));
+    /// assert_eq!(rgps.core_passed(2, 0), Some(false));
+    /// assert_eq!(rgps.core_passed(3, 3), Some(true));
+    /// assert_eq!(rgps.core_passed(3, 4), None);
+    /// assert_eq!(rgps.core_passed(4, 0), None);
+    /// ```
+    pub const fn core_passed(&self, group: u8, core: u8) -> Option<bool> {
+        if core >= Self::RGPS_MASK.count_ones() as u8 {
+            return None;
+        }
+        match self.rgps(group) {
+            Some(rgps) => Some(rgps & (1 << core) != 0),
+            None => None,
+        }
+    }
 }
 
 impl core::fmt::Display for ReturnedGroupPatternStatus {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        f.debug_struct("ReturnedGroupPatternStatus").finish()
+        f.debug_struct("ReturnedGroupPatternStatus")
+            .field("RGPS0", &self.rgps(0).unwrap())
+            .field("RGPS1", &self.rgps(1).unwrap())
+            .field("RGPS2", &self.rgps(2).unwrap())
+            .field("RGPS3", &self.rgps(3).unwrap())
+            .finish()
     }
 }
 
 #[cfg(feature = "defmt-03")]
 impl defmt::Format for ReturnedGroupPatternStatus {
     fn format(&self, fmt: defmt::Formatter) {
-        defmt::write!(fmt, "ReturnedGroupPatternStatus {{  }}",);
+        defmt::write!(
+            fmt,
+            "ReturnedGroupPatternStatus {{ RGPS0: {}, RGPS1: {}, RGPS2: {}, RGPS3: {} }}",
+            self.rgps(0).unwrap(),
+            self.rgps(1).unwrap(),
+            self.rgps(2).unwrap(),
+            self.rgps(3).unwrap(),
+        );
     }
 }
@@ -8,20 +8,66 @@ impl_boilerplate_for!(ReturnedSinglePatternStatus);
 impl ReturnedSinglePatternStatus {
     pub const ADDR: u8 = 0xA0;
 
-    // const RSPS_OFFSET: u8 = 0;
+    const RSPS_OFFSET: u8 = 0;
 
-    // const RSPS_MASK: u32 = 0xffff_ffff;
+    const RSPS_MASK: u32 = 0xffff_ffff;
+
+    /// ## Get the RSPS field.
+    ///
+    /// This returns an `u32` with the Returned Single Pattern Status.
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// use bm13xx_asic::register::ReturnedSinglePatternStatus;
+    ///
+    /// assert_eq!(ReturnedSinglePatternStatus(0x0000_0000).rsps(), 0); // BM1397 default value
+    /// assert_eq!(ReturnedSinglePatternStatus(0x8000_0001).rsps(), 0x8000_0001);
+    /// ```
+    pub const fn rsps(&self) -> u32 {
+        (self.0 >> Self::RSPS_OFFSET) & Self::RSPS_MASK
+    }
+
+    /// ## Get the pattern status of a core in the RSPS field.
+    ///
+    /// This returns `true` if the bit of `core` is set in the Returned Single Pattern Status,
+    /// or `None` if there is no such core.
+    /// A set bit is assumed to be a passing core, this is not documented by the vendor.
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// use bm13xx_asic::register::ReturnedSinglePatternStatus;
+    ///
+    /// let rsps = ReturnedSinglePatternStatus(0x8000_0001);
+    /// assert_eq!(rsps.core_passed(0), Some(true));
+    /// assert_eq!(rsps.core_passed(1), Some(false));
+    /// assert_eq!(rsps.core_passed(31), Some(true));
+    /// assert_eq!(rsps.core_passed(32), None);
+    /// ```
+    pub const fn core_passed(&self, core: u8) -> Option<bool> {
+        if core >= Self::RSPS_MASK.count_ones() as u8 {
+            return None;
+        }
+        Some(self.rsps() & (1 << core) != 0)
+    }
 }
 
 impl core::fmt::Display for ReturnedSinglePatternStatus {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        f.debug_struct("ReturnedSinglePatternStatus").finish()
+        f.debug_struct("ReturnedSinglePatternStatus")
+            .field("rsps", &format_args!("{:#010x}", self.rsps()))
+            .finish()
     }
 }
 
 #[cfg(feature = "defmt-03")]
 impl defmt::Format for ReturnedSinglePatternStatus {
     fn format(&self, fmt: defmt::Formatter) {
-        defmt::write!(fmt, "ReturnedSinglePatternStatus {{  }}",);
+        defmt::write!(
+            fmt,
+            "ReturnedSinglePatternStatus {{ rsps: {:#010x} }}",
+            self.rsps()
+        );
     }
 }
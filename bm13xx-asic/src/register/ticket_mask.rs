@@ -32,22 +32,52 @@ impl TicketMask {
         let largest_power_of_two = (1u32 << (31 - diff.leading_zeros())) - 1u32;
         Self(largest_power_of_two.to_le().reverse_bits().to_be())
     }
+
+    /// ## Get the difficulty implied by the mask.
+    ///
+    /// This is the inverse of `from_difficulty`, so a difficulty which is not a
+    /// power of two reads back rounded down to the previous power of two.
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// use bm13xx_asic::register::TicketMask;
+    ///
+    /// assert_eq!(TicketMask(0x0000_00ff).difficulty(), 256);
+    /// assert_eq!(TicketMask(0x0000_80ff).difficulty(), 512);
+    /// for shift in 0..32 {
+    ///     let diff = 1 << shift;
+    ///     assert_eq!(TicketMask::from_difficulty(diff).difficulty(), diff);
+    /// }
+    /// assert_eq!(TicketMask::from_difficulty(1000).difficulty(), 512);
+    /// assert_eq!(TicketMask::from_difficulty(300).difficulty(), 256);
+    /// assert_eq!(TicketMask::from_difficulty(u32::MAX).difficulty(), 0x8000_0000);
+    /// ```
+    pub fn difficulty(&self) -> u32 {
+        self.0.to_le().reverse_bits().to_be().saturating_add(1)
+    }
 }
 
 impl core::fmt::Display for TicketMask {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        f.debug_struct("TicketMask").finish()
+        f.debug_struct("TicketMask")
+            .field("difficulty", &self.difficulty())
+            .finish()
     }
 }
 
 #[cfg(feature = "defmt-03")]
 impl defmt::Format for TicketMask {
     fn format(&self, fmt: defmt::Formatter) {
-        defmt::write!(fmt, "TicketMask {{  }}",);
+        defmt::write!(fmt, "TicketMask {{ difficulty: {} }}", self.difficulty());
     }
 }
 
 /// # Ticket Mask 2 register
+///
+/// Its layout and relation to the difficulty are unknown: none of the known vendor
+/// firmware sequences program it, so the chips init sequences leave it at its reset
+/// value and only `TicketMask` is derived from the difficulty.
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
 pub struct TicketMask2(pub u32);
 impl_boilerplate_for!(TicketMask2);
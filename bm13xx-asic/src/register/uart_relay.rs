@@ -20,6 +20,8 @@ impl UARTRelay {
     ///
     /// Get and set the GAP_CNT value.
     ///
+    /// The field is 16 bits wide, so any `u16` fits without touching the relay enable bits.
+    ///
     /// ### Example
     ///
     /// ```
@@ -29,6 +31,12 @@ impl UARTRelay {
     /// assert_eq!(uart_relay.gap_cnt(), 0x000f);
     /// assert_eq!(uart_relay.set_gap_cnt(0).gap_cnt(), 0); // min value
     /// assert_eq!(uart_relay.set_gap_cnt(0xffff).gap_cnt(), 0xffff); // max value
+    /// assert!(!uart_relay.ro_relay_enabled() && !uart_relay.co_relay_enabled());
+    ///
+    /// // first chip of a 13 chips chain, as programmed by the baudrate sequence
+    /// let uart_relay = UARTRelay(0x001b_0003);
+    /// assert_eq!(uart_relay.gap_cnt(), 13 + 14);
+    /// assert!(uart_relay.ro_relay_enabled() && uart_relay.co_relay_enabled());
     /// ```
     pub const fn gap_cnt(&self) -> u16 {
         ((self.0 >> Self::GAP_CNT_OFFSET) & Self::GAP_CNT_MASK) as u16
@@ -88,13 +96,23 @@ impl UARTRelay {
 
 impl core::fmt::Display for UARTRelay {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        f.debug_struct("UARTRelay").finish()
+        f.debug_struct("UARTRelay")
+            .field("gap_cnt", &self.gap_cnt())
+            .field("ro_relay_enabled", &self.ro_relay_enabled())
+            .field("co_relay_enabled", &self.co_relay_enabled())
+            .finish()
     }
 }
 
 #[cfg(feature = "defmt-03")]
 impl defmt::Format for UARTRelay {
     fn format(&self, fmt: defmt::Formatter) {
-        defmt::write!(fmt, "UARTRelay {{  }}",);
+        defmt::write!(
+            fmt,
+            "UARTRelay {{ gap_cnt: {}, ro_relay_enabled: {}, co_relay_enabled: {} }}",
+            self.gap_cnt(),
+            self.ro_relay_enabled(),
+            self.co_relay_enabled(),
+        );
     }
 }
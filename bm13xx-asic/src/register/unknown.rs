@@ -1,8 +1,12 @@
 use crate::register::Register;
 
+// Registers whose function is unknown: the chip models only hold their reset
+// default values, no vendor sequence was seen writing them, so they have no field
+// accessors. A register gets its own module once a field meaning is established,
+// as was done for `RegA8`.
 macro_rules! unknown {
     ($REG:ident, $ADDR:expr) => {
-        /// # $REG register
+        #[doc = concat!("# ", stringify!($REG), " register")]
         #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
         pub struct $REG(pub u32);
         impl_boilerplate_for!($REG);
@@ -13,14 +17,14 @@ macro_rules! unknown {
 
         impl core::fmt::Display for $REG {
             fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-                f.debug_struct("$REG").finish()
+                f.debug_struct(stringify!($REG)).finish()
             }
         }
 
         #[cfg(feature = "defmt-03")]
         impl defmt::Format for $REG {
             fn format(&self, fmt: defmt::Formatter) {
-                defmt::write!(fmt, "$REG {{  }}",);
+                defmt::write!(fmt, "{=str} {{  }}", stringify!($REG));
             }
         }
     };
@@ -81,6 +81,19 @@ impl<const C: usize, const SC: usize, const CSC: usize, const D: usize> Sha<C, S
         self.small_cores_cnt
     }
 
+    /// ## Get the number of Small Cores in each Core of the ASIC
+    ///
+    /// ### Example
+    /// ```
+    /// use bm13xx_asic::sha::Sha;
+    ///
+    /// let asic = Sha::<168, 672, 4, 4>::new(); // BM1397
+    /// assert_eq!(asic.core_small_core_count(), 4);
+    /// ```
+    pub fn core_small_core_count(&self) -> usize {
+        CSC
+    }
+
     /// ## Get the number of Domains in the ASIC
     ///
     /// ### Example
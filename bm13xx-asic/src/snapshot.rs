@@ -0,0 +1,13 @@
+use heapless::Vec;
+
+/// # Register Snapshot
+///
+/// Copy of a chip model `registers` and `core_registers` maps, as (address, value) pairs.
+/// Serializable with the `serde` feature, to store or diff chip configurations.
+#[derive(Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RegisterSnapshot {
+    pub registers: Vec<(u8, u32), 64>,
+    pub core_registers: Vec<(u8, u8), 16>,
+}
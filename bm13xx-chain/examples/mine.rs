@@ -0,0 +1,110 @@
+#![allow(dead_code)]
+
+use bm1366::BM1366;
+use bm13xx_chain::Chain;
+use bm13xx_protocol::response::ResponseType;
+
+use embedded_hal_async::delay::DelayNs;
+use fugit::HertzU64;
+use inquire::Select;
+use std::{env, error::Error, time::Duration};
+use tokio::time::{sleep, timeout, Instant};
+use tokio_adapter::FromTokio;
+use tokio_serial::SerialStream;
+
+struct Delay;
+
+impl DelayNs for Delay {
+    async fn delay_ns(&mut self, n: u32) {
+        sleep(Duration::from_nanos(n.into())).await;
+    }
+}
+
+const VERSION_MASK: u32 = 0x1fff_e000;
+
+// Block header fields, from a real block
+const VERSION: u32 = 0x2000_0000;
+const PREV_BLOCK_HEADER_HASH: [u8; 32] = [
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xff, 0x86, 0x02, 0x00, 0x5b, 0xa4, 0xa5, 0x0e,
+    0x55, 0xd3, 0x00, 0xfc, 0xae, 0x0e, 0xd5, 0x56, 0xd7, 0x76, 0xd8, 0x1a, 0x38, 0xe1, 0x99, 0x1f,
+];
+const FULL_MERKLE_ROOT: [u8; 32] = [
+    0x2d, 0x19, 0x75, 0x74, 0x66, 0x63, 0x21, 0x46, 0xb8, 0x71, 0x7a, 0x7e, 0xfe, 0x83, 0xec, 0x35,
+    0xc0, 0x96, 0xf3, 0xa4, 0xc0, 0xd8, 0x86, 0xda, 0xa8, 0x0e, 0x70, 0x2e, 0xed, 0xe9, 0x96, 0x71,
+];
+const N_BITS: u32 = 0x1704_2450;
+const N_TIME: u32 = 0x6570_de83;
+
+/// Delay between two jobs, the BM1366 rolls its whole space in ~21s with version rolling
+const JOB_INTERVAL: Duration = Duration::from_secs(10);
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn Error>> {
+    env_logger::init();
+
+    let mut args: Vec<String> = env::args().collect();
+
+    // use the first arg as serial port, query interactively if not given
+    let port = if args.len() == 2 {
+        args.pop().unwrap()
+    } else {
+        let ports = tokio_serial::available_ports()?;
+        let ports: Vec<String> = ports.into_iter().map(|p| p.port_name).collect();
+        Select::new("Which serial port should be used?", ports).prompt()?
+    };
+
+    let builder = tokio_serial::new(port, 115_200).timeout(Duration::from_millis(50));
+    let serial = SerialStream::open(&builder)?;
+    let adapter = FromTokio::new(serial);
+
+    let mut chain = Chain::new(1, BM1366::default(), 1, adapter, Delay);
+    chain.enumerate().await?;
+    println!("Enumerated {} asics", chain.asic_cnt);
+    chain.init(256).await?;
+    chain.set_baudrate(1_000_000).await?;
+    chain.reset_all_cores().await?;
+    chain.set_hash_freq(HertzU64::MHz(525)).await?;
+    chain.set_version_rolling(VERSION_MASK).await?;
+
+    // local chip model, only used to decode where the nonces come from
+    let mut bm1366 = BM1366::default();
+    bm1366.enable_version_rolling(VERSION_MASK);
+
+    loop {
+        let job_id = chain
+            .send_job_id(
+                VERSION,
+                PREV_BLOCK_HEADER_HASH,
+                FULL_MERKLE_ROOT,
+                N_BITS,
+                N_TIME,
+            )
+            .await?;
+        println!("Sent job {job_id}");
+        let deadline = Instant::now() + JOB_INTERVAL;
+        while let Ok(resp) = timeout(
+            deadline.saturating_duration_since(Instant::now()),
+            chain.poll_response(),
+        )
+        .await
+        {
+            match resp {
+                Ok(Some(ResponseType::JobVer(job))) => println!(
+                    "Nonce {:#010x} version {:#010x} job {} from chip @{} core {} small core {}",
+                    job.nonce,
+                    job.version_bit,
+                    job.job_id,
+                    bm1366.nonce2chip_addr(job.nonce),
+                    bm1366.nonce2core_id(job.nonce),
+                    bm1366.version2small_core_id(job.version_bit),
+                ),
+                Ok(Some(resp)) => println!("Unexpected response {resp:x?}"),
+                Ok(None) => sleep(Duration::from_millis(10)).await,
+                Err(e) => println!("Bad response {e:?}"),
+            }
+        }
+    }
+}
+
+#[path = "common/tokio_adapter.rs"]
+mod tokio_adapter;
@@ -1,6 +1,7 @@
-use bm13xx_asic::register::ChipIdentification;
+use bm13xx_asic::{register::ChipIdentification, SequenceStep};
 use bm13xx_protocol::response::RegisterResponse;
 use derive_more::From;
+use fugit::HertzU64;
 
 pub type Result<T, E> = core::result::Result<T, Error<E>>;
 
@@ -18,6 +19,12 @@ pub enum Error<E> {
         expected_asic_cnt: u8,
         actual_asic_cnt: u8,
     },
+    /// Neither the first nor the last asic answered at its address, the chain CI/CO may be wired backward
+    ChainWiringSuspect { last_chip_addr: u8 },
+    /// The voltage domain sizes do not add up to the chain asic count, or there are too many domains
+    BadDomainSizes,
+    /// The asic address interval is 0, or too large for the chain asic count
+    BadAddrInterval { interval: u16 },
     /// The BM13xx protocol returned an error
     #[from]
     Protocol(bm13xx_protocol::Error),
@@ -25,6 +32,38 @@ pub enum Error<E> {
     Io(E),
     /// The serial interface returned an error while setting baudrate
     SetBaudrate,
+    /// The asics did not answer after a baudrate change, the serial interface was reverted
+    BaudrateChangeFailed { baudrate: u32 },
+    /// The reset pin returned an error
+    Reset,
+    /// The serial interface did not complete a read within the I/O timeout
+    Timeout,
+    /// A job was sent before the chain was initialized
+    NotInitialized,
+    /// Another asic sequence was started and not completed
+    SequenceInProgress { step: SequenceStep },
+    /// An asic did not answer a register read
+    NoResponse { chip_addr: u8, reg_addr: u8 },
+    /// A clock order register read back from an asic differs from the programmed one
+    ClockOrderMismatch {
+        chip_addr: u8,
+        reg_addr: u8,
+        expected: u32,
+        actual: u32,
+    },
+    /// The requested hash frequency is above the asic maximum
+    FrequencyTooHigh { freq: HertzU64, max: HertzU64 },
+    /// The hash PLL parameter read back from an asic differs from the programmed one
+    FrequencyNotConfirmed { expected: u32, actual: u32 },
+    /// Too many asics were given their own hash frequency
+    TooManyChipFreqs { max: usize },
+    /// The chip address is not the address of an asic of the chain
+    BadChipAddr { chip_addr: u8 },
+    /// Too many consecutive corrupted frames were received in strict mode, the link may be broken
+    TooManyProtocolErrors {
+        consecutive: u8,
+        last: bm13xx_protocol::Error,
+    },
 }
 
 #[rustversion::since(1.81)]
@@ -60,9 +99,72 @@ impl<E: core::fmt::Debug> core::fmt::Debug for Error<E> {
                 .field("expected_asic_cnt", &expected_asic_cnt)
                 .field("actual_asic_cnt", &actual_asic_cnt)
                 .finish(),
+            Error::ChainWiringSuspect { last_chip_addr } => f
+                .debug_struct("ChainWiringSuspect")
+                .field("last_chip_addr", &last_chip_addr)
+                .finish(),
+            Error::BadDomainSizes => f.debug_struct("BadDomainSizes").finish(),
+            Error::NotInitialized => f.debug_struct("NotInitialized").finish(),
+            Error::BadAddrInterval { interval } => f
+                .debug_struct("BadAddrInterval")
+                .field("interval", &interval)
+                .finish(),
             Error::Protocol(protocol_err) => f.debug_tuple("Protocol").field(protocol_err).finish(),
             Error::Io(io_err) => f.debug_tuple("Io").field(io_err).finish(),
             Error::SetBaudrate => f.debug_struct("SetBaudrate").finish(),
+            Error::BaudrateChangeFailed { baudrate } => f
+                .debug_struct("BaudrateChangeFailed")
+                .field("baudrate", &baudrate)
+                .finish(),
+            Error::Reset => f.debug_struct("Reset").finish(),
+            Error::Timeout => f.debug_struct("Timeout").finish(),
+            Error::SequenceInProgress { step } => f
+                .debug_struct("SequenceInProgress")
+                .field("step", step)
+                .finish(),
+            Error::NoResponse {
+                chip_addr,
+                reg_addr,
+            } => f
+                .debug_struct("NoResponse")
+                .field("chip_addr", &chip_addr)
+                .field("reg_addr", &format_args!("{:#04x}", reg_addr))
+                .finish(),
+            Error::ClockOrderMismatch {
+                chip_addr,
+                reg_addr,
+                expected,
+                actual,
+            } => f
+                .debug_struct("ClockOrderMismatch")
+                .field("chip_addr", &chip_addr)
+                .field("reg_addr", &format_args!("{:#04x}", reg_addr))
+                .field("expected", &format_args!("{:#010x}", expected))
+                .field("actual", &format_args!("{:#010x}", actual))
+                .finish(),
+            Error::FrequencyTooHigh { freq, max } => f
+                .debug_struct("FrequencyTooHigh")
+                .field("freq", &freq)
+                .field("max", &max)
+                .finish(),
+            Error::BadChipAddr { chip_addr } => f
+                .debug_struct("BadChipAddr")
+                .field("chip_addr", &chip_addr)
+                .finish(),
+            Error::FrequencyNotConfirmed { expected, actual } => f
+                .debug_struct("FrequencyNotConfirmed")
+                .field("expected", &expected)
+                .field("actual", &actual)
+                .finish(),
+            Error::TooManyChipFreqs { max } => f
+                .debug_struct("TooManyChipFreqs")
+                .field("max", &max)
+                .finish(),
+            Error::TooManyProtocolErrors { consecutive, last } => f
+                .debug_struct("TooManyProtocolErrors")
+                .field("consecutive", &consecutive)
+                .field("last", &last)
+                .finish(),
         }
     }
 }
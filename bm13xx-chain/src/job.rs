@@ -0,0 +1,106 @@
+use bm13xx_protocol::{command::Command, crc::crc16};
+use core::time::Duration;
+
+/// # Job
+///
+/// Block header fields of a job, named after the stratum work they usually come from.
+///
+/// ## Example
+/// ```
+/// use bm13xx_chain::Job;
+///
+/// let job = Job {
+///     version: 0x2000_0000,
+///     prev_block_header_hash: [0; 32],
+///     full_merkle_root: [0; 32],
+///     n_bits: 0x1704_2450,
+///     n_time: 0x6570_de83,
+///     starting_nonce: Some(0x8000_0000),
+/// };
+/// let header = job.header(8);
+/// assert_eq!(header[4], 8); // job_id
+/// assert_eq!(header[6..10], [0x00, 0x00, 0x00, 0x80]); // starting_nonce
+/// assert_eq!(header[10..14], [0x50, 0x24, 0x04, 0x17]); // n_bits
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+pub struct Job {
+    pub version: u32,
+    pub prev_block_header_hash: [u8; 32],
+    pub full_merkle_root: [u8; 32],
+    pub n_bits: u32,
+    pub n_time: u32,
+    /// Nonce the asics start hashing from, `None` to leave the default of 0.
+    pub starting_nonce: Option<u32>,
+}
+
+impl Job {
+    /// Build the Job with Header command frame for the given `job_id`.
+    pub fn header(&self, job_id: u8) -> [u8; 88] {
+        let mut data = Command::job_header(
+            job_id,
+            self.n_bits,
+            self.n_time,
+            self.full_merkle_root,
+            self.prev_block_header_hash,
+            self.version,
+        );
+        if let Some(starting_nonce) = self.starting_nonce {
+            data[6..10].clone_from_slice(&starting_nonce.to_le_bytes());
+            let crc = crc16(&data[2..86]);
+            data[86..88].clone_from_slice(&crc.to_be_bytes());
+        }
+        data
+    }
+}
+
+impl From<&Job> for [u8; 88] {
+    /// Build the Job with Header command frame with a `job_id` of 0.
+    fn from(job: &Job) -> Self {
+        job.header(0)
+    }
+}
+
+/// # Job Source
+///
+/// Work given to `Chain::run`, asked for a new `Job` each time the previous one is
+/// about to be exhausted. Any `Iterator` of `Job` is a source.
+pub trait JobSource {
+    /// Next job to send, `None` to stop the run.
+    fn next_job(&mut self) -> Option<Job>;
+}
+
+impl<I: Iterator<Item = Job>> JobSource for I {
+    fn next_job(&mut self) -> Option<Job> {
+        self.next()
+    }
+}
+
+/// # Solved Job
+///
+/// A nonce found by the asics, given by `Chain::run` with the job it solves.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+pub struct SolvedJob {
+    /// The job as it was given by the `JobSource`.
+    pub job: Job,
+    /// The `job_id` the job was sent with.
+    pub job_id: u8,
+    pub nonce: u32,
+    /// The job `version` with the rolled version bits, if Version Rolling is enabled.
+    pub version: u32,
+}
+
+/// # Sent Job
+///
+/// A job sent by `Chain::send_job_header`, with the window it covers to schedule the next one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+pub struct SentJob {
+    /// The `job_id` the job was sent with.
+    pub job_id: u8,
+    /// Time the asics take to roll the whole space of the job, see `Asic::rolling_duration`.
+    pub rolling_duration: Duration,
+    /// Version bits rolled by the asics, 0 if Version Rolling is disabled.
+    pub version_window: u32,
+}
@@ -3,11 +3,13 @@
 //! Thanks to this abstraction layer, it can be used on full-fledged operating
 //! systems as well as embedded devices.
 //!
-//! By default, this library exposes an async API.
+//! By default, this library exposes an async API. The `sync` feature adds adapters to
+//! drive the same `Chain` from a blocking `embedded-io` serial interface, see `SyncChain`.
 //!
 //! # Examples
 //! The crate ships with a CLI example that utilize the library from your host computer:
 //! * [`bm13xx-cli.rs`](examples/cli.rs) uses the asynchronous interface (embedded-io-async).
+//! * [`bm13xx-mine.rs`](examples/mine.rs) sends a hardcoded block header and prints the nonces found.
 //!
 //! The example below demonstrates how to use it with an ESP32,
 //! showcasing the strength of the embedded-hal abstractions.
@@ -74,49 +76,426 @@
 pub(crate) mod fmt;
 
 mod error;
+mod job;
+mod stats;
+mod sweep;
+#[cfg(feature = "sync")]
+mod sync;
+mod timeout;
 
 pub use self::error::{Error, Result};
+pub use self::job::{Job, JobSource, SentJob, SolvedJob};
+pub use self::stats::ChainStats;
+pub use self::sweep::{
+    FrequencySweepResult, PatternTestResults, PATTERN_GROUPS, PATTERN_GROUP_CORES,
+    PATTERN_SINGLE_CORES,
+};
+#[cfg(feature = "sync")]
+pub use self::sync::{block_on, BlockingDelay, BlockingPort, SyncChain};
 
-use bm13xx_asic::{register::ChipIdentification, Asic, CmdDelay};
+use bm13xx_asic::{
+    bring_up_sequence,
+    core_register::{
+        HashClockCtrl, ProcessMonitorCtrl, ProcessMonitorData, ProcessMonitorSelect, SweepClockCtrl,
+    },
+    register::{
+        ChipIdentification, ClockOrderControl0, ClockOrderControl1, ClockOrderStatus,
+        CoreRegisterValue, DriverSelect, FrequencySweepControl1, GoldenNonceForSweepReturn,
+        HashCountingNumber, IoDriverStrenghtConfiguration, NonceReturnedTimeout, PLL0Parameter,
+        ReturnedGroupPatternStatus, ReturnedSinglePatternStatus, VersionRolling,
+    },
+    Asic, BaudratePlan, CmdDelay,
+};
 use bm13xx_protocol::{
     command::{Command, Destination},
-    response::{Response, ResponseType},
+    decoder::{FrameDecoder, FRAME_DECODER_BUF_SIZE},
+    response::{JobVersionResponse, Response, ResponseType},
 };
 
+use core::time::Duration;
+use embedded_hal::digital::{ErrorType, OutputPin};
 use embedded_hal_async::delay::DelayNs;
 use embedded_io_async::{Read, Write};
 use fugit::HertzU64;
+use heapless::{FnvIndexMap, HistoryBuffer, Vec};
+use timeout::with_timeout;
+
+/// Maximum number of voltage domains a chain can be split into.
+pub const MAX_DOMAINS: usize = 64;
+
+/// Maximum number of Write Register commands `Chain::send_all` flushes in a single write.
+pub const MAX_BATCH_CMDS: usize = 16;
+
+/// Baudrate the asics use after reset.
+pub const DEFAULT_BAUDRATE: u32 = 115_200;
+
+/// Maximum number of asics whose hash frequency can differ from the rest of the chain.
+pub const MAX_CHIP_FREQS: usize = 16;
+
+/// Number of recently sent `job_id` kept by the chain to match the job responses.
+pub const JOB_HISTORY_LEN: usize = 8;
+
+/// Number of job responses kept by the chain while waiting for a register response.
+pub const PENDING_JOBS_LEN: usize = 16;
+
+/// Hash frequency `Chain::park` ramps the asics down to.
+pub const PARK_HASH_FREQ: HertzU64 = HertzU64::MHz(50);
+
+/// Number of `ChipIdentification` reads attempted to check a baudrate change.
+const BAUDRATE_CHECK_RETRIES: usize = 3;
+
+/// Time the serial interface is given to deliver more bytes when draining it, or an asic
+/// to answer a register read, if no I/O timeout is set, see `Chain::set_io_timeout`.
+const DRAIN_TIMEOUT: Duration = Duration::from_millis(50);
+
+/// Longest delay `Chain::run` waits for when the serial interface is idle.
+const RUN_IDLE_DELAY_US: u32 = 1_000;
+
+/// Default number of consecutive corrupted frames tolerated by `poll_response` in strict mode.
+pub const DEFAULT_MAX_PROTOCOL_ERRORS: u8 = 3;
+
+/// Round `stride` to a `job_id` increment keeping the Small Core ID bits clear.
+fn job_id_stride(asic: &impl Asic, stride: u8) -> u8 {
+    let bits = JobVersionResponse::small_core_id_bits(asic.core_small_core_count());
+    let space = 1u8 << bits;
+    (stride & !(space - 1)).max(space)
+}
 
 pub trait Baud {
     fn set_baudrate(&mut self, baudrate: u32);
 }
 
-#[derive(Debug, PartialEq)]
+/// State of the asics when enumerating a chain, see `Chain::enumerate_with_mode`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
 #[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
-pub struct Chain<A, P, D> {
+pub enum EnumerateMode {
+    /// The asics just left reset, e.g. after `Chain::power_cycle`.
+    #[default]
+    Cold,
+    /// The asics are already running, e.g. when reconnecting to a mining chain.
+    Warm,
+}
+
+/// Placeholder for a chain without a controllable NRST pin.
+#[derive(Debug, Default, PartialEq)]
+pub struct NoPin;
+
+impl ErrorType for NoPin {
+    type Error = core::convert::Infallible;
+}
+
+impl OutputPin for NoPin {
+    fn set_low(&mut self) -> core::result::Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn set_high(&mut self) -> core::result::Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Chain<A, P, D, R = NoPin> {
     pub asic_cnt: u8,
     asic: A,
     pub asic_addr_interval: u16,
+    asic_addr_interval_cfg: Option<u16>,
+    max_missing: u8,
+    present: [u32; 8],
     domain_cnt: u8,
+    domain_sizes: Vec<u8, MAX_DOMAINS>,
+    baudrate: u32,
+    hash_freq: HertzU64,
+    chip_freqs: FnvIndexMap<u8, HertzU64, MAX_CHIP_FREQS>,
+    job_id: u8,
+    job_id_stride: u8,
+    recent_job_ids: HistoryBuffer<u8, JOB_HISTORY_LEN>,
+    pending_jobs: Vec<ResponseType, PENDING_JOBS_LEN>,
+    decoder: FrameDecoder,
+    stats: ChainStats,
+    strict: bool,
+    max_protocol_errors: u8,
+    protocol_errors: u8,
+    initialized: bool,
+    io_timeout: Option<Duration>,
+    verify_freq: bool,
+    check_wiring: bool,
     port: P,
     delay: D,
+    reset: R,
 }
 
 impl<A: Asic, P: Read + Write + Baud, D: DelayNs> Chain<A, P, D> {
     pub fn new(asic_cnt: u8, asic: A, domain_cnt: u8, port: P, delay: D) -> Self {
-        Chain::<A, P, D> {
+        Chain::new_with_reset(asic_cnt, asic, domain_cnt, port, delay, NoPin)
+    }
+}
+
+impl<A: Asic, P: Read + Write + Baud, D: DelayNs, R: OutputPin> Chain<A, P, D, R> {
+    /// ## Create a chain whose NRST line is driven by the `reset` pin
+    pub fn new_with_reset(
+        asic_cnt: u8,
+        asic: A,
+        domain_cnt: u8,
+        port: P,
+        delay: D,
+        reset: R,
+    ) -> Self {
+        let job_id_stride = job_id_stride(&asic, asic.core_small_core_count() as u8);
+        let hash_freq = asic.hash_freq();
+        Chain::<A, P, D, R> {
             asic_cnt,
             asic,
             asic_addr_interval: 0,
+            asic_addr_interval_cfg: None,
+            max_missing: 0,
+            present: [u32::MAX; 8],
             domain_cnt,
+            domain_sizes: Vec::new(),
+            baudrate: DEFAULT_BAUDRATE,
+            hash_freq,
+            chip_freqs: FnvIndexMap::new(),
+            job_id: 0,
+            job_id_stride,
+            recent_job_ids: HistoryBuffer::new(),
+            pending_jobs: Vec::new(),
+            decoder: FrameDecoder::new(false),
+            stats: ChainStats::default(),
+            strict: false,
+            max_protocol_errors: DEFAULT_MAX_PROTOCOL_ERRORS,
+            protocol_errors: 0,
+            initialized: false,
+            io_timeout: None,
+            verify_freq: false,
+            check_wiring: false,
             port,
             delay,
+            reset,
+        }
+    }
+
+    /// ## Get the human readable name of the chain asics
+    pub fn asic_name(&self) -> &'static str {
+        self.asic.chip_name()
+    }
+
+    /// ## Set a fixed address interval between asics
+    ///
+    /// By default `enumerate` derives `asic_addr_interval` as `256 / asic_cnt`.
+    /// Some boards intentionally use another addressing scheme (e.g. a fixed
+    /// interval of 256 on single-chip boards), so this override will be used
+    /// by `enumerate` instead of the derived value.
+    pub fn set_asic_addr_interval(&mut self, interval: u16) {
+        self.asic_addr_interval_cfg = Some(interval);
+        self.asic_addr_interval = interval;
+    }
+
+    /// ## Tolerate missing asics during enumeration
+    ///
+    /// By default `enumerate` fails if less than `asic_cnt` asics answer. This allows
+    /// up to `max_missing` of them to be absent, the addresses actually answering
+    /// being then probed one by one, see `is_present`.
+    pub fn set_max_missing(&mut self, max_missing: u8) {
+        self.max_missing = max_missing;
+    }
+
+    /// ## Bound the time a read of the serial interface may take
+    ///
+    /// By default the serial interface is expected to return no byte after its own
+    /// timeout, so a read never blocks forever. With a serial interface blocking until
+    /// some bytes are received, this gives up reading after `timeout`, e.g. if an asic
+    /// died. A zero `timeout` disables it.
+    ///
+    /// A read timing out ends `enumerate`, `flush_rx` and register reads as if no more
+    /// bytes were received, and makes `poll_response` return a timeout error.
+    /// `flush_rx` and register reads never wait more than a short internal timeout for
+    /// bytes, even without I/O timeout, so they also end on a serial interface waiting for
+    /// the next byte, e.g. when an asic does not answer `ping`.
+    pub fn set_io_timeout(&mut self, timeout: Duration) {
+        self.io_timeout = if timeout.is_zero() {
+            None
+        } else {
+            Some(timeout)
+        };
+    }
+
+    /// Read the serial interface, within the I/O timeout if any.
+    async fn read(&mut self, buf: &mut [u8]) -> Result<usize, P::Error> {
+        match self.io_timeout {
+            None => self.port.read(buf).await.map_err(Error::Io),
+            Some(timeout) => with_timeout(&mut self.delay, timeout, self.port.read(buf))
+                .await
+                .ok_or(Error::Timeout)?
+                .map_err(Error::Io),
+        }
+    }
+
+    /// Read the serial interface when nothing more may be received, within the I/O timeout,
+    /// or `DRAIN_TIMEOUT` if none.
+    async fn read_bounded(&mut self, buf: &mut [u8]) -> Result<usize, P::Error> {
+        let timeout = self.io_timeout.unwrap_or(DRAIN_TIMEOUT);
+        with_timeout(&mut self.delay, timeout, self.port.read(buf))
+            .await
+            .ok_or(Error::Timeout)?
+            .map_err(Error::Io)
+    }
+
+    /// ## Iterate over the chip addresses of the chain asics
+    ///
+    /// Yields `0, asic_addr_interval, 2 * asic_addr_interval, ...` for the `asic_cnt` asics.
+    pub fn chip_addresses(&self) -> impl Iterator<Item = u8> {
+        let interval = self.asic_addr_interval;
+        (0..self.asic_cnt as u16).map(move |i| (i * interval) as u8)
+    }
+
+    /// Chip address of the asic at index `asic_i` in the chain.
+    fn chip_addr(&self, asic_i: u8) -> u8 {
+        (asic_i as u16 * self.asic_addr_interval) as u8
+    }
+
+    /// Check that `chip_addr` is one of `chip_addresses`.
+    fn check_chip_addr(&self, chip_addr: u8) -> Result<(), P::Error> {
+        if !self.chip_addresses().any(|addr| addr == chip_addr) {
+            return Err(Error::BadChipAddr { chip_addr });
+        }
+        Ok(())
+    }
+
+    /// ## Check if an asic answered at the given chip address during enumeration
+    ///
+    /// All addresses are considered present until `enumerate` is done.
+    pub fn is_present(&self, chip_addr: u8) -> bool {
+        self.present[chip_addr as usize / 32] & (1 << (chip_addr % 32)) != 0
+    }
+
+    fn set_present(&mut self, chip_addr: u8, present: bool) {
+        if present {
+            self.present[chip_addr as usize / 32] |= 1 << (chip_addr % 32);
+        } else {
+            self.present[chip_addr as usize / 32] &= !(1 << (chip_addr % 32));
         }
     }
 
+    /// ## Set the number of asics of each voltage domain
+    ///
+    /// By default the chain is split into `domain_cnt` voltage domains of
+    /// `asic_cnt / domain_cnt` asics each. Some hashboards have domains of
+    /// different sizes, this gives the asic count of each domain, in chain order.
+    /// Domains of different sizes are only accepted if the asic supports them, see
+    /// `Asic::supports_mixed_domains`.
+    ///
+    /// ### Errors
+    ///
+    /// - Bad domain sizes
+    pub fn set_domain_sizes(&mut self, domain_sizes: &[u8]) -> Result<(), P::Error> {
+        if domain_sizes.iter().map(|&s| s as u16).sum::<u16>() != self.asic_cnt as u16 {
+            return Err(Error::BadDomainSizes);
+        }
+        if !self.asic.supports_mixed_domains() && domain_sizes.windows(2).any(|w| w[0] != w[1]) {
+            return Err(Error::BadDomainSizes);
+        }
+        self.domain_sizes = Vec::from_slice(domain_sizes).map_err(|_| Error::BadDomainSizes)?;
+        self.domain_cnt = domain_sizes.len() as u8;
+        Ok(())
+    }
+
+    /// Number of voltage domains and asic count of each, when all domains have the same size.
+    ///
+    /// Both are at least 1, so a single asic chain works whatever `domain_cnt` it was given.
+    fn uniform_domains(&self) -> (u8, u8) {
+        let domain_cnt = self.domain_cnt.clamp(1, self.asic_cnt.max(1));
+        (domain_cnt, (self.asic_cnt / domain_cnt).max(1))
+    }
+
+    /// ## Hold all asics of the chain in reset
+    ///
+    /// Drives NRST low and resets the asic model state. The chain stays unusable
+    /// until NRST is released, which is what `power_cycle` does.
+    ///
+    /// Bytes received and not yet decoded are dropped, see `flush_rx`.
+    ///
+    /// ### Errors
+    ///
+    /// - Reset pin error
+    /// - I/O error
+    pub async fn reset(&mut self) -> Result<(), P::Error> {
+        self.reset.set_low().map_err(|_| Error::Reset)?;
+        self.asic.reset();
+        self.hash_freq = self.asic.hash_freq();
+        self.chip_freqs.clear();
+        self.reset_job_id();
+        self.pending_jobs.clear();
+        self.initialized = false;
+        self.decoder = FrameDecoder::new(false);
+        self.flush_rx().await?;
+        Ok(())
+    }
+
+    /// ## Drop the bytes received and not yet decoded
+    ///
+    /// Empties the frame decoder and reads the serial interface until it has no more
+    /// bytes to give, e.g. to drop the garbage received around a baudrate change. A read
+    /// returning no byte, or no byte received within the I/O timeout, ends the drain.
+    ///
+    /// Returns the number of bytes dropped.
+    ///
+    /// ### Errors
+    ///
+    /// - I/O error
+    pub async fn flush_rx(&mut self) -> Result<usize, P::Error> {
+        let mut dropped = self.decoder.len();
+        self.decoder.clear();
+        let mut buf = [0u8; FRAME_DECODER_BUF_SIZE / 2];
+        loop {
+            let n = match self.read_bounded(&mut buf).await {
+                Err(Error::Timeout) => 0,
+                n => n?,
+            };
+            if n == 0 {
+                break;
+            }
+            dropped += n;
+        }
+        if dropped > 0 {
+            debug!("Flushed {} received bytes", dropped);
+        }
+        Ok(dropped)
+    }
+
+    /// ## Power cycle the chain and bring it back to a ready state
+    ///
+    /// Holds the asics in reset using `reset`, releases NRST, restores the
+    /// default baudrate the asics wake up with, then runs `enumerate` and `init`.
+    ///
+    /// ### Errors
+    ///
+    /// - Reset pin error
+    /// - Any `enumerate` or `init` error
+    pub async fn power_cycle(&mut self, difficulty: u32) -> Result<(), P::Error> {
+        self.pulse_reset().await?;
+        self.enumerate().await?;
+        self.init(difficulty).await
+    }
+
+    /// Pulse NRST and restore the default baudrate the asics wake up with.
+    async fn pulse_reset(&mut self) -> Result<(), P::Error> {
+        self.reset().await?;
+        self.delay.delay_ms(100).await;
+        self.reset.set_high().map_err(|_| Error::Reset)?;
+        self.delay.delay_ms(100).await;
+        self.port.set_baudrate(DEFAULT_BAUDRATE);
+        self.baudrate = DEFAULT_BAUDRATE;
+        Ok(())
+    }
+
     /// ## Enumerate all asics on the chain
     ///
-    /// Sets the `asic_addr_interval` according to the number of asics enumerated
+    /// Sets the `asic_addr_interval` according to the number of asics expected,
+    /// unless a fixed interval was given using `set_asic_addr_interval`.
+    ///
+    /// Enumeration stops once `asic_cnt` asics answered, or when the serial interface
+    /// has no more bytes to give. If some asics are missing within the tolerance given
+    /// by `set_max_missing`, each address is then probed to know which are present.
     ///
     /// ### Errors
     ///
@@ -126,7 +505,114 @@ impl<A: Asic, P: Read + Write + Baud, D: DelayNs> Chain<A, P, D> {
     /// - Unexpected asic
     /// - Protocol error
     /// - Unexpected asic count
+    /// - Chain wiring suspect, see `set_check_wiring`
     pub async fn enumerate(&mut self) -> Result<(), P::Error> {
+        self.enumerate_with_mode(EnumerateMode::Cold).await
+    }
+
+    /// ## Enumerate all asics on the chain, given the state they are in
+    ///
+    /// Same as `enumerate` for `EnumerateMode::Cold`. None of the modes drive NRST.
+    ///
+    /// With `EnumerateMode::Warm`, the asics are re-enumerated without being reset
+    /// first: the bytes still received from a running chain are dropped before counting
+    /// them, and their cores are always reset before they are addressed again, whatever
+    /// the chip generation. The serial interface must already use the asics baudrate.
+    ///
+    /// ### Errors
+    ///
+    /// Same as `enumerate`.
+    pub async fn enumerate_with_mode(&mut self, mode: EnumerateMode) -> Result<(), P::Error> {
+        if mode == EnumerateMode::Warm {
+            self.flush_rx().await?;
+        }
+        let (asic_cnt, post_s19jpro) = self.count_asics(self.asic_cnt).await?;
+        if asic_cnt > 0 {
+            self.asic_addr_interval = self
+                .asic_addr_interval_cfg
+                .unwrap_or(256 / (self.asic_cnt as u16));
+        }
+        let missing = self.asic_cnt - asic_cnt;
+        if asic_cnt == 0 || missing > self.max_missing {
+            return Err(Error::UnexpectedAsicCount {
+                expected_asic_cnt: self.asic_cnt,
+                actual_asic_cnt: asic_cnt,
+            });
+        }
+        self.address_asics(missing, post_s19jpro, mode == EnumerateMode::Warm)
+            .await?;
+        self.check_wiring().await
+    }
+
+    /// ## Check the chain wiring after enumeration
+    ///
+    /// When enabled, `enumerate` reads `ChipIdentification` from the first and the last
+    /// asic of the chain once they are addressed. A chain whose CI/CO are wired backward
+    /// still counts and addresses its asics, but they answer in reverse order, so none
+    /// of these two answers as expected. Disabled by default.
+    pub fn set_check_wiring(&mut self, check_wiring: bool) {
+        self.check_wiring = check_wiring;
+    }
+
+    /// Fails if neither the first nor the last asic answers at its address, when enabled.
+    async fn check_wiring(&mut self) -> Result<(), P::Error> {
+        if !self.check_wiring {
+            return Ok(());
+        }
+        let last_chip_addr = ((self.asic_cnt as u16 - 1) * self.asic_addr_interval) as u8;
+        if self.ping(0).await? || self.ping(last_chip_addr).await? {
+            return Ok(());
+        }
+        Err(Error::ChainWiringSuspect { last_chip_addr })
+    }
+
+    /// ## Enumerate the asics again after some were added or removed
+    ///
+    /// Pulses NRST, which also resets the asic model and any sequence in progress,
+    /// restores the default baudrate, then enumerates without expecting a given
+    /// number of asics. `asic_cnt` and `asic_addr_interval` are updated to what was
+    /// found, and the voltage domain sizes are dropped if they do not add up anymore.
+    ///
+    /// Like `enumerate`, this relies on the serial interface read returning no byte
+    /// once all the asics answered. The chain must be initialized again afterwards.
+    ///
+    /// Returns the new asic count.
+    ///
+    /// ### Errors
+    ///
+    /// - Reset pin error
+    /// - I/O error
+    /// - Unexpected response
+    /// - Bad register response
+    /// - Unexpected asic
+    /// - Protocol error
+    /// - Unexpected asic count, if no asic answered
+    pub async fn reenumerate(&mut self) -> Result<u8, P::Error> {
+        self.pulse_reset().await?;
+        let (asic_cnt, post_s19jpro) = self.count_asics(u8::MAX).await?;
+        if asic_cnt == 0 {
+            return Err(Error::UnexpectedAsicCount {
+                expected_asic_cnt: self.asic_cnt,
+                actual_asic_cnt: 0,
+            });
+        }
+        self.asic_cnt = asic_cnt;
+        self.asic_addr_interval = self
+            .asic_addr_interval_cfg
+            .unwrap_or(256 / (asic_cnt as u16));
+        if self.domain_sizes.iter().map(|&s| s as u16).sum::<u16>() != asic_cnt as u16 {
+            self.domain_sizes.clear();
+        }
+        self.domain_cnt = self.domain_cnt.min(asic_cnt);
+        self.present = [u32::MAX; 8];
+        self.address_asics(0, post_s19jpro, false).await?;
+        Ok(asic_cnt)
+    }
+
+    /// Count the asics answering a ChipIdentification read, up to `max`.
+    ///
+    /// Also tells if the asics are post S19jPro ones.
+    async fn count_asics(&mut self, max: u8) -> Result<(u8, bool), P::Error> {
         let cmd = Command::read_reg(ChipIdentification::ADDR, Destination::All);
         self.port.write_all(&cmd).await.map_err(Error::Io)?;
 
@@ -135,12 +621,13 @@ impl<A: Asic, P: Read + Write + Baud, D: DelayNs> Chain<A, P, D> {
         loop {
             debug!("Enumerating asic: {}", asic_cnt);
             // FIXME: This is a workaround for the Timeout based loop
-            if asic_cnt == self.asic_cnt {
+            if asic_cnt == max {
                 break;
             }
             // TODO: fix the Timeout based loop
             let mut resp = [0u8; 9];
-            match self.port.read(&mut resp).await {
+            match self.read(&mut resp).await {
+                Ok(0) | Err(Error::Timeout) => break, // no more asic answering
                 Ok(_) => {}
                 Err(e) => {
                     error!("Error reading response: {:?}", e);
@@ -166,107 +653,3029 @@ impl<A: Asic, P: Read + Write + Baud, D: DelayNs> Chain<A, P, D> {
                 return Err(Error::UnexpectedResponse { resp });
             };
         }
-        if asic_cnt > 0 {
-            self.asic_addr_interval = 256 / (asic_cnt as u16);
-        }
-        if asic_cnt != self.asic_cnt {
-            return Err(Error::UnexpectedAsicCount {
-                expected_asic_cnt: self.asic_cnt,
-                actual_asic_cnt: asic_cnt,
-            });
-        }
+        Ok((asic_cnt, post_s19jpro))
+    }
+
+    /// Give an address to every asic, then probe them if `missing` asics did not answer.
+    async fn address_asics(
+        &mut self,
+        missing: u8,
+        post_s19jpro: bool,
+        reset_cores: bool,
+    ) -> Result<(), P::Error> {
         self.delay.delay_ms(50).await;
-        if post_s19jpro {
+        if post_s19jpro || reset_cores {
             self.delay.delay_ms(100).await;
             while let Some(step) = self.asic.reset_core_next(Destination::All) {
                 self.send(step).await?;
             }
         }
-        let cmd = Command::chain_inactive();
-        self.port.write_all(&cmd).await.map_err(Error::Io)?;
-        if !post_s19jpro {
-            self.delay.delay_ms(2).await;
-            self.port.write_all(&cmd).await.map_err(Error::Io)?;
-            self.delay.delay_ms(2).await;
-            self.port.write_all(&cmd).await.map_err(Error::Io)?;
-        }
-        self.delay.delay_ms(30).await;
-        for i in 0..asic_cnt {
-            let cmd = Command::set_chip_addr((i as u16 * self.asic_addr_interval) as u8);
-            self.port.write_all(&cmd).await.map_err(Error::Io)?;
-            self.delay.delay_ms(10).await;
+        self.set_chip_addrs(if post_s19jpro { 1 } else { 3 })
+            .await?;
+        for chip_addr in self.chip_addresses() {
+            let present = missing == 0 || self.ping(chip_addr).await?;
+            if !present {
+                warn!("Asic @{} is missing", chip_addr);
+            }
+            self.set_present(chip_addr, present);
+        }
+        Ok(())
+    }
+
+    /// Deactivate the chain with `inactive_cnt` ChainInactive commands, then give its
+    /// address to every asic, see `chip_addresses`.
+    async fn set_chip_addrs(&mut self, inactive_cnt: usize) -> Result<(), P::Error> {
+        for step in bring_up_sequence(self.asic_cnt, self.asic_addr_interval, inactive_cnt) {
+            self.send(step).await?;
+        }
+        Ok(())
+    }
+
+    /// ## Address the asics again with a given interval
+    ///
+    /// Deactivates the chain, then gives `i * interval` as address to the i-th asic,
+    /// e.g. an `interval` of 1 gives sequential addresses. The interval is then kept,
+    /// see `set_asic_addr_interval`. The asics must have been enumerated first.
+    ///
+    /// ### Errors
+    ///
+    /// - Bad address interval, if it is 0 or the last asic address does not fit a `u8`
+    /// - I/O error
+    pub async fn assign_addresses(&mut self, interval: u16) -> Result<(), P::Error> {
+        let last = (self.asic_cnt as u32).saturating_sub(1) * interval as u32;
+        if interval == 0 || last > u8::MAX as u32 {
+            return Err(Error::BadAddrInterval { interval });
+        }
+        self.set_asic_addr_interval(interval);
+        self.set_chip_addrs(1).await
+    }
+
+    /// ## Send a batch of Write Register commands
+    ///
+    /// All frames, typically built with `Command::write_regs`, are flushed
+    /// back-to-back, using a single write on the serial interface for every
+    /// `MAX_BATCH_CMDS` frames.
+    ///
+    /// ### Errors
+    ///
+    /// - I/O error
+    pub async fn send_all(&mut self, cmds: &[[u8; 11]]) -> Result<(), P::Error> {
+        let mut buf = [0u8; MAX_BATCH_CMDS * 11];
+        for batch in cmds.chunks(MAX_BATCH_CMDS) {
+            for (frame, cmd) in buf.chunks_exact_mut(11).zip(batch) {
+                frame.copy_from_slice(cmd);
+            }
+            self.port
+                .write_all(&buf[..batch.len() * 11])
+                .await
+                .map_err(Error::Io)?;
         }
-        self.delay.delay_ms(100).await;
         Ok(())
     }
 
-    async fn send(&mut self, step: CmdDelay) -> Result<(), P::Error> {
+    async fn send<const N: usize>(&mut self, step: CmdDelay<N>) -> Result<(), P::Error> {
         self.port.write_all(&step.cmd).await.map_err(Error::Io)?;
         self.delay.delay_ms(step.delay_ms).await;
         Ok(())
     }
 
+    /// ## Send a job built from a block header
+    ///
+    /// The `job_id` is managed by the chain: before each job, it is advanced by the
+    /// job-id stride (wrapping around the `job_id` space) and the new value is returned in the `SentJob`,
+    /// along with the window the job covers: the next job should be sent before its
+    /// `rolling_duration` elapses. See `send_job_id` to only get the `job_id`.
+    ///
+    /// The stride defaults to the number of Small Cores in each Core, because the ASIC
+    /// reuses the low bits of the `job_id` to report which Small Core found the nonce.
+    /// So a `JobResponse.job_id` can be mapped back to the job it belongs to by
+    /// masking these low bits: `job_id & !(stride - 1)`. At most `max_outstanding_jobs`
+    /// jobs can be in flight before a `job_id` is reused.
+    ///
+    /// ### Errors
+    ///
+    /// - The chain was not initialized, see `init`
+    /// - I/O error
+    pub async fn send_job_header(
+        &mut self,
+        version: u32,
+        prev_block_header_hash: [u8; 32],
+        full_merkle_root: [u8; 32],
+        n_bits: u32,
+        n_time: u32,
+    ) -> Result<SentJob, P::Error> {
+        self.send_tracked_job(|job_id| {
+            Command::job_header(
+                job_id,
+                n_bits,
+                n_time,
+                full_merkle_root,
+                prev_block_header_hash,
+                version,
+            )
+        })
+        .await
+    }
+
+    /// Advance the `job_id`, then send the job frame built for it and track it.
+    async fn send_tracked_job(
+        &mut self,
+        header: impl FnOnce(u8) -> [u8; 88],
+    ) -> Result<SentJob, P::Error> {
+        self.check_initialized()?;
+        let space = self.job_id_space();
+        self.job_id = ((self.job_id as u16 + self.job_id_stride as u16) % space) as u8;
+        let job = header(self.job_id);
+        self.port.write_all(&job).await.map_err(Error::Io)?;
+        self.recent_job_ids.write(self.job_id);
+        let version_window = self
+            .asic
+            .register(VersionRolling::ADDR)
+            .map(VersionRolling)
+            .filter(|vr| vr.enabled())
+            .map_or(0, |vr| vr.mask());
+        Ok(SentJob {
+            job_id: self.job_id,
+            rolling_duration: self.asic.rolling_duration(),
+            version_window,
+        })
+    }
+
+    /// ## Send a job built from a block header, only returning its `job_id`
+    ///
+    /// Same as `send_job_header`.
+    pub async fn send_job_id(
+        &mut self,
+        version: u32,
+        prev_block_header_hash: [u8; 32],
+        full_merkle_root: [u8; 32],
+        n_bits: u32,
+        n_time: u32,
+    ) -> Result<u8, P::Error> {
+        self.send_job_header(
+            version,
+            prev_block_header_hash,
+            full_merkle_root,
+            n_bits,
+            n_time,
+        )
+        .await
+        .map(|job| job.job_id)
+    }
+
+    /// ## Send a `Job`
+    ///
+    /// Same as `send_job_header`, also applying the `starting_nonce` of the `Job` if any.
+    pub async fn send_job_struct(&mut self, job: &Job) -> Result<SentJob, P::Error> {
+        self.send_tracked_job(|job_id| job.header(job_id)).await
+    }
+
+    /// ## Send an already built job
+    ///
+    /// The `job_id` inside the frame is left untouched and is not tracked by the chain.
+    /// Unlike `send_job_header`, the chain is not required to be initialized.
     pub async fn send_job(&mut self, job: &[u8]) -> Result<u8, P::Error> {
         self.port.write_all(job).await.map_err(Error::Io)?;
         Ok(job.len() as u8)
     }
 
-    pub async fn read_job(&mut self, job: &mut [u8]) -> Result<u8, P::Error> {
-        self.port.read_exact(job).await.map_err(Error::Io).unwrap();
-        Ok(job.len() as u8)
+    /// ## Get the `job_id` of the last job sent using `send_job_header`
+    pub fn current_job_id(&self) -> u8 {
+        self.job_id
     }
 
-    pub async fn init(&mut self, diffculty: u32) -> Result<(), P::Error> {
-        while let Some(step) = self.asic.init_next(diffculty) {
-            self.send(step).await?;
+    /// ## Check `init` completed since the last reset, before sending jobs
+    fn check_initialized(&self) -> Result<(), P::Error> {
+        if self.initialized {
+            Ok(())
+        } else {
+            Err(Error::NotInitialized)
         }
-        self.delay.delay_ms(100).await;
-        Ok(())
     }
 
-    pub async fn set_baudrate(&mut self, baudrate: u32) -> Result<(), P::Error> {
-        while let Some(step) = self.asic.set_baudrate_next(
-            baudrate,
-            self.domain_cnt,
-            self.asic_cnt / self.domain_cnt,
-            self.asic_addr_interval,
-        ) {
-            self.send(step).await?;
-        }
-        self.delay.delay_ms(50).await;
-        self.port.set_baudrate(baudrate);
-        self.delay.delay_ms(50).await;
-        Ok(())
+    /// ## Reset the `job_id`, so the next job sent using `send_job_header` will start over
+    pub fn reset_job_id(&mut self) {
+        self.job_id = 0;
+        self.recent_job_ids.clear();
     }
 
-    pub async fn reset_all_cores(&mut self) -> Result<(), P::Error> {
-        for asic_i in 0..self.asic_cnt {
-            while let Some(step) = self
-                .asic
-                .reset_core_next(Destination::Chip(asic_i * self.asic_addr_interval as u8))
-            {
-                self.send(step).await?;
-            }
-        }
-        self.delay.delay_ms(100).await;
-        Ok(())
+    /// ## Find back the `job_id` of a recently sent job
+    ///
+    /// The asics report the job a nonce belongs to in the raw `job_id` byte of the response,
+    /// its low bits being replaced by the Small Core ID. So `returned_job_id` is this byte
+    /// shifted right by `JobVersionResponse::small_core_id_bits`, which is the only part of
+    /// the sent `job_id` that comes back.
+    ///
+    /// Returns the full `job_id` of the matching job among the last `JOB_HISTORY_LEN` jobs
+    /// sent using `send_job_header` or `send_job_struct`, or `None` if the nonce is for an older or
+    /// unknown job.
+    pub fn match_job(&self, returned_job_id: usize) -> Option<u8> {
+        let bits = JobVersionResponse::small_core_id_bits(self.asic.core_small_core_count());
+        self.recent_job_ids
+            .oldest_ordered()
+            .filter(|&&job_id| (job_id as usize >> bits) == returned_job_id)
+            .last()
+            .copied()
     }
 
-    pub async fn set_hash_freq(&mut self, freq: HertzU64) -> Result<(), P::Error> {
-        while let Some(step) = self.asic.set_hash_freq_next(freq) {
-            self.send(step).await?;
-        }
-        self.delay.delay_ms(100).await;
-        Ok(())
+    /// ## Set the increment applied to `job_id` between two jobs
+    ///
+    /// The low bits of the `job_id` do not come back in the job responses, see `match_job`.
+    /// So `stride` is rounded down to a multiple of the Small Core ID space, and is at least
+    /// this space, to keep every `job_id` recognizable in the responses.
+    pub fn set_job_id_stride(&mut self, stride: u8) {
+        self.job_id_stride = job_id_stride(&self.asic, stride);
     }
 
-    pub async fn set_version_rolling(&mut self, mask: u32) -> Result<(), P::Error> {
-        if self.asic.has_version_rolling() {
-            while let Some(step) = self.asic.set_version_rolling_next(mask) {
-                self.send(step).await?;
-            }
-            self.delay.delay_ms(100).await;
-        }
-        Ok(())
+    /// ## Get the increment applied to `job_id` between two jobs
+    pub fn job_id_stride(&self) -> u8 {
+        self.job_id_stride
+    }
+
+    /// ## Maximum number of jobs in flight without `job_id` aliasing
+    ///
+    /// This is the number of distinct `job_id` sent before wrapping around the `job_id`
+    /// space, e.g. 32 for a BM1366 or a BM1397 with the default stride. Only the last
+    /// `JOB_HISTORY_LEN` ones are matched back by `match_job` though.
+    pub fn max_outstanding_jobs(&self) -> usize {
+        let space = self.job_id_space();
+        (space
+            >> self
+                .job_id_stride
+                .trailing_zeros()
+                .min(space.trailing_zeros())) as usize
+    }
+
+    /// Number of `job_id` values coming back in the job responses.
+    ///
+    /// The asics return 5 bits of `job_id` above the Small Core ID bits.
+    fn job_id_space(&self) -> u16 {
+        let bits = JobVersionResponse::small_core_id_bits(self.asic.core_small_core_count());
+        (1u16 << (5 + bits)).min(256)
+    }
+
+    /// ## Get the statistics accumulated by `poll_response`
+    pub fn stats(&self) -> ChainStats {
+        self.stats
+    }
+
+    /// ## Enable the strict mode of `poll_response`
+    ///
+    /// By default each corrupted frame is reported as a protocol error and polling can go on.
+    /// In strict mode, once `set_max_protocol_errors` consecutive corrupted frames are
+    /// received, `poll_response` reports `Error::TooManyProtocolErrors` instead, until a valid
+    /// frame is decoded. This helps to detect a fundamentally broken link, like a bad baudrate.
+    pub fn set_strict(&mut self, strict: bool) {
+        self.strict = strict;
+    }
+
+    /// ## Set the number of consecutive corrupted frames tolerated in strict mode
+    pub fn set_max_protocol_errors(&mut self, max_protocol_errors: u8) {
+        self.max_protocol_errors = max_protocol_errors;
+    }
+
+    /// ## Reset the statistics accumulated by `poll_response`
+    pub fn reset_stats(&mut self) {
+        self.stats = ChainStats::default();
+    }
+
+    /// ## Poll the next response from the chain
+    ///
+    /// Reads the serial interface until a complete response frame is decoded.
+    /// Returns `None` if the serial interface has no more bytes to give.
+    /// The job responses received during register reads are returned first.
+    ///
+    /// ### Errors
+    ///
+    /// - I/O error
+    /// - Timeout, see `set_io_timeout`
+    /// - Protocol error, the corrupted frame is skipped
+    /// - Too many protocol errors, in strict mode, the corrupted frame is skipped
+    pub async fn poll_response(&mut self) -> Result<Option<ResponseType>, P::Error> {
+        if let Some(job_resp) = self.take_pending_job() {
+            return Ok(Some(job_resp));
+        }
+        loop {
+            if let Some(resp) = self.try_next_frame()? {
+                return Ok(Some(resp));
+            }
+            let mut buf = [0u8; FRAME_DECODER_BUF_SIZE / 2];
+            let n = self.read(&mut buf).await?;
+            if n == 0 {
+                return Ok(None);
+            }
+            self.decoder.push(&buf[..n]);
+        }
+    }
+
+    /// ## Decode the next response already received, without reading the serial interface
+    ///
+    /// Same as `poll_response`, but only parses the bytes already buffered by previous
+    /// reads. Returns `None` if no complete response frame is buffered yet.
+    ///
+    /// ### Errors
+    ///
+    /// - Protocol error, the corrupted frame is skipped
+    /// - Too many protocol errors, in strict mode, the corrupted frame is skipped
+    pub fn try_next_frame(&mut self) -> Result<Option<ResponseType>, P::Error> {
+        let resyncs = self.decoder.resyncs();
+        let resp = self.decoder.next_frame();
+        self.stats.resyncs = self
+            .stats
+            .resyncs
+            .wrapping_add(self.decoder.resyncs().wrapping_sub(resyncs));
+        match resp {
+            Some(Ok(resp)) => {
+                self.protocol_errors = 0;
+                self.stats.frames = self.stats.frames.wrapping_add(1);
+                if matches!(resp, ResponseType::Job(_) | ResponseType::JobVer(_)) {
+                    self.stats.nonces = self.stats.nonces.wrapping_add(1);
+                }
+                Ok(Some(resp))
+            }
+            Some(Err(e)) => {
+                if matches!(e, bm13xx_protocol::Error::InvalidCrc { .. }) {
+                    self.stats.bad_crc = self.stats.bad_crc.wrapping_add(1);
+                }
+                self.protocol_errors = self.protocol_errors.saturating_add(1);
+                if self.strict && self.protocol_errors >= self.max_protocol_errors {
+                    return Err(Error::TooManyProtocolErrors {
+                        consecutive: self.protocol_errors,
+                        last: e,
+                    });
+                }
+                Err(e.into())
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// ## Run the main mining loop
+    ///
+    /// Sends the jobs of `source` one after the other and gives each nonce found to `sink`,
+    /// until `source` has no more job.
+    ///
+    /// A new job is sent once 3/4 of the asic `rolling_duration` have elapsed, so the
+    /// asics never run out of work. The chain has no clock: elapsed time is accounted by
+    /// the delays waited while the serial interface is idle, and by the time each received
+    /// frame takes at the current baudrate, so a busy chain still gets new jobs. The serial
+    /// interface should then return no byte quickly rather than block, and the hash
+    /// frequency and Version Rolling must be set before running. Nonces for a job older than the last `JOB_HISTORY_LEN` jobs are
+    /// dropped, and corrupted frames are skipped.
+    ///
+    /// ### Errors
+    ///
+    /// - I/O error
+    /// - Too many protocol errors, in strict mode
+    pub async fn run<S: JobSource>(
+        &mut self,
+        source: &mut S,
+        sink: &mut impl FnMut(SolvedJob),
+    ) -> Result<(), P::Error> {
+        let refresh = self.asic.rolling_duration() * 3 / 4;
+        let bits = JobVersionResponse::small_core_id_bits(self.asic.core_small_core_count());
+        let mut jobs: HistoryBuffer<(u8, Job), JOB_HISTORY_LEN> = HistoryBuffer::new();
+        // time taken by the shortest frame, 9 bytes of 10 bits
+        let frame_time = Duration::from_micros(90_000_000 / self.baudrate.max(1) as u64);
+        let mut elapsed = refresh;
+        loop {
+            if elapsed >= refresh {
+                let Some(job) = source.next_job() else {
+                    return Ok(());
+                };
+                let job_id = self.send_job_struct(&job).await?.job_id;
+                jobs.write((job_id, job));
+                elapsed = Duration::ZERO;
+            }
+            let resp = self.poll_response().await;
+            if !matches!(resp, Ok(None) | Err(Error::Timeout)) {
+                elapsed += frame_time;
+            }
+            let (raw_job_id, nonce, version) = match resp {
+                Ok(Some(ResponseType::Job(j))) => (j.job_id, j.nonce, None),
+                Ok(Some(ResponseType::JobVer(j))) => (j.job_id, j.nonce, Some(j)),
+                Ok(Some(ResponseType::Reg(_))) | Err(Error::Protocol(_)) => continue,
+                Ok(None) | Err(Error::Timeout) => {
+                    let wait =
+                        (refresh - elapsed).min(Duration::from_micros(RUN_IDLE_DELAY_US as u64));
+                    self.delay.delay_us(wait.as_micros() as u32).await;
+                    elapsed += wait;
+                    continue;
+                }
+                Err(e) => return Err(e),
+            };
+            let Some(job_id) = self.match_job((raw_job_id >> bits) as usize) else {
+                debug!("Dropping nonce {:x} of unknown job {:x}", nonce, raw_job_id);
+                continue;
+            };
+            if let Some((_, job)) = jobs.oldest_ordered().filter(|(id, _)| *id == job_id).last() {
+                sink(SolvedJob {
+                    job: job.clone(),
+                    job_id,
+                    nonce,
+                    version: version.map_or(job.version, |j| j.full_version(job.version)),
+                });
+            }
+        }
+    }
+
+    pub async fn read_job(&mut self, job: &mut [u8]) -> Result<u8, P::Error> {
+        self.port.read_exact(job).await.map_err(Error::Io).unwrap();
+        Ok(job.len() as u8)
+    }
+
+    /// ## Initialize the chain asics
+    ///
+    /// Jobs can only be sent once this has completed, and must be re-initialized after each `reset`.
+    pub async fn init(&mut self, diffculty: u32) -> Result<(), P::Error> {
+        self.check_no_sequence()?;
+        while let Some(step) = self.asic.init_next(diffculty) {
+            self.send(step).await?;
+        }
+        self.delay.delay_ms(100).await;
+        self.initialized = true;
+        Ok(())
+    }
+
+    /// ## Get the clock path and actual baudrate the asics would use for `baudrate`
+    ///
+    /// The asics can only divide their baudrate base clock by an integer, so the
+    /// requested baudrate may not be exactly achievable.
+    pub fn baudrate_plan(&self, baudrate: u32) -> BaudratePlan {
+        self.asic.baudrate_plan(baudrate)
+    }
+
+    /// ## Change the baudrate of the chain
+    ///
+    /// Programs the asics, switches the serial interface to the new baudrate,
+    /// then checks the first asic still answers a `ChipIdentification` read.
+    /// If it does not within a few retries, the serial interface is reverted
+    /// to the previous baudrate. A warning is logged if the asics can not achieve
+    /// exactly the requested baudrate, see `baudrate_plan`. In both cases, the bytes
+    /// left in the serial interface are dropped, see `flush_rx`.
+    ///
+    /// ### Errors
+    ///
+    /// - I/O error
+    /// - Baudrate change failed
+    /// - Another sequence in progress
+    pub async fn set_baudrate(&mut self, baudrate: u32) -> Result<(), P::Error> {
+        self.check_no_sequence()?;
+        let plan = self.asic.baudrate_plan(baudrate);
+        if plan.achievable_baud != baudrate {
+            warn!(
+                "Baudrate {} is not exactly achievable, asics will use {}",
+                baudrate, plan.achievable_baud
+            );
+        }
+        loop {
+            let step = if self.domain_sizes.is_empty() {
+                let (domain_cnt, domain_asic_cnt) = self.uniform_domains();
+                self.asic.set_baudrate_next(
+                    baudrate,
+                    domain_cnt,
+                    domain_asic_cnt,
+                    self.asic_addr_interval,
+                )
+            } else {
+                self.asic.set_baudrate_next_domains(
+                    baudrate,
+                    &self.domain_sizes,
+                    self.asic_addr_interval,
+                )
+            };
+            match step {
+                Some(step) => self.send(step).await?,
+                None => break,
+            }
+        }
+        self.delay.delay_ms(50).await;
+        self.port.set_baudrate(baudrate);
+        self.delay.delay_ms(50).await;
+        for _ in 0..BAUDRATE_CHECK_RETRIES {
+            if self.ping(0).await? {
+                self.baudrate = baudrate;
+                self.flush_rx().await?;
+                return Ok(());
+            }
+            self.delay.delay_ms(10).await;
+        }
+        warn!("Baudrate change to {} failed, reverting", baudrate);
+        self.port.set_baudrate(self.baudrate);
+        self.flush_rx().await?;
+        Err(Error::BaudrateChangeFailed { baudrate })
+    }
+
+    /// ## Change the baudrate of the chain to the first working candidate
+    ///
+    /// Tries each of the `candidates` baudrates in order using `set_baudrate`, so a
+    /// failing one is reverted before trying the next, typically a lower one.
+    ///
+    /// Returns the baudrate the chain is now using.
+    ///
+    /// ### Errors
+    ///
+    /// - I/O error
+    /// - Baudrate change failed, for the last candidate if none worked
+    /// - Another sequence in progress
+    pub async fn negotiate_baudrate(&mut self, candidates: &[u32]) -> Result<u32, P::Error> {
+        let mut err = Error::BaudrateChangeFailed {
+            baudrate: self.baudrate,
+        };
+        for &baudrate in candidates {
+            match self.set_baudrate(baudrate).await {
+                Ok(()) => return Ok(baudrate),
+                Err(e @ Error::BaudrateChangeFailed { .. }) => err = e,
+                Err(e) => return Err(e),
+            }
+        }
+        Err(err)
+    }
+
+    /// Read a register of the asic at `chip_addr`, `None` if it does not answer properly.
+    ///
+    /// Stale register responses and corrupted frames are skipped until nothing more is
+    /// received, see `read_bounded`. The job responses received meanwhile are kept, see
+    /// `drain_pending_jobs`.
+    async fn read_chip_reg(
+        &mut self,
+        chip_addr: u8,
+        reg_addr: u8,
+    ) -> Result<Option<u32>, P::Error> {
+        let cmd = Command::read_reg(reg_addr, Destination::Chip(chip_addr));
+        self.port.write_all(&cmd).await.map_err(Error::Io)?;
+        loop {
+            match self.try_next_frame() {
+                Ok(Some(ResponseType::Reg(reg_resp))) => {
+                    if reg_resp.chip_addr == chip_addr && reg_resp.reg_addr == reg_addr {
+                        return Ok(Some(reg_resp.reg_value));
+                    }
+                    debug!(
+                        "Skipping stale response of register {:#04x} @{}",
+                        reg_resp.reg_addr, reg_resp.chip_addr
+                    );
+                }
+                Ok(Some(job_resp)) => self.push_pending_job(job_resp),
+                Ok(None) => {
+                    let mut buf = [0u8; 9];
+                    match self.read_bounded(&mut buf).await {
+                        Ok(0) | Err(Error::Timeout) => return Ok(None),
+                        Ok(n) => {
+                            self.decoder.push(&buf[..n]);
+                        }
+                        Err(e) => return Err(e),
+                    }
+                }
+                Err(Error::Protocol(_)) => continue,
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    /// Keep a job response received while waiting for a register response.
+    fn push_pending_job(&mut self, job_resp: ResponseType) {
+        if self.pending_jobs.is_full() {
+            warn!("Too many pending job responses, dropping the oldest");
+            self.pending_jobs.remove(0);
+        }
+        let _ = self.pending_jobs.push(job_resp);
+    }
+
+    /// Oldest job response kept by `push_pending_job`, if any.
+    fn take_pending_job(&mut self) -> Option<ResponseType> {
+        (!self.pending_jobs.is_empty()).then(|| self.pending_jobs.remove(0))
+    }
+
+    /// ## Take the job responses received during register reads
+    ///
+    /// Nonces keep coming while the chain reads a register, they are kept in order, up
+    /// to `PENDING_JOBS_LEN`, instead of being dropped. `poll_response` returns them
+    /// first, this allows to get them without polling the serial interface.
+    pub fn drain_pending_jobs(&mut self) -> impl Iterator<Item = ResponseType> + '_ {
+        core::iter::from_fn(|| self.take_pending_job())
+    }
+
+    /// ## Check the asic at `chip_addr` is responsive
+    ///
+    /// Reads `ChipIdentification` from this asic only and checks the expected chip answered,
+    /// within the I/O timeout, or a short internal one if none. A missing or wrong answer
+    /// is not an error, so this can be used in health monitoring loops, e.g. after a
+    /// frequency change.
+    ///
+    /// ### Errors
+    ///
+    /// - I/O error
+    pub async fn ping(&mut self, chip_addr: u8) -> Result<bool, P::Error> {
+        Ok(self
+            .read_chip_reg(chip_addr, ChipIdentification::ADDR)
+            .await?
+            .is_some_and(|ident| ChipIdentification(ident).chip_id() == self.asic.chip_id()))
+    }
+
+    /// ## Write a core register of all the cores of `dest`
+    ///
+    /// Allows to tune core registers like `ClockDelayCtrl` or `HashClockCtrl` at runtime.
+    ///
+    /// ### Errors
+    ///
+    /// - I/O error
+    pub async fn write_core_register(
+        &mut self,
+        dest: Destination,
+        core_reg_id: u8,
+        core_reg_val: u8,
+    ) -> Result<(), P::Error> {
+        let step = self
+            .asic
+            .write_core_reg_next(core_reg_id, core_reg_val, dest);
+        self.send(step).await
+    }
+
+    /// ## Read a core register of the asic at `chip_addr`
+    ///
+    /// Returns `None` if the asic does not answer properly.
+    ///
+    /// ### Errors
+    ///
+    /// - I/O error
+    pub async fn read_core_register(
+        &mut self,
+        chip_addr: u8,
+        core_reg_id: u8,
+    ) -> Result<Option<u8>, P::Error> {
+        let cmd = self
+            .asic
+            .read_core_reg_cmd(core_reg_id, Destination::Chip(chip_addr));
+        self.port.write_all(&cmd).await.map_err(Error::Io)?;
+        Ok(self
+            .read_chip_reg(chip_addr, CoreRegisterValue::ADDR)
+            .await?
+            .map(|val| CoreRegisterValue(val).core_reg_val()))
+    }
+
+    /// ## Measure the process monitor of the asic at `chip_addr`
+    ///
+    /// Starts a measurement on the selected delay chain through `ProcessMonitorCtrl`
+    /// and reads back `ProcessMonitorData`, see `ProcessMonitorData::data`.
+    /// Only meaningful for chips having a process monitor, like the BM1397.
+    ///
+    /// ### Errors
+    ///
+    /// - I/O error
+    /// - No response
+    pub async fn read_process_monitor(
+        &mut self,
+        chip_addr: u8,
+        pm_sel: ProcessMonitorSelect,
+    ) -> Result<u8, P::Error> {
+        self.write_core_register(
+            Destination::Chip(chip_addr),
+            ProcessMonitorCtrl::ID,
+            ProcessMonitorCtrl::start(pm_sel),
+        )
+        .await?;
+        // let the measurement complete
+        self.delay.delay_ms(1).await;
+        self.read_core_register(chip_addr, ProcessMonitorData::ID)
+            .await?
+            .ok_or(Error::NoResponse {
+                chip_addr,
+                reg_addr: CoreRegisterValue::ADDR,
+            })
+    }
+
+    /// ## Run a frequency sweep self-test on the asic at `chip_addr`
+    ///
+    /// Writes `ctrl` to `FrequencySweepControl1`, waits for the sweep, then reads back
+    /// `GoldenNonceForSweepReturn`, `ReturnedGroupPatternStatus` and
+    /// `ReturnedSinglePatternStatus`. The control value starting the sweep and the meaning
+    /// of the pattern status are undocumented, so they are left to the caller.
+    /// Only meaningful for chips having the frequency sweep, like the BM1397.
+    ///
+    /// ### Errors
+    ///
+    /// - I/O error
+    /// - No response
+    pub async fn run_frequency_sweep(
+        &mut self,
+        chip_addr: u8,
+        ctrl: FrequencySweepControl1,
+    ) -> Result<FrequencySweepResult, P::Error> {
+        let cmd = Command::write_reg(
+            FrequencySweepControl1::ADDR,
+            ctrl.0,
+            Destination::Chip(chip_addr),
+        );
+        self.port.write_all(&cmd).await.map_err(Error::Io)?;
+        self.delay.delay_ms(100).await;
+        let mut regs = [0u32; 3];
+        for (reg, reg_addr) in regs.iter_mut().zip([
+            GoldenNonceForSweepReturn::ADDR,
+            ReturnedGroupPatternStatus::ADDR,
+            ReturnedSinglePatternStatus::ADDR,
+        ]) {
+            *reg = self
+                .read_chip_reg(chip_addr, reg_addr)
+                .await?
+                .ok_or(Error::NoResponse {
+                    chip_addr,
+                    reg_addr,
+                })?;
+        }
+        Ok(FrequencySweepResult {
+            golden_nonce: GoldenNonceForSweepReturn(regs[0]),
+            group_pattern_status: ReturnedGroupPatternStatus(regs[1]),
+            single_pattern_status: ReturnedSinglePatternStatus(regs[2]),
+        })
+    }
+
+    /// ## Run a frequency sweep self-test on the cores of the asic at `chip_addr`
+    ///
+    /// Enables the sweep frequency mode of all the asic cores with `clk_sel` in
+    /// `SweepClockCtrl`, then runs `run_frequency_sweep` with `ctrl`. `SweepClockCtrl` is
+    /// set back to its `0x00` reset value afterwards, even if the sweep failed.
+    ///
+    /// ### Errors
+    ///
+    /// - I/O error
+    /// - No response
+    pub async fn core_frequency_sweep(
+        &mut self,
+        chip_addr: u8,
+        clk_sel: u8,
+        ctrl: FrequencySweepControl1,
+    ) -> Result<FrequencySweepResult, P::Error> {
+        let dest = Destination::Chip(chip_addr);
+        let sweep_clk = SweepClockCtrl(0)
+            .enable_sweep_frequency_mode()
+            .set_clk_sel(clk_sel)
+            .0;
+        self.write_core_register(dest, SweepClockCtrl::ID, sweep_clk)
+            .await?;
+        let res = self.run_frequency_sweep(chip_addr, ctrl).await;
+        self.write_core_register(dest, SweepClockCtrl::ID, 0)
+            .await?;
+        res
+    }
+
+    /// ## Read the pattern test results of the asic at `chip_addr`
+    ///
+    /// Reads back `ReturnedGroupPatternStatus` and `ReturnedSinglePatternStatus`, left by a
+    /// previous self-test like `run_frequency_sweep`, and decodes them in a per-core pass map.
+    ///
+    /// ### Errors
+    ///
+    /// - I/O error
+    /// - No response
+    pub async fn pattern_test_results(
+        &mut self,
+        chip_addr: u8,
+    ) -> Result<PatternTestResults, P::Error> {
+        let mut regs = [0u32; 2];
+        for (reg, reg_addr) in regs.iter_mut().zip([
+            ReturnedGroupPatternStatus::ADDR,
+            ReturnedSinglePatternStatus::ADDR,
+        ]) {
+            *reg = self
+                .read_chip_reg(chip_addr, reg_addr)
+                .await?
+                .ok_or(Error::NoResponse {
+                    chip_addr,
+                    reg_addr,
+                })?;
+        }
+        Ok(PatternTestResults::new(
+            ReturnedGroupPatternStatus(regs[0]),
+            ReturnedSinglePatternStatus(regs[1]),
+        ))
+    }
+
+    /// ## Set the clock order of all the asics
+    ///
+    /// `order[x]` is the value of `CLK_SELx`. Chips without clock ordering ignore it.
+    ///
+    /// ### Errors
+    ///
+    /// - I/O error
+    /// - Another sequence in progress
+    pub async fn set_clock_order(&mut self, order: &[u8; 16]) -> Result<(), P::Error> {
+        self.check_no_sequence()?;
+        while let Some(step) = self.asic.set_clock_order_next(order) {
+            self.send(step).await?;
+        }
+        Ok(())
+    }
+
+    /// ## Verify the clock order of an asic
+    ///
+    /// Reads back `ClockOrderControl0` and `ClockOrderControl1` from the asic at
+    /// `chip_addr` and compares them against the programmed values.
+    ///
+    /// `ClockOrderStatus` is also read, but only logged: the layout of its bits is not
+    /// documented and no capture relates it to the programmed order, so it cannot be
+    /// decoded and compared against `ClockOrderControl0/1`.
+    ///
+    /// ### Errors
+    ///
+    /// - I/O error
+    /// - Chip address not in `chip_addresses`
+    /// - No response
+    /// - Clock order mismatch
+    pub async fn verify_clock_order(&mut self, chip_addr: u8) -> Result<(), P::Error> {
+        self.check_chip_addr(chip_addr)?;
+        for reg_addr in [ClockOrderControl0::ADDR, ClockOrderControl1::ADDR] {
+            let Some(expected) = self.asic.register(reg_addr) else {
+                continue;
+            };
+            let actual =
+                self.read_chip_reg(chip_addr, reg_addr)
+                    .await?
+                    .ok_or(Error::NoResponse {
+                        chip_addr,
+                        reg_addr,
+                    })?;
+            if actual != expected {
+                return Err(Error::ClockOrderMismatch {
+                    chip_addr,
+                    reg_addr,
+                    expected,
+                    actual,
+                });
+            }
+        }
+        if let Some(status) = self
+            .read_chip_reg(chip_addr, ClockOrderStatus::ADDR)
+            .await?
+        {
+            debug!("Asic @{}: {}", chip_addr, ClockOrderStatus(status));
+        }
+        Ok(())
+    }
+
+    /// Fails if an asic sequence was left uncompleted, e.g. by an I/O error.
+    fn check_no_sequence(&self) -> Result<(), P::Error> {
+        let step = self.asic.sequence_step();
+        if step.is_in_progress() {
+            return Err(Error::SequenceInProgress { step });
+        }
+        Ok(())
+    }
+
+    /// Fails if `freq` is above the asic maximum hash frequency.
+    fn check_hash_freq(&self, freq: HertzU64) -> Result<(), P::Error> {
+        let max = self.asic.max_hash_freq();
+        if freq > max {
+            return Err(Error::FrequencyTooHigh { freq, max });
+        }
+        Ok(())
+    }
+
+    /// ## Reset the cores of the asics at `dest`
+    ///
+    /// ### Errors
+    ///
+    /// - I/O error
+    /// - Another sequence in progress
+    pub async fn reset_cores(&mut self, dest: Destination) -> Result<(), P::Error> {
+        self.check_no_sequence()?;
+        while let Some(step) = self.asic.reset_core_next(dest) {
+            self.send(step).await?;
+        }
+        self.delay.delay_ms(100).await;
+        Ok(())
+    }
+
+    /// ## Reset the cores of all the asics, one asic at a time
+    ///
+    /// The asics missing during enumeration are skipped.
+    ///
+    /// ### Errors
+    ///
+    /// - I/O error
+    /// - Another sequence in progress
+    pub async fn reset_all_cores(&mut self) -> Result<(), P::Error> {
+        self.reset_all_cores_with_progress(&mut |_| {}).await
+    }
+
+    /// ## Same as `reset_all_cores`, calling `progress` with each asic address once reset
+    ///
+    /// This takes seconds on long chains, `progress` allows to report it.
+    pub async fn reset_all_cores_with_progress(
+        &mut self,
+        progress: &mut impl FnMut(u8),
+    ) -> Result<(), P::Error> {
+        self.check_no_sequence()?;
+        for chip_addr in self.chip_addresses() {
+            if !self.is_present(chip_addr) {
+                continue;
+            }
+            while let Some(step) = self.asic.reset_core_next(Destination::Chip(chip_addr)) {
+                self.send(step).await?;
+            }
+            progress(chip_addr);
+        }
+        self.delay.delay_ms(100).await;
+        Ok(())
+    }
+
+    /// ## Check the hash frequency of the chain asics after changing it
+    ///
+    /// When enabled, `set_hash_freq`, `set_hash_freq_staggered` and `set_hash_freq_direct`
+    /// read back `PLL0Parameter` from the first asic once done, and compare it against
+    /// the value the asic model expects. Disabled by default.
+    pub fn set_verify_freq(&mut self, verify_freq: bool) {
+        self.verify_freq = verify_freq;
+    }
+
+    /// Fails if the first asic `PLL0Parameter` differs from the model, when enabled.
+    async fn verify_hash_freq(&mut self) -> Result<(), P::Error> {
+        if !self.verify_freq {
+            return Ok(());
+        }
+        let Some(expected) = self.asic.register(PLL0Parameter::ADDR) else {
+            return Ok(());
+        };
+        let chip_addr = 0;
+        let actual = self
+            .read_chip_reg(chip_addr, PLL0Parameter::ADDR)
+            .await?
+            .ok_or(Error::NoResponse {
+                chip_addr,
+                reg_addr: PLL0Parameter::ADDR,
+            })?;
+        if actual != expected {
+            return Err(Error::FrequencyNotConfirmed { expected, actual });
+        }
+        Ok(())
+    }
+
+    /// ## Set the hash frequency of all asics
+    ///
+    /// The PLL is ramped up progressively from its current frequency. Asics set apart with
+    /// `set_chip_hash_freq` are first ramped back to the chain hash frequency, so that no
+    /// asic jumps by more than a ramp step.
+    ///
+    /// ### Errors
+    ///
+    /// - I/O error
+    /// - Another sequence in progress
+    /// - Frequency too high
+    /// - No response or frequency not confirmed, see `set_verify_freq`
+    pub async fn set_hash_freq(&mut self, freq: HertzU64) -> Result<(), P::Error> {
+        self.check_no_sequence()?;
+        self.check_hash_freq(freq)?;
+        let apart: Vec<u8, MAX_CHIP_FREQS> = self.chip_freqs.keys().copied().collect();
+        for chip_addr in apart {
+            self.set_chip_hash_freq(chip_addr, self.hash_freq).await?;
+        }
+        while let Some(step) = self.asic.set_hash_freq_next(freq) {
+            self.send(step).await?;
+        }
+        self.hash_freq = freq;
+        self.chip_freqs.clear();
+        self.delay.delay_ms(100).await;
+        self.verify_hash_freq().await
+    }
+
+    /// ## Set the hash frequency of all asics, one voltage domain at a time
+    ///
+    /// Same as `set_hash_freq`, but each voltage domain is fully ramped before the next
+    /// one starts, the steps of the asics of a domain being interleaved. Ramping the whole
+    /// chain at once makes every PLL step a board-wide current step, which can trip the
+    /// power supply of large hashboards. This takes `domain_cnt` times longer than
+    /// `set_hash_freq`.
+    ///
+    /// The domains are the ones given to `set_domain_sizes`, or `domain_cnt` domains of
+    /// `asic_cnt / domain_cnt` asics, the last one taking the remaining asics. A domain
+    /// can hold up to `MAX_CHIP_FREQS` asics.
+    ///
+    /// ### Errors
+    ///
+    /// - I/O error
+    /// - Another sequence in progress
+    /// - Frequency too high
+    /// - Voltage domain sizes not adding up to the asic count
+    /// - Too many asics in a voltage domain
+    /// - No response or frequency not confirmed, see `set_verify_freq`
+    pub async fn set_hash_freq_staggered(&mut self, freq: HertzU64) -> Result<(), P::Error> {
+        self.check_no_sequence()?;
+        self.check_hash_freq(freq)?;
+        let mut domain_sizes = self.domain_sizes.clone();
+        if domain_sizes.is_empty() {
+            let (domain_cnt, domain_asic_cnt) = self.uniform_domains();
+            for domain_i in 1..=domain_cnt {
+                let size = if domain_i == domain_cnt {
+                    self.asic_cnt
+                        .saturating_sub((domain_cnt - 1) * domain_asic_cnt)
+                } else {
+                    domain_asic_cnt
+                };
+                domain_sizes.push(size).map_err(|_| Error::BadDomainSizes)?;
+            }
+        }
+        if domain_sizes.iter().map(|&s| s as u16).sum::<u16>() != self.asic_cnt as u16 {
+            return Err(Error::BadDomainSizes);
+        }
+        if domain_sizes.iter().any(|&s| s as usize > MAX_CHIP_FREQS) {
+            return Err(Error::TooManyChipFreqs {
+                max: MAX_CHIP_FREQS,
+            });
+        }
+        // the model holds the chain at `freq`, only the asics of the domain being ramped
+        // are set apart
+        let start_freqs = self.chip_freqs.clone();
+        let start = self.hash_freq;
+        self.set_model_hash_freq(freq, Destination::All);
+        self.hash_freq = freq;
+        self.chip_freqs.clear();
+        let mut first = 0u8;
+        for size in domain_sizes {
+            let domain = first..first + size;
+            for asic_i in domain.clone() {
+                let chip_addr = self.chip_addr(asic_i);
+                let chip_start = start_freqs.get(&chip_addr).copied().unwrap_or(start);
+                self.set_model_hash_freq(chip_start, Destination::Chip(chip_addr));
+            }
+            // bit i set once the sequence of the i-th asic of the domain is over
+            let mut done = 0u32;
+            while done != (1 << size) - 1 {
+                let mut delay_ms = 0;
+                for (i, asic_i) in domain.clone().enumerate() {
+                    if done & (1 << i) != 0 {
+                        continue;
+                    }
+                    let dest = Destination::Chip(self.chip_addr(asic_i));
+                    match self.asic.set_hash_freq_next_dest(freq, dest) {
+                        Some(step) => {
+                            self.port.write_all(&step.cmd).await.map_err(Error::Io)?;
+                            delay_ms = delay_ms.max(step.delay_ms);
+                        }
+                        None => done |= 1 << i,
+                    }
+                }
+                self.delay.delay_ms(delay_ms).await;
+            }
+            first += size;
+        }
+        self.delay.delay_ms(100).await;
+        self.verify_hash_freq().await
+    }
+
+    /// ## Bring the asic model to `freq` without sending anything to the asics
+    fn set_model_hash_freq(&mut self, freq: HertzU64, dest: Destination) {
+        while self.asic.set_hash_freq_next_dest(freq, dest).is_some() {}
+    }
+
+    /// ## Park the asics
+    ///
+    /// Ramps the hash frequency of all asics down to `PARK_HASH_FREQ`, then disables their
+    /// hashing clocks in `HashClockCtrl`, to stop hashing without losing the chain
+    /// enumeration and configuration. Asics set apart with `set_chip_hash_freq` are parked
+    /// too. See `unpark` to resume hashing.
+    ///
+    /// ### Errors
+    ///
+    /// - I/O error
+    /// - Another sequence in progress
+    /// - No response or frequency not confirmed, see `set_verify_freq`
+    pub async fn park(&mut self) -> Result<(), P::Error> {
+        self.set_hash_freq(PARK_HASH_FREQ).await?;
+        self.set_hash_clock(false).await
+    }
+
+    /// ## Resume hashing on parked asics
+    ///
+    /// Enables the hashing clocks in `HashClockCtrl` again, then ramps the hash frequency
+    /// of all asics up to `freq`, see `park`.
+    ///
+    /// ### Errors
+    ///
+    /// - I/O error
+    /// - Another sequence in progress
+    /// - Frequency too high
+    /// - No response or frequency not confirmed, see `set_verify_freq`
+    pub async fn unpark(&mut self, freq: HertzU64) -> Result<(), P::Error> {
+        self.check_no_sequence()?;
+        self.check_hash_freq(freq)?;
+        self.set_hash_clock(true).await?;
+        self.set_hash_freq(freq).await
+    }
+
+    /// Enable or disable the hashing clocks of all asics, if the chip model has `HashClockCtrl`.
+    async fn set_hash_clock(&mut self, enable: bool) -> Result<(), P::Error> {
+        let Some(val) = self.asic.core_register(HashClockCtrl::ID) else {
+            return Ok(());
+        };
+        let mut hash_clk_ctrl = HashClockCtrl(val);
+        if enable {
+            hash_clk_ctrl.enable();
+        } else {
+            hash_clk_ctrl.disable();
+        }
+        self.write_core_register(Destination::All, HashClockCtrl::ID, hash_clk_ctrl.0)
+            .await
+    }
+
+    /// ## Set the hash frequency of a single asic
+    ///
+    /// Same ramp as `set_hash_freq`, but only the asic at `chip_addr` is written,
+    /// e.g. to down-clock a hot chip.
+    ///
+    /// ### Errors
+    ///
+    /// - I/O error
+    /// - Another sequence in progress
+    /// - Frequency too high
+    /// - Chip address not in `chip_addresses`
+    /// - Too many chip frequencies
+    pub async fn set_chip_hash_freq(
+        &mut self,
+        chip_addr: u8,
+        freq: HertzU64,
+    ) -> Result<(), P::Error> {
+        self.check_no_sequence()?;
+        self.check_hash_freq(freq)?;
+        self.check_chip_addr(chip_addr)?;
+        let dest = Destination::Chip(chip_addr);
+        if freq != self.hash_freq
+            && !self.chip_freqs.contains_key(&chip_addr)
+            && self.chip_freqs.len() == MAX_CHIP_FREQS
+        {
+            return Err(Error::TooManyChipFreqs {
+                max: MAX_CHIP_FREQS,
+            });
+        }
+        while let Some(step) = self.asic.set_hash_freq_next_dest(freq, dest) {
+            self.send(step).await?;
+        }
+        if freq == self.hash_freq {
+            self.chip_freqs.remove(&chip_addr);
+        } else {
+            self.chip_freqs.insert(chip_addr, freq).unwrap();
+        }
+        self.delay.delay_ms(100).await;
+        Ok(())
+    }
+
+    /// ## Get the hash frequency the asic at `chip_addr` was set to
+    ///
+    /// This is the chain hash frequency, unless the asic was set apart using
+    /// `set_chip_hash_freq`.
+    pub fn chip_hash_freq(&self, chip_addr: u8) -> HertzU64 {
+        self.chip_freqs
+            .get(&chip_addr)
+            .copied()
+            .unwrap_or(self.hash_freq)
+    }
+
+    /// ## Get the theoretical Hashrate of the whole chain in GH/s
+    ///
+    /// Sums the hashrate of each asic at its own hash frequency, see `chip_hash_freq`.
+    pub fn total_theoretical_hashrate_ghs(&self) -> f32 {
+        let freq_sum: u64 = self
+            .chip_addresses()
+            .map(|chip_addr| self.chip_hash_freq(chip_addr).raw())
+            .sum();
+        freq_sum as f32 * self.asic.small_core_count() as f32 / 1_000_000_000.0
+    }
+
+    /// ## Set the hash frequency without ramping
+    ///
+    /// Writes the final PLL parameter at once, followed by a single settle delay.
+    ///
+    /// This skips the progressive ramp of `set_hash_freq`, which protects the
+    /// hashboard from current and thermal steps: only use it for bench testing
+    /// on an already warm board, never in production.
+    ///
+    /// ### Errors
+    ///
+    /// - I/O error
+    /// - Another sequence in progress
+    /// - Frequency too high
+    /// - No response or frequency not confirmed, see `set_verify_freq`
+    pub async fn set_hash_freq_direct(&mut self, freq: HertzU64) -> Result<(), P::Error> {
+        self.check_no_sequence()?;
+        self.check_hash_freq(freq)?;
+        while let Some(step) = self.asic.set_hash_freq_direct_next(freq) {
+            self.send(step).await?;
+        }
+        self.hash_freq = freq;
+        self.chip_freqs.clear();
+        self.delay.delay_ms(100).await;
+        self.verify_hash_freq().await
+    }
+
+    /// ## Set the nonce returned timeout of all the asics
+    ///
+    /// The asics flush the nonces they found after `timeout`, the raw Timeout field of
+    /// `NonceReturnedTimeout`. It is counted in ticks of the hash clock, so the same
+    /// value gives a shorter timeout at a higher hash frequency. The length of a tick is
+    /// not documented, so no duration conversion is provided.
+    ///
+    /// Keep it well below the asic `rolling_duration`, the time to exhaust the space of
+    /// a job: jobs are then sent at least every `rolling_duration`, and a longer
+    /// timeout would return the nonces of a job after the next ones were sent,
+    /// leaving `match_job` with a shorter history to find them back.
+    ///
+    /// ### Errors
+    ///
+    /// - I/O error
+    /// - Another sequence in progress
+    pub async fn set_nonce_timeout(&mut self, timeout: u16) -> Result<(), P::Error> {
+        self.check_no_sequence()?;
+        let Some(reg) = self.asic.register(NonceReturnedTimeout::ADDR) else {
+            return Ok(());
+        };
+        let reg = NonceReturnedTimeout(reg).set_timeout(timeout).0;
+        let step = self
+            .asic
+            .write_reg_next(NonceReturnedTimeout::ADDR, reg, Destination::All);
+        self.send(step).await
+    }
+
+    /// ## Set the strength of one IO driver of the asics at `dest`
+    ///
+    /// The IO drivers are configured when setting the baudrate, this allows to tune one
+    /// of them, typically CLKO or RO on long chains, without going through it again.
+    /// The other drivers keep their current strength. Does nothing if the asic has no
+    /// IO Driver Strenght Configuration register.
+    ///
+    /// ### Errors
+    ///
+    /// - I/O error
+    /// - Another sequence in progress
+    pub async fn set_driver_strength(
+        &mut self,
+        sel: DriverSelect,
+        strength: u8,
+        dest: Destination,
+    ) -> Result<(), P::Error> {
+        self.check_no_sequence()?;
+        let Some(reg) = self.asic.register(IoDriverStrenghtConfiguration::ADDR) else {
+            return Ok(());
+        };
+        let reg = IoDriverStrenghtConfiguration(reg)
+            .set_strenght(sel, strength)
+            .0;
+        let step = self
+            .asic
+            .write_reg_next(IoDriverStrenghtConfiguration::ADDR, reg, dest);
+        self.send(step).await
+    }
+
+    /// ## Enable Version Rolling with the given mask
+    ///
+    /// The asics answer with longer frames once Version Rolling is enabled, so the bytes
+    /// received before the change are dropped rather than decoded with the wrong frame
+    /// size, see `flush_rx`. Does nothing if the asic has no Version Rolling.
+    ///
+    /// ### Errors
+    ///
+    /// - I/O error
+    /// - Another sequence in progress
+    pub async fn set_version_rolling(&mut self, mask: u32) -> Result<(), P::Error> {
+        self.check_no_sequence()?;
+        if self.asic.has_version_rolling() {
+            while let Some(step) = self.asic.set_version_rolling_next(mask) {
+                self.send(step).await?;
+            }
+            self.delay.delay_ms(100).await;
+            self.flush_rx().await?;
+            self.decoder.set_version_rolling(true);
+        }
+        Ok(())
+    }
+
+    /// ## Change the Version Rolling mask of all the asics
+    ///
+    /// Unlike `set_version_rolling`, this does not enable Version Rolling, so the mask
+    /// can be narrowed or widened at any time.
+    ///
+    /// ### Errors
+    ///
+    /// - I/O error
+    /// - Another sequence in progress
+    pub async fn set_version_mask(&mut self, mask: u32) -> Result<(), P::Error> {
+        self.check_no_sequence()?;
+        while let Some(step) = self.asic.set_version_mask_next(mask) {
+            self.send(step).await?;
+        }
+        Ok(())
+    }
+
+    /// ## Override the Hash Counting Number of all the asics
+    ///
+    /// `set_version_rolling` programs the value of the board the asic model was captured
+    /// from, see `HashCountingNumber`, so this must be called after it to take effect.
+    /// Does nothing if the asic has no Hash Counting Number register.
+    ///
+    /// ### Errors
+    ///
+    /// - I/O error
+    /// - Another sequence in progress
+    pub async fn set_hash_counting_number(&mut self, hcn: u32) -> Result<(), P::Error> {
+        self.check_no_sequence()?;
+        let Some(reg) = self.asic.register(HashCountingNumber::ADDR) else {
+            return Ok(());
+        };
+        let reg = HashCountingNumber(reg).set_hcn(hcn).0;
+        let step = self
+            .asic
+            .write_reg_next(HashCountingNumber::ADDR, reg, Destination::All);
+        self.send(step).await
+    }
+}
+
+impl<A: Asic, P, D, R> core::fmt::Display for Chain<A, P, D, R> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Chain")
+            .field("chip_id", &format_args!("{:#06x}", self.asic.chip_id()))
+            .field("asic_cnt", &self.asic_cnt)
+            .field("asic_addr_interval", &self.asic_addr_interval)
+            .field("domain_cnt", &self.domain_cnt)
+            .finish()
+    }
+}
+
+#[cfg(feature = "defmt-03")]
+impl<A: Asic, P, D, R> defmt::Format for Chain<A, P, D, R> {
+    fn format(&self, fmt: defmt::Formatter) {
+        defmt::write!(
+            fmt,
+            "Chain {{ chip_id: {=u16:#06x}, asic_cnt: {}, asic_addr_interval: {}, domain_cnt: {} }}",
+            self.asic.chip_id(),
+            self.asic_cnt,
+            self.asic_addr_interval,
+            self.domain_cnt,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::*;
+    use bm1366::BM1366;
+    use bm1397::BM1397;
+    use bm13xx_asic::{
+        core_register::HashClockCtrl,
+        register::{
+            CoreRegisterControl, IoDriverStrenghtConfiguration, MiscControlV2, PLL0Divider,
+            PLL0Parameter, UARTRelay, VersionRolling,
+        },
+        SequenceStep,
+    };
+    use bm13xx_protocol::crc::{crc16, crc5};
+    use core::convert::Infallible;
+    use std::{collections::VecDeque, vec::Vec};
+
+    /// BM1366 ChipIdentification response, as received during enumeration
+    const BM1366_CHIP_IDENT: [u8; 9] = [0xAA, 0x55, 0x13, 0x66, 0x00, 0x00, 0x00, 0x00, 0x0C];
+
+    /// Register read response from the asic at `chip_addr`
+    fn reg_resp(chip_addr: u8, reg_addr: u8, reg_value: u32) -> [u8; 9] {
+        let mut resp = [0xAA, 0x55, 0, 0, 0, 0, chip_addr, reg_addr, 0];
+        resp[2..6].copy_from_slice(&reg_value.to_be_bytes());
+        resp[8] = (0..0x20)
+            .find(|&c| {
+                resp[8] = c;
+                crc5(&resp[2..9]) == 0
+            })
+            .unwrap();
+        resp
+    }
+
+    /// Nonce response for the raw `job_id` byte, without Version Rolling
+    fn job_resp(nonce: u32, job_id: u8) -> [u8; 9] {
+        let mut resp = [0xAA, 0x55, 0, 0, 0, 0, 0, job_id, 0];
+        resp[2..6].copy_from_slice(&nonce.to_be_bytes());
+        resp[8] = (0x80..0xA0)
+            .find(|&c| {
+                resp[8] = c;
+                crc5(&resp[2..9]) == 0
+            })
+            .unwrap();
+        resp
+    }
+
+    /// BM1366 ChipIdentification response from the asic at `chip_addr`
+    fn bm1366_chip_ident(chip_addr: u8) -> [u8; 9] {
+        reg_resp(chip_addr, ChipIdentification::ADDR, 0x1366_0000)
+    }
+
+    /// Serial port recording every written frame and replaying scripted RX bytes
+    #[derive(Debug, Default, PartialEq)]
+    struct MockPort {
+        rx: VecDeque<u8>,
+        tx: Vec<Vec<u8>>,
+        baudrate: u32,
+        /// Chip addresses answering to a ChipIdentification read
+        idents: Vec<u8>,
+        /// Highest baudrate the chips answer at, unlimited if 0
+        max_baudrate: u32,
+        /// Bytes given to the next ChipIdentification read to all the chips
+        enum_rx: VecDeque<u8>,
+        /// Reads never complete, like a blocking serial interface with a dead chain
+        hang: bool,
+        /// Reads of an empty rx never complete, like an asynchronous serial interface
+        pend_empty: bool,
+        /// Last chip address of a chain wired backward, whose chips answer in reverse order
+        reversed: Option<u8>,
+    }
+
+    impl embedded_io_async::ErrorType for MockPort {
+        type Error = Infallible;
+    }
+
+    impl Read for MockPort {
+        async fn read(&mut self, buf: &mut [u8]) -> core::result::Result<usize, Infallible> {
+            if self.hang || (self.pend_empty && self.rx.is_empty()) {
+                core::future::pending::<()>().await;
+            }
+            let n = buf.len().min(self.rx.len());
+            for b in buf[..n].iter_mut() {
+                *b = self.rx.pop_front().unwrap();
+            }
+            Ok(n)
+        }
+    }
+
+    impl Write for MockPort {
+        async fn write(&mut self, buf: &[u8]) -> core::result::Result<usize, Infallible> {
+            if buf.len() == 7
+                && buf[2] == 0x42
+                && buf[5] == ChipIdentification::ADDR
+                && self.idents.contains(&buf[4])
+                && (self.max_baudrate == 0 || self.baudrate <= self.max_baudrate)
+            {
+                let chip_addr = self.reversed.map_or(buf[4], |last| last - buf[4]);
+                self.rx.extend(bm1366_chip_ident(chip_addr));
+            }
+            if buf == Command::read_reg(ChipIdentification::ADDR, Destination::All) {
+                self.rx.extend(self.enum_rx.drain(..));
+            }
+            self.tx.push(buf.to_vec());
+            Ok(buf.len())
+        }
+    }
+
+    impl Baud for MockPort {
+        fn set_baudrate(&mut self, baudrate: u32) {
+            self.baudrate = baudrate;
+        }
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct MockDelay;
+
+    impl DelayNs for MockDelay {
+        async fn delay_ns(&mut self, _ns: u32) {}
+    }
+
+    fn chain_bm1366(asic_cnt: u8, domain_cnt: u8) -> Chain<BM1366, MockPort, MockDelay> {
+        let mut port = MockPort::default();
+        for _ in 0..asic_cnt {
+            port.rx.extend(BM1366_CHIP_IDENT);
+        }
+        Chain::new(asic_cnt, BM1366::default(), domain_cnt, port, MockDelay)
+    }
+
+    /// Destinations of the chip-specific writes to a given register
+    fn chip_writes(chain: &Chain<BM1366, MockPort, MockDelay>, reg_addr: u8) -> Vec<u8> {
+        chain
+            .port
+            .tx
+            .iter()
+            .filter(|f| f.len() == 11 && f[2] == 0x41 && f[5] == reg_addr)
+            .map(|f| f[4])
+            .collect()
+    }
+
+    /// Follow the hash frequency of 4 asics through the PLL writes, checking that none of
+    /// them steps by more than a ramp step, up to the PLL resolution.
+    fn ramp_hash_freqs(
+        chain: &Chain<BM1366, MockPort, MockDelay>,
+        mut freqs: [HertzU64; 4],
+    ) -> [HertzU64; 4] {
+        let pll_freq = |parameter| {
+            let mut bm1366 = BM1366::default();
+            bm1366.plls[bm1366::BM1366_PLL_ID_HASH].set_parameter(parameter);
+            bm1366.hash_freq()
+        };
+        for f in chain.port.tx.iter().filter(|f| f[5] == PLL0Parameter::ADDR) {
+            let freq = pll_freq(u32::from_be_bytes(f[6..10].try_into().unwrap()));
+            for (asic_i, chip_freq) in freqs.iter_mut().enumerate() {
+                if f[2] == 0x51 || f[4] == asic_i as u8 * 64 {
+                    let step = freq.raw().abs_diff(chip_freq.raw());
+                    assert!(step < 7_250_000, "asic {asic_i}: {chip_freq} -> {freq}");
+                    *chip_freq = freq;
+                }
+            }
+        }
+        freqs
+    }
+
+    #[tokio::test]
+    async fn asic_addr_interval_override() {
+        let mut chain = chain_bm1366(2, 1);
+        chain.set_asic_addr_interval(32);
+        chain.enumerate().await.unwrap();
+        assert_eq!(chain.asic_addr_interval, 32);
+        assert!(chain.port.tx.contains(&Command::set_chip_addr(32).to_vec()));
+        chain.port.tx.clear();
+        chain.port.rx.extend(BM1366_CHIP_IDENT);
+        chain.set_baudrate(1_000_000).await.unwrap();
+        assert_eq!(chip_writes(&chain, UARTRelay::ADDR), [0, 32]);
+        assert_eq!(chain.port.baudrate, 1_000_000);
+    }
+
+    #[test]
+    fn display() {
+        let chain = chain_bm1366(2, 1);
+        assert_eq!(chain.asic_name(), "BM1366");
+        assert_eq!(
+            std::format!("{chain}"),
+            "Chain { chip_id: 0x1366, asic_cnt: 2, asic_addr_interval: 0, domain_cnt: 1 }"
+        );
+    }
+
+    #[tokio::test]
+    async fn job_id_wraps_around() {
+        let mut chain = chain_bm1366(1, 1);
+        chain.initialized = true;
+        for i in 1..32u32 {
+            let job_id = chain.send_job_id(0, [0; 32], [0; 32], 0, 0).await.unwrap();
+            assert_eq!(job_id as u32, i * 8);
+        }
+        assert_eq!(chain.current_job_id(), 248);
+        assert_eq!(
+            chain.send_job_id(0, [0; 32], [0; 32], 0, 0).await.unwrap(),
+            0
+        );
+        assert_eq!(
+            chain.send_job_id(0, [0; 32], [0; 32], 0, 0).await.unwrap(),
+            8
+        );
+        assert_eq!(chain.port.tx.last().unwrap()[4], 8);
+        chain.reset_job_id();
+        assert_eq!(chain.current_job_id(), 0);
+        chain.set_job_id_stride(16);
+        assert_eq!(
+            chain.send_job_id(0, [0; 32], [0; 32], 0, 0).await.unwrap(),
+            16
+        );
+    }
+
+    #[tokio::test]
+    async fn job_id_no_aliasing() {
+        let mut chain = chain_bm1366(1, 1);
+        chain.initialized = true;
+        assert_eq!(chain.job_id_stride(), 8);
+        assert_eq!(chain.max_outstanding_jobs(), 32);
+        for (stride, expected) in [(1, 8), (0, 8), (12, 8), (16, 16), (250, 248)] {
+            chain.set_job_id_stride(stride);
+            assert_eq!(chain.job_id_stride(), expected);
+            chain.reset_job_id();
+            let window = chain.max_outstanding_jobs();
+            let mut returned = Vec::new();
+            for _ in 0..window {
+                let job_id = chain.send_job_id(0, [0; 32], [0; 32], 0, 0).await.unwrap();
+                // raw job_id byte of a nonce found by the last Small Core
+                let raw = job_id | 7;
+                assert_eq!(chain.match_job((raw >> 3) as usize), Some(job_id));
+                returned.push(raw >> 3);
+            }
+            returned.sort_unstable();
+            returned.dedup();
+            assert_eq!(returned.len(), window);
+            // the next job reuses the first job_id
+            let first = chain.send_job_id(0, [0; 32], [0; 32], 0, 0).await.unwrap();
+            assert_eq!(first, expected);
+        }
+        assert_eq!(chain.max_outstanding_jobs(), 32);
+
+        // 2 Small Core ID bits, so only 7 bits of job_id come back
+        let mut chain = Chain::new(1, BM1397::default(), 1, MockPort::default(), MockDelay);
+        chain.initialized = true;
+        assert_eq!(chain.job_id_stride(), 4);
+        assert_eq!(chain.max_outstanding_jobs(), 32);
+        let mut returned = Vec::new();
+        for _ in 0..chain.max_outstanding_jobs() {
+            let job_id = chain.send_job_id(0, [0; 32], [0; 32], 0, 0).await.unwrap();
+            assert!(job_id < 128);
+            let raw = job_id | 3;
+            assert_eq!(chain.match_job((raw >> 2) as usize), Some(job_id));
+            returned.push(raw >> 2);
+        }
+        returned.sort_unstable();
+        returned.dedup();
+        assert_eq!(returned.len(), 32);
+        assert_eq!(chain.current_job_id(), 0);
+        chain.set_job_id_stride(128);
+        assert_eq!(chain.max_outstanding_jobs(), 1);
+    }
+
+    #[tokio::test]
+    async fn match_job() {
+        let mut chain = chain_bm1366(1, 1);
+        chain.initialized = true;
+        let mut sent = Vec::<u8>::new();
+        for _ in 0..10 {
+            sent.push(chain.send_job_id(0, [0; 32], [0; 32], 0, 0).await.unwrap());
+        }
+        // raw job_id byte of a nonce found by Small Core 5 on the last job
+        let raw = sent[9] | 5;
+        assert_eq!(chain.match_job((raw >> 3) as usize), Some(sent[9]));
+        assert_eq!(chain.match_job((sent[2] >> 3) as usize), Some(sent[2]));
+        // too old
+        assert_eq!(chain.match_job((sent[1] >> 3) as usize), None);
+        chain.reset_job_id();
+        assert_eq!(chain.match_job((sent[9] >> 3) as usize), None);
+        // nor after the asics were reset
+        let job_id = chain.send_job_id(0, [0; 32], [0; 32], 0, 0).await.unwrap();
+        chain.reset().await.unwrap();
+        assert_eq!(chain.current_job_id(), 0);
+        assert_eq!(chain.match_job((job_id >> 3) as usize), None);
+    }
+
+    #[tokio::test]
+    async fn run() {
+        let mut chain = chain_bm1366(1, 1);
+        chain.initialized = true;
+        chain.port.rx.clear();
+        // found by Small Core 2 on the first job, then one for a job never sent
+        chain.port.rx.extend(job_resp(0x1234_5678, 8 | 2));
+        chain.port.rx.extend(job_resp(0x9abc_def0, 0x80));
+        let jobs: Vec<Job> = (0..3)
+            .map(|i| Job {
+                n_time: i,
+                ..Default::default()
+            })
+            .collect();
+        let mut solved = Vec::new();
+        chain
+            .run(&mut jobs.clone().into_iter(), &mut |s| solved.push(s))
+            .await
+            .unwrap();
+        assert_eq!(chain.port.tx.len(), 3);
+        assert_eq!(chain.port.tx[2], jobs[2].header(24).to_vec());
+        assert_eq!(
+            solved,
+            [SolvedJob {
+                job: jobs[0].clone(),
+                job_id: 8,
+                nonce: 0x1234_5678,
+                version: 0,
+            }]
+        );
+
+        // a chain returning nonces without pause still gets new jobs
+        chain.port.tx.clear();
+        chain.baudrate = 3_000_000;
+        let refresh = chain.asic.rolling_duration() * 3 / 4;
+        let frame_time = Duration::from_micros(90_000_000 / 3_000_000);
+        let frames = (refresh.as_micros() / frame_time.as_micros()) as usize + 1;
+        for _ in 0..3 * frames {
+            chain.port.rx.extend(job_resp(0x1234_5678, 32));
+        }
+        let mut nonces = 0;
+        chain
+            .run(&mut jobs[..2].iter().cloned(), &mut |_| nonces += 1)
+            .await
+            .unwrap();
+        assert_eq!(chain.port.tx.len(), 2);
+        assert_eq!(nonces, 2 * frames);
+    }
+
+    #[tokio::test]
+    async fn asic_addr_interval_derived() {
+        let mut chain = chain_bm1366(2, 1);
+        chain.enumerate().await.unwrap();
+        assert_eq!(chain.asic_addr_interval, 128);
+    }
+
+    #[tokio::test]
+    async fn mixed_domain_sizes() {
+        let mut chain = chain_bm1366(13, 4);
+        assert_eq!(
+            chain.set_domain_sizes(&[3, 3, 3]),
+            Err(Error::BadDomainSizes)
+        );
+        chain.set_domain_sizes(&[3, 3, 3, 4]).unwrap();
+        chain.enumerate().await.unwrap();
+        assert_eq!(chain.asic_addr_interval, 19);
+        chain.port.tx.clear();
+        chain.port.rx.extend(BM1366_CHIP_IDENT);
+        chain.set_baudrate(1_000_000).await.unwrap();
+        // last chip of each domain, in decreasing address order
+        assert_eq!(
+            chip_writes(&chain, IoDriverStrenghtConfiguration::ADDR),
+            [228, 152, 95, 38]
+        );
+        // first and last chip of each domain, alternating
+        assert_eq!(
+            chip_writes(&chain, UARTRelay::ADDR),
+            [171, 228, 114, 152, 57, 95, 0, 38]
+        );
+        let gap_cnts: Vec<u16> = chain
+            .port
+            .tx
+            .iter()
+            .filter(|f| f[2] == 0x41 && f[5] == UARTRelay::ADDR)
+            .map(|f| UARTRelay(u32::from_be_bytes([f[6], f[7], f[8], f[9]])).gap_cnt())
+            .collect();
+        assert_eq!(gap_cnts, [18, 18, 21, 21, 24, 24, 27, 27]);
+
+        // the BM1397 baudrate sequence does not depend on the voltage domains
+        let mut chain = Chain::new(13, BM1397::default(), 4, MockPort::default(), MockDelay);
+        chain.set_domain_sizes(&[3, 3, 3, 4]).unwrap();
+        assert!(chain
+            .asic
+            .set_baudrate_next_domains(1_000_000, &[3, 3, 3, 4], 19)
+            .is_some());
+    }
+
+    /// NRST pin recording every level it is driven to
+    #[derive(Debug, Default, PartialEq)]
+    struct MockPin {
+        levels: Vec<bool>,
+    }
+
+    impl ErrorType for MockPin {
+        type Error = Infallible;
+    }
+
+    impl OutputPin for MockPin {
+        fn set_low(&mut self) -> core::result::Result<(), Infallible> {
+            self.levels.push(false);
+            Ok(())
+        }
+
+        fn set_high(&mut self) -> core::result::Result<(), Infallible> {
+            self.levels.push(true);
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn enumerate_with_mode() {
+        const IDENT: u32 = 0x1366_1800; // pre-S19jPro like ChipIdentification
+        let core_resets = |chain: &Chain<BM1366, MockPort, MockDelay>| {
+            chain
+                .port
+                .tx
+                .iter()
+                .filter(|f| f[2] == 0x51 && f[5] == MiscControlV2::ADDR)
+                .count()
+        };
+        let inactives = |chain: &Chain<BM1366, MockPort, MockDelay>| {
+            chain
+                .port
+                .tx
+                .iter()
+                .filter(|f| **f == Command::chain_inactive())
+                .count()
+        };
+
+        let mut chain = chain_bm1366(1, 1);
+        chain.port.rx.clear();
+        chain.port.enum_rx.extend(reg_resp(0, 0, IDENT));
+        chain
+            .enumerate_with_mode(EnumerateMode::Cold)
+            .await
+            .unwrap();
+        assert_eq!(
+            chain.port.tx[0],
+            Command::read_reg(ChipIdentification::ADDR, Destination::All)
+        );
+        assert_eq!(core_resets(&chain), 0);
+        assert_eq!(inactives(&chain), 3);
+        assert_eq!(chain.port.tx.last().unwrap(), &Command::set_chip_addr(0));
+
+        // a running chain still sends nonces
+        let mut chain = chain_bm1366(1, 1);
+        chain.port.rx.clear();
+        chain.port.rx.extend(job_resp(0x1234_5678, 8));
+        chain.port.enum_rx.extend(reg_resp(0, 0, IDENT));
+        chain
+            .enumerate_with_mode(EnumerateMode::Warm)
+            .await
+            .unwrap();
+        assert_eq!(
+            chain.port.tx[0],
+            Command::read_reg(ChipIdentification::ADDR, Destination::All)
+        );
+        assert!(core_resets(&chain) > 0);
+        assert_eq!(inactives(&chain), 3);
+        assert_eq!(chain.port.tx.last().unwrap(), &Command::set_chip_addr(0));
+
+        let mut chain = chain_bm1366(1, 1);
+        chain.port.rx.clear();
+        chain.port.rx.extend(job_resp(0x1234_5678, 8));
+        chain.port.enum_rx.extend(reg_resp(0, 0, IDENT));
+        assert!(matches!(
+            chain.enumerate().await,
+            Err(Error::UnexpectedResponse { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn check_wiring() {
+        let mut chain = chain_bm1366(4, 1);
+        chain.port.idents = std::vec![0, 64, 128, 192];
+        chain.set_check_wiring(true);
+        chain.enumerate().await.unwrap();
+
+        chain.port.rx.extend(BM1366_CHIP_IDENT.repeat(4));
+        chain.port.reversed = Some(192);
+        assert_eq!(
+            chain.enumerate().await,
+            Err(Error::ChainWiringSuspect {
+                last_chip_addr: 192
+            })
+        );
+
+        chain.port.rx.extend(BM1366_CHIP_IDENT.repeat(4));
+        chain.set_check_wiring(false);
+        chain.enumerate().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn power_cycle() {
+        let mut port = MockPort::default();
+        port.enum_rx.extend(BM1366_CHIP_IDENT);
+        port.baudrate = 1_000_000;
+        let mut chain =
+            Chain::new_with_reset(1, BM1366::default(), 1, port, MockDelay, MockPin::default());
+        chain.reset().await.unwrap();
+        assert_eq!(chain.reset.levels, [false]);
+        chain.power_cycle(256).await.unwrap();
+        assert_eq!(chain.reset.levels, [false, false, true]);
+        assert_eq!(chain.port.baudrate, 115_200);
+        assert_eq!(
+            chain.port.tx[0],
+            Command::read_reg(ChipIdentification::ADDR, Destination::All)
+        );
+        assert!(chain.port.tx.len() > 1);
+    }
+
+    #[tokio::test]
+    async fn assign_addresses() {
+        let mut chain = chain_bm1366(3, 1);
+        chain.enumerate().await.unwrap();
+        chain.port.tx.clear();
+        chain.assign_addresses(1).await.unwrap();
+        assert_eq!(chain.asic_addr_interval, 1);
+        assert_eq!(
+            chain.port.tx,
+            [
+                Command::chain_inactive().to_vec(),
+                Command::set_chip_addr(0).to_vec(),
+                Command::set_chip_addr(1).to_vec(),
+                Command::set_chip_addr(2).to_vec(),
+            ]
+        );
+        assert_eq!(
+            chain.assign_addresses(128).await,
+            Err(Error::BadAddrInterval { interval: 128 })
+        );
+        assert_eq!(
+            chain.assign_addresses(0).await,
+            Err(Error::BadAddrInterval { interval: 0 })
+        );
+        assert_eq!(chain.asic_addr_interval, 1);
+    }
+
+    #[tokio::test]
+    async fn reenumerate() {
+        let mut chain = chain_bm1366(4, 2);
+        chain.enumerate().await.unwrap();
+        chain.set_domain_sizes(&[2, 2]).unwrap();
+        // leave a sequence in progress
+        chain.asic.set_hash_freq_next(HertzU64::MHz(100)).unwrap();
+        // two more asics were plugged
+        chain.port.enum_rx.extend(BM1366_CHIP_IDENT.repeat(6));
+        chain.port.tx.clear();
+        assert_eq!(chain.reenumerate().await, Ok(6));
+        assert_eq!(chain.asic_cnt, 6);
+        assert_eq!(chain.asic_addr_interval, 42);
+        assert_eq!(chain.asic.sequence_step(), SequenceStep::None);
+        assert!(chain.domain_sizes.is_empty());
+        assert!(chain
+            .port
+            .tx
+            .contains(&Command::set_chip_addr(210).to_vec()));
+        // then three were removed
+        chain.port.enum_rx.extend(BM1366_CHIP_IDENT.repeat(3));
+        assert_eq!(chain.reenumerate().await, Ok(3));
+        assert_eq!(chain.asic_cnt, 3);
+        assert_eq!(chain.asic_addr_interval, 85);
+        assert_eq!(chain.chip_addresses().collect::<Vec<_>>(), [0, 85, 170]);
+        // and all of them
+        assert_eq!(
+            chain.reenumerate().await,
+            Err(Error::UnexpectedAsicCount {
+                expected_asic_cnt: 3,
+                actual_asic_cnt: 0
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn send_all_single_write() {
+        let mut chain = chain_bm1366(1, 1);
+        let cmds: heapless::Vec<[u8; 11], 4> = Command::write_regs(
+            &[(0x80, 0), (0x18, 0x7A31), (0x3C, 0x8000_8540)],
+            Destination::All,
+        );
+        chain.send_all(&cmds).await.unwrap();
+        assert_eq!(chain.port.tx.len(), 1);
+        assert_eq!(chain.port.tx[0].len(), 33);
+        for (frame, cmd) in chain.port.tx[0].chunks(11).zip(&cmds) {
+            assert_eq!(frame, cmd);
+        }
+        chain.port.tx.clear();
+        let cmds = [Command::write_reg(0x80, 0, Destination::All); MAX_BATCH_CMDS + 1];
+        chain.send_all(&cmds).await.unwrap();
+        assert_eq!(chain.port.tx.len(), 2);
+        assert_eq!(chain.port.tx[1].len(), 11);
+    }
+
+    #[tokio::test]
+    async fn set_baudrate_single_asic() {
+        for domain_cnt in [0, 1, 4] {
+            let mut chain = chain_bm1366(1, domain_cnt);
+            chain.enumerate().await.unwrap();
+            chain.port.idents = std::vec![0];
+            chain.set_baudrate(1_000_000).await.unwrap();
+            assert_eq!(chain.port.baudrate, 1_000_000);
+            chain
+                .set_hash_freq_staggered(HertzU64::MHz(100))
+                .await
+                .unwrap();
+            assert!(chip_writes(&chain, PLL0Parameter::ADDR)
+                .iter()
+                .all(|&a| a == 0));
+        }
+    }
+
+    #[tokio::test]
+    async fn set_baudrate_reverts_on_failure() {
+        let mut chain = chain_bm1366(1, 1);
+        chain.enumerate().await.unwrap();
+        chain.port.baudrate = DEFAULT_BAUDRATE;
+        // nobody answers at the new baudrate
+        assert_eq!(
+            chain.set_baudrate(1_000_000).await,
+            Err(Error::BaudrateChangeFailed {
+                baudrate: 1_000_000
+            })
+        );
+        assert_eq!(chain.port.baudrate, DEFAULT_BAUDRATE);
+        let ident_read = Command::read_reg(ChipIdentification::ADDR, Destination::Chip(0));
+        let reads = chain.port.tx.iter().filter(|f| **f == ident_read).count();
+        assert_eq!(reads, BAUDRATE_CHECK_RETRIES);
+        // same on an asynchronous serial interface, without I/O timeout
+        chain.port.pend_empty = true;
+        assert!(!chain.ping(0).await.unwrap());
+        assert_eq!(
+            chain.set_baudrate(1_000_000).await,
+            Err(Error::BaudrateChangeFailed {
+                baudrate: 1_000_000
+            })
+        );
+        assert_eq!(chain.port.baudrate, DEFAULT_BAUDRATE);
+        chain.port.pend_empty = false;
+        chain.port.tx.clear();
+        // answer on the second attempt
+        chain.port.rx.extend([0u8; 9]);
+        chain.port.rx.extend(BM1366_CHIP_IDENT);
+        chain.set_baudrate(1_000_000).await.unwrap();
+        assert_eq!(chain.port.baudrate, 1_000_000);
+        assert_eq!(chain.baudrate, 1_000_000);
+    }
+
+    #[tokio::test]
+    async fn flush_rx() {
+        let mut chain = chain_bm1366(1, 1);
+        chain.enumerate().await.unwrap();
+        // a partial frame is waiting in the decoder
+        chain.port.rx.extend([0xAA, 0x55, 0x13]);
+        assert!(chain.poll_response().await.unwrap().is_none());
+        assert_eq!(chain.decoder.len(), 3);
+        chain.port.rx.extend([0x00; 40]);
+        assert_eq!(chain.flush_rx().await, Ok(43));
+        assert!(chain.decoder.is_empty());
+        assert!(chain.port.rx.is_empty());
+        // garbage received after a baudrate change is dropped
+        chain.port.rx.extend(BM1366_CHIP_IDENT);
+        chain.port.rx.extend([0x00, 0xAA, 0x55]);
+        chain.set_baudrate(1_000_000).await.unwrap();
+        assert!(chain.port.rx.is_empty());
+        chain.port.rx.extend([0x00, 0xAA, 0x55]);
+        chain.reset().await.unwrap();
+        assert!(chain.port.rx.is_empty());
+        // an asynchronous serial interface waits for the next byte, without I/O timeout
+        chain.port.pend_empty = true;
+        chain.port.rx.extend([0x00; 20]);
+        assert_eq!(chain.flush_rx().await, Ok(20));
+        chain.reset().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn io_timeout() {
+        let mut chain = chain_bm1366(1, 1);
+        chain.port.hang = true;
+        chain.set_io_timeout(Duration::from_millis(10));
+        assert!(matches!(chain.poll_response().await, Err(Error::Timeout)));
+        assert!(!chain.ping(0).await.unwrap());
+        assert_eq!(chain.flush_rx().await, Ok(0));
+        assert_eq!(
+            chain.enumerate().await,
+            Err(Error::UnexpectedAsicCount {
+                expected_asic_cnt: 1,
+                actual_asic_cnt: 0
+            })
+        );
+        chain.port.hang = false;
+        chain.port.rx.extend(BM1366_CHIP_IDENT);
+        assert!(matches!(
+            chain.poll_response().await,
+            Ok(Some(ResponseType::Reg(_)))
+        ));
+    }
+
+    #[tokio::test]
+    async fn negotiate_baudrate() {
+        let mut chain = chain_bm1366(1, 1);
+        chain.enumerate().await.unwrap();
+        chain.port.baudrate = DEFAULT_BAUDRATE;
+        chain.port.idents = std::vec![0];
+        chain.port.max_baudrate = 1_000_000;
+        assert_eq!(
+            chain.negotiate_baudrate(&[3_000_000, 1_000_000]).await,
+            Ok(1_000_000)
+        );
+        assert_eq!(chain.port.baudrate, 1_000_000);
+        assert_eq!(chain.baudrate, 1_000_000);
+        // none of the candidates work
+        assert_eq!(
+            chain.negotiate_baudrate(&[6_000_000, 3_000_000]).await,
+            Err(Error::BaudrateChangeFailed {
+                baudrate: 3_000_000
+            })
+        );
+        assert_eq!(chain.port.baudrate, 1_000_000);
+    }
+
+    #[tokio::test]
+    async fn poll_response_resync() {
+        let mut chain = chain_bm1366(0, 1);
+        chain.port.rx.extend([0x00, 0x55]);
+        chain.port.rx.extend(BM1366_CHIP_IDENT);
+        let mut bad = BM1366_CHIP_IDENT;
+        bad[3] = 0x70;
+        chain.port.rx.extend(bad);
+        chain.port.rx.extend(BM1366_CHIP_IDENT);
+        match chain.poll_response().await {
+            Ok(Some(ResponseType::Reg(r))) => assert_eq!(r.reg_value, 0x1366_0000),
+            r => panic!("unexpected {:?}", r),
+        }
+        assert!(matches!(
+            chain.poll_response().await,
+            Err(Error::Protocol(_))
+        ));
+        assert!(matches!(
+            chain.poll_response().await,
+            Ok(Some(ResponseType::Reg(_)))
+        ));
+        assert!(matches!(chain.poll_response().await, Ok(None)));
+    }
+
+    #[tokio::test]
+    async fn pending_jobs() {
+        let mut chain = chain_bm1366(1, 1);
+        chain.port.rx.clear();
+        chain.port.idents = std::vec![0];
+        // nonces already on their way when the register read is sent
+        chain.port.rx.extend(job_resp(0x1234_5678, 8));
+        chain.port.rx.extend(job_resp(0x9abc_def0, 9));
+        assert_eq!(chain.ping(0).await, Ok(true));
+        let jobs: Vec<ResponseType> = chain.drain_pending_jobs().collect();
+        assert_eq!(jobs.len(), 2);
+        assert!(
+            matches!(jobs[0], ResponseType::Job(ref job) if job.nonce == 0x1234_5678 && job.job_id == 8)
+        );
+        assert!(
+            matches!(jobs[1], ResponseType::Job(ref job) if job.nonce == 0x9abc_def0 && job.job_id == 9)
+        );
+        assert_eq!(chain.drain_pending_jobs().count(), 0);
+
+        // also given by poll_response, before the new frames
+        chain.port.rx.extend(job_resp(0x1234_5678, 16));
+        assert_eq!(chain.ping(0).await, Ok(true));
+        chain.port.rx.extend(job_resp(0x9abc_def0, 24));
+        assert!(matches!(
+            chain.poll_response().await,
+            Ok(Some(ResponseType::Job(job))) if job.job_id == 16
+        ));
+        assert!(matches!(
+            chain.poll_response().await,
+            Ok(Some(ResponseType::Job(job))) if job.job_id == 24
+        ));
+        assert!(matches!(chain.poll_response().await, Ok(None)));
+
+        // a stale register response does not end the wait for the expected one
+        chain.port.rx.extend(job_resp(0x1234_5678, 32));
+        chain
+            .port
+            .rx
+            .extend(reg_resp(0, PLL0Parameter::ADDR, 0xc0a8_0263));
+        chain.port.rx.extend(job_resp(0x9abc_def0, 40));
+        assert_eq!(chain.ping(0).await, Ok(true));
+        assert!(chain.port.rx.is_empty());
+        let jobs: Vec<ResponseType> = chain.drain_pending_jobs().collect();
+        assert_eq!(jobs.len(), 2);
+        assert!(matches!(jobs[1], ResponseType::Job(ref job) if job.job_id == 40));
+    }
+
+    #[tokio::test]
+    async fn try_next_frame() {
+        let mut chain = chain_bm1366(0, 1);
+        chain.port.rx.extend(BM1366_CHIP_IDENT);
+        // nothing buffered yet, the serial interface is not read
+        assert!(matches!(chain.try_next_frame(), Ok(None)));
+        assert_eq!(chain.port.rx.len(), BM1366_CHIP_IDENT.len());
+        chain.decoder.push(&BM1366_CHIP_IDENT);
+        chain.decoder.push(&BM1366_CHIP_IDENT);
+        chain.decoder.push(&BM1366_CHIP_IDENT[..4]);
+        assert!(matches!(
+            chain.try_next_frame(),
+            Ok(Some(ResponseType::Reg(_)))
+        ));
+        assert!(matches!(
+            chain.try_next_frame(),
+            Ok(Some(ResponseType::Reg(_)))
+        ));
+        // partial frame
+        assert!(matches!(chain.try_next_frame(), Ok(None)));
+        assert_eq!(chain.stats().frames, 2);
+        chain.decoder.push(&BM1366_CHIP_IDENT[4..]);
+        assert!(matches!(
+            chain.try_next_frame(),
+            Ok(Some(ResponseType::Reg(_)))
+        ));
+        assert_eq!(chain.port.rx.len(), BM1366_CHIP_IDENT.len());
+    }
+
+    #[tokio::test]
+    async fn poll_response_stats() {
+        const JOB: [u8; 9] = [0xAA, 0x55, 0x97, 0xC3, 0x28, 0xB6, 0x01, 0x63, 0x9C];
+        let mut chain = chain_bm1366(0, 1);
+        chain.port.rx.extend(JOB);
+        chain.port.rx.extend([0x00, 0x55]);
+        chain.port.rx.extend(BM1366_CHIP_IDENT);
+        let mut bad = JOB;
+        bad[3] = 0x70;
+        chain.port.rx.extend(bad);
+        chain.port.rx.extend(JOB);
+        while !matches!(chain.poll_response().await, Ok(None)) {}
+        assert_eq!(
+            chain.stats(),
+            ChainStats {
+                frames: 3,
+                bad_crc: 1,
+                // leading garbage, then the corrupted frame tail
+                resyncs: 2,
+                nonces: 2,
+            }
+        );
+        chain.reset_stats();
+        assert_eq!(chain.stats(), ChainStats::default());
+    }
+
+    #[tokio::test]
+    async fn poll_response_strict() {
+        let mut bad = BM1366_CHIP_IDENT;
+        bad[3] = 0x70;
+        let mut chain = chain_bm1366(0, 1);
+        chain.set_strict(true);
+        chain.set_max_protocol_errors(2);
+        chain.port.rx.extend(bad);
+        chain.port.rx.extend(BM1366_CHIP_IDENT);
+        chain.port.rx.extend(bad);
+        chain.port.rx.extend(bad);
+        chain.port.rx.extend(bad);
+        assert!(matches!(
+            chain.poll_response().await,
+            Err(Error::Protocol(_))
+        ));
+        // a valid frame resets the consecutive errors count
+        assert!(matches!(
+            chain.poll_response().await,
+            Ok(Some(ResponseType::Reg(_)))
+        ));
+        assert!(matches!(
+            chain.poll_response().await,
+            Err(Error::Protocol(_))
+        ));
+        for _ in 0..2 {
+            assert!(matches!(
+                chain.poll_response().await,
+                Err(Error::TooManyProtocolErrors { .. })
+            ));
+        }
+        assert!(matches!(chain.poll_response().await, Ok(None)));
+    }
+
+    #[tokio::test]
+    async fn set_hash_freq_direct_single_write() {
+        let mut chain = chain_bm1366(1, 1);
+        chain
+            .set_hash_freq_direct(HertzU64::MHz(400))
+            .await
+            .unwrap();
+        assert_eq!(chain.port.tx.len(), 1);
+        assert_eq!(chain.port.tx[0][5], PLL0Parameter::ADDR);
+        chain.port.tx.clear();
+        chain.set_hash_freq(HertzU64::MHz(425)).await.unwrap();
+        assert!(chain.port.tx.len() > 1);
+    }
+
+    #[tokio::test]
+    async fn set_hash_freq_verify() {
+        let mut chain = chain_bm1366(1, 1);
+        chain.enumerate().await.unwrap();
+        chain.set_verify_freq(true);
+        assert_eq!(
+            chain.set_hash_freq(HertzU64::MHz(100)).await,
+            Err(Error::NoResponse {
+                chip_addr: 0,
+                reg_addr: PLL0Parameter::ADDR
+            })
+        );
+        let expected = chain.asic.register(PLL0Parameter::ADDR).unwrap();
+        chain
+            .port
+            .rx
+            .extend(reg_resp(0, PLL0Parameter::ADDR, expected));
+        chain.set_hash_freq(HertzU64::MHz(100)).await.unwrap();
+        // the asic rejected the last PLL write
+        chain
+            .port
+            .rx
+            .extend(reg_resp(0, PLL0Parameter::ADDR, expected - 1));
+        assert_eq!(
+            chain.set_hash_freq_direct(HertzU64::MHz(100)).await,
+            Err(Error::FrequencyNotConfirmed {
+                expected,
+                actual: expected - 1
+            })
+        );
+        assert_eq!(
+            chain.port.tx.last().unwrap(),
+            &Command::read_reg(PLL0Parameter::ADDR, Destination::Chip(0)).to_vec()
+        );
+    }
+
+    #[tokio::test]
+    async fn set_hash_freq_too_high() {
+        let mut chain = chain_bm1366(1, 1);
+        let max = chain.asic.max_hash_freq();
+        let above = max + HertzU64::Hz(1);
+        assert_eq!(
+            chain.set_hash_freq(above).await,
+            Err(Error::FrequencyTooHigh { freq: above, max })
+        );
+        assert!(matches!(
+            chain.set_chip_hash_freq(0, above).await,
+            Err(Error::FrequencyTooHigh { .. })
+        ));
+        assert!(matches!(
+            chain.set_hash_freq_direct(above).await,
+            Err(Error::FrequencyTooHigh { .. })
+        ));
+        assert!(chain.port.tx.is_empty());
+        chain.set_hash_freq_direct(max).await.unwrap();
+        assert!(!chain.port.tx.is_empty());
+    }
+
+    #[tokio::test]
+    async fn chip_addresses() {
+        let mut chain = chain_bm1366(4, 1);
+        chain.enumerate().await.unwrap();
+        assert_eq!(chain.asic_addr_interval, 64);
+        assert_eq!(
+            chain.chip_addresses().collect::<Vec<u8>>(),
+            [0, 64, 128, 192]
+        );
+    }
+
+    #[tokio::test]
+    async fn ping() {
+        let mut chain = chain_bm1366(2, 1);
+        chain.port.idents = std::vec![0];
+        assert!(chain.ping(0).await.unwrap());
+        assert!(!chain.ping(128).await.unwrap());
+        assert_eq!(
+            chain.port.tx[1],
+            Command::read_reg(ChipIdentification::ADDR, Destination::Chip(128))
+        );
+        // another chip answering is not the expected one
+        chain.port.rx.extend(bm1366_chip_ident(2));
+        assert!(!chain.ping(4).await.unwrap());
+        // a stale response is skipped
+        chain.port.rx.extend(bm1366_chip_ident(2));
+        chain.port.rx.extend(bm1366_chip_ident(4));
+        assert!(chain.ping(4).await.unwrap());
+        assert!(chain.port.rx.is_empty());
+    }
+
+    #[tokio::test]
+    async fn set_chip_hash_freq() {
+        let mut chain = chain_bm1366(4, 1);
+        chain.enumerate().await.unwrap();
+        chain.port.tx.clear();
+        chain
+            .set_chip_hash_freq(128, HertzU64::MHz(75))
+            .await
+            .unwrap();
+        assert!(!chain.port.tx.is_empty());
+        assert_eq!(chip_writes(&chain, PLL0Parameter::ADDR), [128; 4]);
+        assert!(chain.port.tx.iter().all(|f| f[2] == 0x41));
+        assert_eq!(
+            chain.set_chip_hash_freq(32, HertzU64::MHz(75)).await,
+            Err(Error::BadChipAddr { chip_addr: 32 })
+        );
+    }
+
+    #[tokio::test]
+    async fn set_chip_hash_freq_then_chain_ramp() {
+        let mut chain = chain_bm1366(4, 1);
+        chain.enumerate().await.unwrap();
+        chain
+            .set_chip_hash_freq(128, HertzU64::MHz(75))
+            .await
+            .unwrap();
+        chain.port.tx.clear();
+        chain.set_hash_freq(HertzU64::MHz(100)).await.unwrap();
+        let freqs = ramp_hash_freqs(
+            &chain,
+            [
+                HertzU64::MHz(50),
+                HertzU64::MHz(50),
+                HertzU64::MHz(75),
+                HertzU64::MHz(50),
+            ],
+        );
+        assert_eq!(freqs, [HertzU64::MHz(100); 4]);
+        assert_eq!(chain.chip_hash_freq(128), HertzU64::MHz(100));
+    }
+
+    #[tokio::test]
+    async fn park() {
+        let mut chain = chain_bm1366(2, 1);
+        chain.enumerate().await.unwrap();
+        chain.set_hash_freq(HertzU64::MHz(75)).await.unwrap();
+        chain.port.tx.clear();
+        chain.park().await.unwrap();
+        let pll_param = |freq| {
+            let mut bm1366 = BM1366::default();
+            bm1366.set_hash_freq(freq);
+            bm1366.plls[bm1366::BM1366_PLL_ID_HASH].parameter()
+        };
+        let hash_clk = |val| {
+            Command::write_reg(
+                CoreRegisterControl::ADDR,
+                CoreRegisterControl::write_core_reg_id(0, HashClockCtrl::ID, val),
+                Destination::All,
+            )
+        };
+        // ramped down, then hashing clocks disabled
+        assert_eq!(chain.port.tx.len(), 6);
+        assert_eq!(chain.port.tx[0][5], PLL0Divider::ADDR);
+        for (i, khz) in [68_750, 62_500, 56_250, 50_000].into_iter().enumerate() {
+            assert_eq!(
+                chain.port.tx[1 + i],
+                Command::write_reg(
+                    PLL0Parameter::ADDR,
+                    pll_param(HertzU64::kHz(khz)),
+                    Destination::All
+                )
+            );
+        }
+        assert_eq!(chain.port.tx[5], hash_clk(0x00));
+        assert_eq!(chain.asic.hash_freq(), PARK_HASH_FREQ);
+        assert_eq!(chain.asic.core_register(HashClockCtrl::ID), Some(0x00));
+
+        chain.port.tx.clear();
+        chain.unpark(HertzU64::MHz(75)).await.unwrap();
+        assert_eq!(chain.port.tx[0], hash_clk(0x40));
+        assert_eq!(
+            chain.port.tx.last().unwrap(),
+            &Command::write_reg(
+                PLL0Parameter::ADDR,
+                pll_param(HertzU64::MHz(75)),
+                Destination::All
+            )
+        );
+        assert_eq!(chain.chip_hash_freq(64), HertzU64::MHz(75));
+    }
+
+    #[tokio::test]
+    async fn set_hash_freq_staggered() {
+        let mut chain = chain_bm1366(4, 2);
+        chain.enumerate().await.unwrap();
+        chain
+            .set_chip_hash_freq(128, HertzU64::MHz(75))
+            .await
+            .unwrap();
+        let mut reference = chain_bm1366(4, 2);
+        reference.set_hash_freq(HertzU64::MHz(100)).await.unwrap();
+        chain.port.tx.clear();
+        chain
+            .set_hash_freq_staggered(HertzU64::MHz(100))
+            .await
+            .unwrap();
+        let writes = chip_writes(&chain, PLL0Parameter::ADDR);
+        let steps = reference
+            .port
+            .tx
+            .iter()
+            .filter(|f| f[5] == PLL0Parameter::ADDR)
+            .count();
+        assert!(steps > 1);
+        // the first domain is fully ramped before the second one starts, the asic at 75 MHz
+        // needing less steps
+        let mut expected = [0, 64].repeat(steps);
+        expected.extend([128, 192].repeat(4));
+        expected.extend([192].repeat(steps - 4));
+        assert_eq!(writes, expected);
+        let values = |chain: &Chain<BM1366, MockPort, MockDelay>, dest: u8| -> Vec<[u8; 4]> {
+            chain
+                .port
+                .tx
+                .iter()
+                .filter(|f| f[4] == dest && f[5] == PLL0Parameter::ADDR)
+                .map(|f| f[6..10].try_into().unwrap())
+                .collect()
+        };
+        assert_eq!(values(&chain, 0).last(), values(&reference, 0).last());
+        assert_eq!(values(&chain, 192).last(), values(&reference, 0).last());
+        assert_eq!(
+            ramp_hash_freqs(
+                &chain,
+                [
+                    HertzU64::MHz(50),
+                    HertzU64::MHz(50),
+                    HertzU64::MHz(75),
+                    HertzU64::MHz(50),
+                ],
+            ),
+            [HertzU64::MHz(100); 4]
+        );
+        assert_eq!(
+            chain.asic.register(PLL0Parameter::ADDR),
+            reference.asic.register(PLL0Parameter::ADDR)
+        );
+        assert_eq!(chain.chip_hash_freq(128), HertzU64::MHz(100));
+        // the model does not keep any asic apart
+        chain.port.tx.clear();
+        chain.set_hash_freq(HertzU64::MHz(75)).await.unwrap();
+        reference.port.tx.clear();
+        reference.set_hash_freq(HertzU64::MHz(75)).await.unwrap();
+        assert_eq!(chain.port.tx, reference.port.tx);
+
+        chain.domain_sizes = heapless::Vec::from_slice(&[2, 1]).unwrap();
+        assert_eq!(
+            chain.set_hash_freq_staggered(HertzU64::MHz(100)).await,
+            Err(Error::BadDomainSizes)
+        );
+
+        let mut chain = chain_bm1366(20, 1);
+        chain.enumerate().await.unwrap();
+        assert_eq!(
+            chain.set_hash_freq_staggered(HertzU64::MHz(100)).await,
+            Err(Error::TooManyChipFreqs {
+                max: MAX_CHIP_FREQS
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn total_theoretical_hashrate() {
+        let mut chain = chain_bm1366(4, 1);
+        chain.enumerate().await.unwrap();
+        assert_eq!(
+            chain.total_theoretical_hashrate_ghs(),
+            4.0 * chain.asic.theoretical_hashrate_ghs()
+        );
+        chain
+            .set_chip_hash_freq(128, HertzU64::MHz(75))
+            .await
+            .unwrap();
+        assert_eq!(chain.chip_hash_freq(64), HertzU64::MHz(50));
+        assert_eq!(chain.chip_hash_freq(128), HertzU64::MHz(75));
+        assert!((chain.total_theoretical_hashrate_ghs() - 201.15).abs() < 0.001);
+        // a chain-wide frequency overrides the per-chip ones
+        chain.set_hash_freq(HertzU64::MHz(75)).await.unwrap();
+        assert_eq!(chain.chip_hash_freq(64), HertzU64::MHz(75));
+        assert!((chain.total_theoretical_hashrate_ghs() - 268.2).abs() < 0.001);
+    }
+
+    #[tokio::test]
+    async fn enumerate_missing_chip() {
+        let mut chain = chain_bm1366(4, 1);
+        chain.port.rx.clear();
+        chain.port.rx.extend(BM1366_CHIP_IDENT.repeat(3));
+        assert_eq!(
+            chain.enumerate().await,
+            Err(Error::UnexpectedAsicCount {
+                expected_asic_cnt: 4,
+                actual_asic_cnt: 3
+            })
+        );
+
+        chain.port.rx.extend(BM1366_CHIP_IDENT.repeat(3));
+        chain.port.idents = std::vec![0, 64, 192];
+        chain.set_max_missing(1);
+        chain.enumerate().await.unwrap();
+        assert_eq!(chain.asic_addr_interval, 64);
+        assert!(chain.is_present(0));
+        assert!(chain.is_present(64));
+        assert!(!chain.is_present(128));
+        assert!(chain.is_present(192));
+
+        chain.port.tx.clear();
+        let mut reset = Vec::new();
+        chain
+            .reset_all_cores_with_progress(&mut |chip_addr| reset.push(chip_addr))
+            .await
+            .unwrap();
+        assert_eq!(reset, [0, 64, 192]);
+        assert!(chain
+            .port
+            .tx
+            .iter()
+            .all(|f| f[2] & 0x10 != 0 || f[4] != 128));
+        assert!(chain
+            .port
+            .tx
+            .iter()
+            .any(|f| f[2] & 0x10 == 0 && f[4] == 192));
+    }
+
+    #[tokio::test]
+    async fn reset_cores() {
+        let mut chain = chain_bm1366(4, 1);
+        chain.enumerate().await.unwrap();
+        chain.port.tx.clear();
+        chain.reset_cores(Destination::Chip(64)).await.unwrap();
+        assert!(!chain.port.tx.is_empty());
+        assert!(chain.port.tx.iter().all(|f| f[2] & 0x10 != 0 || f[4] == 64));
+    }
+
+    #[tokio::test]
+    async fn send_job_before_init() {
+        let mut chain = chain_bm1366(1, 1);
+        assert_eq!(
+            chain.send_job_header(0, [0; 32], [0; 32], 0, 0).await,
+            Err(Error::NotInitialized)
+        );
+        assert_eq!(
+            chain.send_job_struct(&Job::default()).await,
+            Err(Error::NotInitialized)
+        );
+        assert!(chain.port.tx.is_empty());
+        // an already built job is sent as is
+        let raw = Job::default().header(0x42);
+        assert_eq!(chain.send_job(&raw).await, Ok(88));
+        assert_eq!(chain.port.tx[0], raw);
+        assert_eq!(chain.current_job_id(), 0);
+        chain.init(256).await.unwrap();
+        assert_eq!(chain.send_job_id(0, [0; 32], [0; 32], 0, 0).await, Ok(8));
+        chain.reset().await.unwrap();
+        assert_eq!(
+            chain.send_job_header(0, [0; 32], [0; 32], 0, 0).await,
+            Err(Error::NotInitialized)
+        );
+    }
+
+    #[tokio::test]
+    async fn send_job_window() {
+        let mut chain = chain_bm1366(1, 1);
+        chain.initialized = true;
+        let job = chain
+            .send_job_header(0, [0; 32], [0; 32], 0, 0)
+            .await
+            .unwrap();
+        assert_eq!(job.job_id, 8);
+        assert_eq!(job.rolling_duration, chain.asic.rolling_duration());
+        assert_eq!(job.version_window, 0);
+        chain.set_version_rolling(0x1fff_e000).await.unwrap();
+        let job = chain
+            .send_job_header(0, [0; 32], [0; 32], 0, 0)
+            .await
+            .unwrap();
+        assert_eq!(job.job_id, 16);
+        assert_eq!(job.rolling_duration, chain.asic.rolling_duration());
+        assert!(job.rolling_duration > Duration::from_secs(21));
+        assert_eq!(job.version_window, 0x1fff_e000);
+    }
+
+    #[tokio::test]
+    async fn send_job_struct() {
+        let mut chain = chain_bm1366(1, 1);
+        chain.initialized = true;
+        let mut job = Job {
+            version: 0x2000_0000,
+            n_bits: 0x1704_2450,
+            n_time: 0x6570_de83,
+            ..Default::default()
+        };
+        chain
+            .send_job_header(job.version, [0; 32], [0; 32], job.n_bits, job.n_time)
+            .await
+            .unwrap();
+        let sent = chain.send_job_struct(&job).await.unwrap();
+        assert_eq!(sent.job_id, 16);
+        assert_eq!(sent.rolling_duration, chain.asic.rolling_duration());
+        assert_eq!(chain.port.tx[1][4], 16);
+        assert_eq!(chain.port.tx[0][5..86], chain.port.tx[1][5..86]);
+        job.starting_nonce = Some(0x1234_5678);
+        chain.send_job_struct(&job).await.unwrap();
+        assert_eq!(chain.port.tx[2][6..10], [0x78, 0x56, 0x34, 0x12]);
+        assert_eq!(
+            chain.port.tx[2][86..88],
+            crc16(&chain.port.tx[2][2..86]).to_be_bytes()
+        );
+    }
+
+    #[tokio::test]
+    async fn sequence_in_progress() {
+        let mut chain = chain_bm1366(1, 1);
+        chain.enumerate().await.unwrap();
+        chain.port.tx.clear();
+        // an init interrupted after its first command
+        chain.asic.init_next(256).unwrap();
+        let err = Err(Error::SequenceInProgress {
+            step: SequenceStep::Init(0),
+        });
+        assert_eq!(chain.set_baudrate(1_000_000).await, err);
+        assert_eq!(chain.set_hash_freq(HertzU64::MHz(200)).await, err);
+        assert_eq!(chain.init(256).await, err);
+        assert!(chain.port.tx.is_empty());
+        chain.reset().await.unwrap();
+        chain.init(256).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn set_version_rolling_frame_size() {
+        const JOB_VER: [u8; 11] = [
+            0xAA, 0x55, 0x2F, 0xD5, 0x96, 0xCE, 0x02, 0x93, 0x94, 0xFB, 0x86,
+        ];
+        let mut chain = chain_bm1366(1, 1);
+        // former frame size responses, partially decoded when Version Rolling is enabled
+        let old = job_resp(0x1234_5678, 8);
+        chain.decoder.push(&old[..5]);
+        chain.port.rx.extend(&old[5..]);
+        chain.port.rx.extend(old);
+        chain.set_version_rolling(0x1fff_e000).await.unwrap();
+        chain.port.rx.extend(JOB_VER);
+        match chain.poll_response().await {
+            Ok(Some(ResponseType::JobVer(r))) => {
+                assert_eq!(r.nonce, 0x2fd5_96ce);
+                assert_eq!(r.version_bit, 0x129f_6000);
+            }
+            r => panic!("unexpected {:?}", r),
+        }
+        assert!(matches!(chain.poll_response().await, Ok(None)));
+        assert_eq!(chain.stats().resyncs, 0);
+        assert_eq!(chain.stats().bad_crc, 0);
+    }
+
+    #[tokio::test]
+    async fn set_version_mask() {
+        let mut chain = chain_bm1366(1, 1);
+        chain.set_version_rolling(0x1fff_e000).await.unwrap();
+        chain.port.tx.clear();
+        chain.set_version_mask(0x1ffe_0000).await.unwrap();
+        assert_eq!(
+            chain.port.tx,
+            [Command::write_reg(VersionRolling::ADDR, 0x9000_fff0, Destination::All).to_vec()]
+        );
+        assert_eq!(
+            VersionRolling(u32::from_be_bytes(
+                chain.port.tx[0][6..10].try_into().unwrap()
+            ))
+            .mask(),
+            0x1ffe_0000
+        );
+    }
+
+    #[tokio::test]
+    async fn set_nonce_timeout() {
+        let mut chain = chain_bm1366(2, 1);
+        chain.set_nonce_timeout(0x000c).await.unwrap();
+        assert_eq!(
+            chain.port.tx,
+            [
+                Command::write_reg(NonceReturnedTimeout::ADDR, 0x00fd_000c, Destination::All)
+                    .to_vec()
+            ]
+        );
+        assert_eq!(
+            chain.asic.register(NonceReturnedTimeout::ADDR),
+            Some(0x00fd_000c)
+        );
+    }
+
+    #[tokio::test]
+    async fn set_driver_strength() {
+        let mut chain = chain_bm1366(2, 1);
+        assert_eq!(
+            chain.asic.register(IoDriverStrenghtConfiguration::ADDR),
+            Some(0x0001_2111)
+        );
+        chain
+            .set_driver_strength(DriverSelect::CLKO, 4, Destination::Chip(0))
+            .await
+            .unwrap();
+        assert_eq!(
+            chain.port.tx,
+            [Command::write_reg(
+                IoDriverStrenghtConfiguration::ADDR,
+                0x0001_4111,
+                Destination::Chip(0)
+            )
+            .to_vec()]
+        );
+        assert_eq!(
+            chain.asic.register(IoDriverStrenghtConfiguration::ADDR),
+            Some(0x0001_4111)
+        );
+    }
+
+    #[tokio::test]
+    async fn set_hash_counting_number() {
+        let mut chain = chain_bm1366(2, 1);
+        chain.set_version_rolling(0x1fff_e000).await.unwrap();
+        assert_eq!(
+            chain.asic.register(HashCountingNumber::ADDR),
+            Some(0x0000_151c)
+        );
+        chain.port.tx.clear();
+        chain.set_hash_counting_number(0x1a44).await.unwrap();
+        assert_eq!(
+            chain.port.tx,
+            [Command::write_reg(HashCountingNumber::ADDR, 0x1a44, Destination::All).to_vec()]
+        );
+        assert_eq!(
+            chain.asic.register(HashCountingNumber::ADDR),
+            Some(0x0000_1a44)
+        );
+    }
+
+    #[tokio::test]
+    async fn core_register() {
+        let mut chain = chain_bm1366(2, 1);
+        chain
+            .write_core_register(Destination::Chip(128), HashClockCtrl::ID, 0x41)
+            .await
+            .unwrap();
+        assert_eq!(chain.port.tx[0][5], CoreRegisterControl::ADDR);
+        assert_eq!(chain.port.tx[0][6..10], [0x80, 0x00, 0x85, 0x41]);
+        assert_eq!(
+            chain.asic.core_registers.get(&HashClockCtrl::ID),
+            Some(&0x41)
+        );
+
+        chain.port.rx.clear();
+        chain
+            .port
+            .rx
+            .extend(reg_resp(128, CoreRegisterValue::ADDR, 0x0000_0041));
+        assert_eq!(
+            chain.read_core_register(128, HashClockCtrl::ID).await,
+            Ok(Some(0x41))
+        );
+        assert_eq!(chain.port.tx[1][6..10], [0x80, 0x00, 0x05, 0xff]);
+        assert_eq!(
+            chain.port.tx[2],
+            Command::read_reg(CoreRegisterValue::ADDR, Destination::Chip(128))
+        );
+        assert_eq!(
+            chain.read_core_register(0, HashClockCtrl::ID).await,
+            Ok(None)
+        );
+    }
+
+    #[tokio::test]
+    async fn read_process_monitor() {
+        let mut chain = chain_bm1366(2, 1);
+        chain.port.rx.clear();
+        chain
+            .port
+            .rx
+            .extend(reg_resp(128, CoreRegisterValue::ADDR, 0x0000_0042));
+        assert_eq!(
+            chain
+                .read_process_monitor(128, ProcessMonitorSelect::SVTDelayChain)
+                .await,
+            Ok(0x42)
+        );
+        // start a SVT measurement, then latch and read ProcessMonitorData
+        assert_eq!(chain.port.tx[0][4], 128);
+        assert_eq!(chain.port.tx[0][6..10], [0x80, 0x00, 0x81, 0x05]);
+        assert_eq!(chain.port.tx[1][6..10], [0x80, 0x00, 0x02, 0xff]);
+        assert_eq!(
+            chain
+                .read_process_monitor(0, ProcessMonitorSelect::LVTDelayChain)
+                .await,
+            Err(Error::NoResponse {
+                chip_addr: 0,
+                reg_addr: CoreRegisterValue::ADDR
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn run_frequency_sweep() {
+        let mut chain = chain_bm1366(2, 1);
+        chain.port.rx.clear();
+        chain
+            .port
+            .rx
+            .extend(reg_resp(128, GoldenNonceForSweepReturn::ADDR, 0x0037_6400));
+        chain
+            .port
+            .rx
+            .extend(reg_resp(128, ReturnedGroupPatternStatus::ADDR, 0x0F0A_0501));
+        chain.port.rx.extend(reg_resp(
+            128,
+            ReturnedSinglePatternStatus::ADDR,
+            0x8000_0001,
+        ));
+        let res = chain
+            .run_frequency_sweep(128, FrequencySweepControl1(0x0000_0070))
+            .await
+            .unwrap();
+        assert_eq!(
+            chain.port.tx[0],
+            Command::write_reg(
+                FrequencySweepControl1::ADDR,
+                0x0000_0070,
+                Destination::Chip(128)
+            )
+        );
+        assert_eq!(res.golden_nonce.gnoswr(), 0x0037_6400);
+        assert_eq!(res.group_pattern_status.rgps(2), Some(0xA));
+        assert_eq!(res.single_pattern_status.rsps(), 0x8000_0001);
+
+        chain
+            .port
+            .rx
+            .extend(reg_resp(0, GoldenNonceForSweepReturn::ADDR, 0x0037_6400));
+        assert_eq!(
+            chain
+                .run_frequency_sweep(0, FrequencySweepControl1(0x0000_0070))
+                .await,
+            Err(Error::NoResponse {
+                chip_addr: 0,
+                reg_addr: ReturnedGroupPatternStatus::ADDR
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn core_frequency_sweep() {
+        let mut chain = chain_bm1366(2, 1);
+        chain.port.rx.clear();
+        for (reg_addr, reg_value) in [
+            (GoldenNonceForSweepReturn::ADDR, 0x0037_6400),
+            (ReturnedGroupPatternStatus::ADDR, 0x0F0A_0501),
+            (ReturnedSinglePatternStatus::ADDR, 0x8000_0001),
+        ] {
+            chain.port.rx.extend(reg_resp(128, reg_addr, reg_value));
+        }
+        let res = chain
+            .core_frequency_sweep(128, 3, FrequencySweepControl1(0x0000_0070))
+            .await
+            .unwrap();
+        assert_eq!(res.group_pattern_status.rgps(1), Some(0x5));
+        let sweep_clk = |val| {
+            Command::write_reg(
+                CoreRegisterControl::ADDR,
+                CoreRegisterControl::write_core_reg_id(0, SweepClockCtrl::ID, val),
+                Destination::Chip(128),
+            )
+        };
+        assert_eq!(chain.port.tx[0], sweep_clk(0x83));
+        assert_eq!(chain.port.tx[1][5], FrequencySweepControl1::ADDR);
+        assert_eq!(chain.port.tx.last().unwrap(), &sweep_clk(0x00));
+
+        // sweep mode is left even if the asic does not answer
+        chain.port.tx.clear();
+        assert!(matches!(
+            chain
+                .core_frequency_sweep(128, 3, FrequencySweepControl1(0x0000_0070))
+                .await,
+            Err(Error::NoResponse { .. })
+        ));
+        assert_eq!(chain.port.tx.last().unwrap(), &sweep_clk(0x00));
+    }
+
+    #[tokio::test]
+    async fn pattern_test_results() {
+        let mut chain = chain_bm1366(2, 1);
+        chain.port.rx.clear();
+        chain
+            .port
+            .rx
+            .extend(reg_resp(128, ReturnedGroupPatternStatus::ADDR, 0xFFFF_FFFF));
+        chain.port.rx.extend(reg_resp(
+            128,
+            ReturnedSinglePatternStatus::ADDR,
+            0xFFFF_FFFF,
+        ));
+        let res = chain.pattern_test_results(128).await.unwrap();
+        assert!(res.all_passed());
+
+        chain
+            .port
+            .rx
+            .extend(reg_resp(0, ReturnedGroupPatternStatus::ADDR, 0x0F0A_0501));
+        chain
+            .port
+            .rx
+            .extend(reg_resp(0, ReturnedSinglePatternStatus::ADDR, 0x0000_0000));
+        let res = chain.pattern_test_results(0).await.unwrap();
+        assert_eq!(res.groups[1], [true, false, true, false]);
+        assert_eq!(res.group_passed_cnt(), 9);
+        assert_eq!(res.single, [false; 32]);
+        assert!(!res.all_passed());
+
+        chain
+            .port
+            .rx
+            .extend(reg_resp(0, ReturnedGroupPatternStatus::ADDR, 0x0F0A_0501));
+        assert_eq!(
+            chain.pattern_test_results(0).await,
+            Err(Error::NoResponse {
+                chip_addr: 0,
+                reg_addr: ReturnedSinglePatternStatus::ADDR
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn verify_clock_order() {
+        let mut chain = chain_bm1366(2, 1);
+        chain.enumerate().await.unwrap();
+        chain
+            .port
+            .rx
+            .extend(reg_resp(128, ClockOrderControl0::ADDR, 0));
+        chain
+            .port
+            .rx
+            .extend(reg_resp(128, ClockOrderControl1::ADDR, 0));
+        chain
+            .port
+            .rx
+            .extend(reg_resp(128, ClockOrderStatus::ADDR, 0xFFFF));
+        chain.verify_clock_order(128).await.unwrap();
+
+        chain
+            .port
+            .rx
+            .extend(reg_resp(0, ClockOrderControl0::ADDR, 0));
+        chain
+            .port
+            .rx
+            .extend(reg_resp(0, ClockOrderControl1::ADDR, 0x1234_5678));
+        assert_eq!(
+            chain.verify_clock_order(0).await,
+            Err(Error::ClockOrderMismatch {
+                chip_addr: 0,
+                reg_addr: ClockOrderControl1::ADDR,
+                expected: 0,
+                actual: 0x1234_5678
+            })
+        );
+
+        assert_eq!(
+            chain.verify_clock_order(0).await,
+            Err(Error::NoResponse {
+                chip_addr: 0,
+                reg_addr: ClockOrderControl0::ADDR
+            })
+        );
+
+        assert_eq!(
+            chain.verify_clock_order(64).await,
+            Err(Error::BadChipAddr { chip_addr: 64 })
+        );
     }
 }
@@ -0,0 +1,41 @@
+use core::time::Duration;
+
+/// # Chain Statistics
+///
+/// Counters accumulated by `Chain::poll_response`, all wrapping around.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+pub struct ChainStats {
+    /// Frames successfully decoded
+    pub frames: u32,
+    /// Frames dropped because of an invalid CRC
+    pub bad_crc: u32,
+    /// Times bytes were discarded to find the next preamble
+    pub resyncs: u32,
+    /// Nonces received, with or without version
+    pub nonces: u32,
+}
+
+impl ChainStats {
+    /// ## Get the nonce rate, in nonces per second
+    ///
+    /// ### Example
+    /// ```
+    /// use bm13xx_chain::ChainStats;
+    /// use core::time::Duration;
+    ///
+    /// let stats = ChainStats {
+    ///     nonces: 30,
+    ///     ..Default::default()
+    /// };
+    /// assert_eq!(stats.nonce_rate(Duration::from_secs(10)), 3.0);
+    /// assert_eq!(stats.nonce_rate(Duration::ZERO), 0.0);
+    /// ```
+    pub fn nonce_rate(&self, elapsed: Duration) -> f32 {
+        if elapsed.is_zero() {
+            0.0
+        } else {
+            self.nonces as f32 / elapsed.as_secs_f32()
+        }
+    }
+}
@@ -0,0 +1,112 @@
+use bm13xx_asic::register::{
+    GoldenNonceForSweepReturn, ReturnedGroupPatternStatus, ReturnedSinglePatternStatus,
+};
+
+/// # Frequency Sweep Result
+///
+/// Registers read back from an asic after a frequency sweep self-test,
+/// see `Chain::run_frequency_sweep`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+pub struct FrequencySweepResult {
+    pub golden_nonce: GoldenNonceForSweepReturn,
+    pub group_pattern_status: ReturnedGroupPatternStatus,
+    pub single_pattern_status: ReturnedSinglePatternStatus,
+}
+
+impl FrequencySweepResult {
+    /// ## Get the per-core pattern test results of this sweep
+    pub const fn pattern_test_results(&self) -> PatternTestResults {
+        PatternTestResults::new(self.group_pattern_status, self.single_pattern_status)
+    }
+}
+
+/// Number of groups in `ReturnedGroupPatternStatus`.
+pub const PATTERN_GROUPS: usize = 4;
+/// Number of cores per group in `ReturnedGroupPatternStatus`.
+pub const PATTERN_GROUP_CORES: usize = 4;
+/// Number of cores in `ReturnedSinglePatternStatus`.
+pub const PATTERN_SINGLE_CORES: usize = 32;
+
+/// # Pattern Test Results
+///
+/// Per-core pass map decoded from `ReturnedGroupPatternStatus` and
+/// `ReturnedSinglePatternStatus`, see `Chain::pattern_test_results`.
+/// A set status bit is assumed to be a passing core, this is not documented by the vendor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+pub struct PatternTestResults {
+    /// `groups[group][core]` is the group pattern status of `core` in `group`.
+    pub groups: [[bool; PATTERN_GROUP_CORES]; PATTERN_GROUPS],
+    /// `single[core]` is the single pattern status of `core`.
+    pub single: [bool; PATTERN_SINGLE_CORES],
+}
+
+impl PatternTestResults {
+    /// ## Decode the pattern status registers
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// use bm13xx_asic::register::{ReturnedGroupPatternStatus, ReturnedSinglePatternStatus};
+    /// use bm13xx_chain::PatternTestResults;
+    ///
+    /// let res = PatternTestResults::new(
+    ///     ReturnedGroupPatternStatus(0x0F0A_0501),
+    ///     ReturnedSinglePatternStatus(0x8000_0001),
+    /// );
+    /// assert_eq!(res.groups[0], [true, false, false, false]);
+    /// assert_eq!(res.groups[1], [true, false, true, false]);
+    /// assert_eq!(res.groups[2], [false, true, false, true]);
+    /// assert_eq!(res.groups[3], [true; 4]);
+    /// assert!(res.single[0] && res.single[31]);
+    /// assert_eq!(res.group_passed_cnt(), 9);
+    /// assert_eq!(res.single_passed_cnt(), 2);
+    /// assert!(!res.all_passed());
+    /// ```
+    pub const fn new(
+        group_pattern_status: ReturnedGroupPatternStatus,
+        single_pattern_status: ReturnedSinglePatternStatus,
+    ) -> Self {
+        let mut groups = [[false; PATTERN_GROUP_CORES]; PATTERN_GROUPS];
+        let mut group = 0;
+        while group < PATTERN_GROUPS {
+            let mut core = 0;
+            while core < PATTERN_GROUP_CORES {
+                groups[group][core] = matches!(
+                    group_pattern_status.core_passed(group as u8, core as u8),
+                    Some(true)
+                );
+                core += 1;
+            }
+            group += 1;
+        }
+        let mut single = [false; PATTERN_SINGLE_CORES];
+        let mut core = 0;
+        while core < PATTERN_SINGLE_CORES {
+            single[core] = matches!(single_pattern_status.core_passed(core as u8), Some(true));
+            core += 1;
+        }
+        Self { groups, single }
+    }
+
+    /// ## Number of passing cores in the group pattern status
+    pub fn group_passed_cnt(&self) -> usize {
+        self.groups
+            .iter()
+            .flatten()
+            .filter(|&&passed| passed)
+            .count()
+    }
+
+    /// ## Number of passing cores in the single pattern status
+    pub fn single_passed_cnt(&self) -> usize {
+        self.single.iter().filter(|&&passed| passed).count()
+    }
+
+    /// ## Whether every core passed both the group and single pattern tests
+    pub fn all_passed(&self) -> bool {
+        self.group_passed_cnt() == PATTERN_GROUPS * PATTERN_GROUP_CORES
+            && self.single_passed_cnt() == PATTERN_SINGLE_CORES
+    }
+}
@@ -0,0 +1,143 @@
+use crate::{Baud, Chain, NoPin};
+use core::{
+    future::Future,
+    pin::pin,
+    task::{Context, Poll, RawWaker, RawWakerVTable, Waker},
+};
+use embedded_io::{ErrorType, Read, ReadReady, Write};
+
+/// # Blocking Port
+///
+/// Serial interface adapter, giving a blocking `embedded_io` port to a `Chain`.
+///
+/// A read returns 0 bytes when nothing was received yet instead of blocking, as
+/// reported by `ReadReady`, which is how the `Chain` sees the end of the received data.
+#[derive(Debug, Default, PartialEq)]
+pub struct BlockingPort<T>(pub T);
+
+impl<T: ErrorType> ErrorType for BlockingPort<T> {
+    type Error = T::Error;
+}
+
+impl<T: Read + ReadReady> embedded_io_async::Read for BlockingPort<T> {
+    async fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        if !self.0.read_ready()? {
+            return Ok(0);
+        }
+        self.0.read(buf)
+    }
+}
+
+impl<T: Write> embedded_io_async::Write for BlockingPort<T> {
+    async fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+        self.0.write(buf)
+    }
+
+    async fn flush(&mut self) -> Result<(), Self::Error> {
+        self.0.flush()
+    }
+}
+
+impl<T: Baud> Baud for BlockingPort<T> {
+    fn set_baudrate(&mut self, baudrate: u32) {
+        self.0.set_baudrate(baudrate);
+    }
+}
+
+/// # Blocking Delay
+///
+/// Delay adapter, giving a blocking `embedded_hal` delay to a `Chain`.
+#[derive(Debug, Default, PartialEq)]
+pub struct BlockingDelay<D>(pub D);
+
+impl<D: embedded_hal::delay::DelayNs> embedded_hal_async::delay::DelayNs for BlockingDelay<D> {
+    async fn delay_ns(&mut self, ns: u32) {
+        self.0.delay_ns(ns);
+    }
+}
+
+/// A `Chain` over a blocking serial interface and delay, see `block_on`.
+pub type SyncChain<A, P, D, R = NoPin> = Chain<A, BlockingPort<P>, BlockingDelay<D>, R>;
+
+/// ## Run a `SyncChain` operation to completion
+///
+/// The `Chain` sequences are shared with the asynchronous interface, this drives one of
+/// them from synchronous code. With `BlockingPort` and `BlockingDelay` they never wait
+/// for a wake-up, so no executor is needed.
+///
+/// ### Example
+///
+/// ```
+/// use bm1366::BM1366;
+/// use bm13xx_chain::{block_on, Baud, BlockingDelay, BlockingPort, SyncChain};
+/// use core::convert::Infallible;
+/// use std::collections::VecDeque;
+///
+/// /// Serial port with one BM1366 answering `ChipIdentification` reads
+/// struct Port(VecDeque<u8>);
+///
+/// impl embedded_io::ErrorType for Port {
+///     type Error = Infallible;
+/// }
+/// impl embedded_io::Read for Port {
+///     fn read(&mut self, buf: &mut [u8]) -> Result<usize, Infallible> {
+///         let n = buf.len().min(self.0.len());
+///         buf[..n].iter_mut().for_each(|b| *b = self.0.pop_front().unwrap());
+///         Ok(n)
+///     }
+/// }
+/// impl embedded_io::ReadReady for Port {
+///     fn read_ready(&mut self) -> Result<bool, Infallible> {
+///         Ok(!self.0.is_empty())
+///     }
+/// }
+/// impl embedded_io::Write for Port {
+///     fn write(&mut self, buf: &[u8]) -> Result<usize, Infallible> {
+///         if buf[2] & 0x0f == 0x02 && buf[5] == 0x00 {
+///             // ChipIdentification read
+///             self.0.extend([0xAA, 0x55, 0x13, 0x66, 0x00, 0x00, 0x00, 0x00, 0x0C]);
+///         }
+///         Ok(buf.len())
+///     }
+///     fn flush(&mut self) -> Result<(), Infallible> {
+///         Ok(())
+///     }
+/// }
+/// impl Baud for Port {
+///     fn set_baudrate(&mut self, _baudrate: u32) {}
+/// }
+///
+/// struct Delay;
+///
+/// impl embedded_hal::delay::DelayNs for Delay {
+///     fn delay_ns(&mut self, _ns: u32) {}
+/// }
+///
+/// let port = BlockingPort(Port(VecDeque::new()));
+/// let mut chain: SyncChain<_, _, _> =
+///     SyncChain::new(1, BM1366::default(), 1, port, BlockingDelay(Delay));
+/// block_on(chain.enumerate()).unwrap();
+/// assert_eq!(chain.asic_cnt, 1);
+/// assert_eq!(block_on(chain.ping(0)), Ok(true));
+/// ```
+pub fn block_on<F: Future>(fut: F) -> F::Output {
+    let waker = noop_waker();
+    let mut cx = Context::from_waker(&waker);
+    let mut fut = pin!(fut);
+    loop {
+        if let Poll::Ready(out) = fut.as_mut().poll(&mut cx) {
+            return out;
+        }
+    }
+}
+
+fn noop_waker() -> Waker {
+    const VTABLE: RawWakerVTable = RawWakerVTable::new(
+        |_| RawWaker::new(core::ptr::null(), &VTABLE),
+        |_| {},
+        |_| {},
+        |_| {},
+    );
+    // SAFETY: the vtable functions do nothing, so any data pointer is valid
+    unsafe { Waker::from_raw(RawWaker::new(core::ptr::null(), &VTABLE)) }
+}
@@ -0,0 +1,29 @@
+use core::{
+    future::{poll_fn, Future},
+    pin::pin,
+    task::Poll,
+    time::Duration,
+};
+use embedded_hal_async::delay::DelayNs;
+
+/// Run `fut` until it completes or `timeout` elapses, whichever comes first.
+///
+/// Returns `None` if `timeout` elapsed first, `fut` is then dropped.
+pub(crate) async fn with_timeout<F: Future>(
+    delay: &mut impl DelayNs,
+    timeout: Duration,
+    fut: F,
+) -> Option<F::Output> {
+    let mut fut = pin!(fut);
+    let mut deadline = pin!(delay.delay_us(timeout.as_micros().min(u32::MAX as u128) as u32));
+    poll_fn(|cx| {
+        if let Poll::Ready(out) = fut.as_mut().poll(cx) {
+            return Poll::Ready(Some(out));
+        }
+        if deadline.as_mut().poll(cx).is_ready() {
+            return Poll::Ready(None);
+        }
+        Poll::Pending
+    })
+    .await
+}
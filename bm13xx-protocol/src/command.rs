@@ -63,6 +63,11 @@ impl Command {
     /// - when mining, the nonce space (u32) will be divided evenly according to `ChipAddress` :
     ///   each chip will add it's own `ChipAddress` near by the MSB of the starting nonce for a job.
     ///
+    /// There is no way to re-address a single, already addressed, chip: the only address
+    /// field of this frame is the new `ChipAddress`, so it can not be targeted with a
+    /// `Destination`. To recover a wrongly addressed chip, send `Command::chain_inactive`
+    /// and address the whole chain again.
+    ///
     /// ## Example
     /// ```
     /// use bm13xx_protocol::command::Command;
@@ -90,6 +95,10 @@ impl Command {
     /// command of `ChipIdentification` to all chips on the chain, this is usefull to
     /// enumerate all chips on the chain.
     ///
+    /// There is no known command to read a block of registers at once: neither the
+    /// BM1397/BM1366 documentation nor the captured vendor firmware traffic use one,
+    /// so dumping the registers takes one Read Register command per register.
+    ///
     /// ## Example
     /// ```
     /// use bm13xx_protocol::command::{Command, Destination};
@@ -153,6 +162,31 @@ impl Command {
         data
     }
 
+    /// # Write Registers Command batch
+    ///
+    /// Build a `Command::write_reg` frame for each `(reg_addr, reg_val)` pair, all sent
+    /// to the same `dest`, so they can be flushed back-to-back on the chain.
+    ///
+    /// ## Panics
+    ///
+    /// If `regs` holds more than `N` registers.
+    ///
+    /// ## Example
+    /// ```
+    /// use bm13xx_protocol::command::{Command, Destination};
+    /// use heapless::Vec;
+    ///
+    /// let cmds: Vec<[u8; 11], 4> = Command::write_regs(&[(0x80, 0x0000_0000), (0x18, 0x0000_7A31)], Destination::All);
+    /// assert_eq!(cmds.len(), 2);
+    /// assert_eq!(cmds[0], Command::write_reg(0x80, 0x0000_0000, Destination::All));
+    /// assert_eq!(cmds[1], Command::write_reg(0x18, 0x0000_7A31, Destination::All));
+    /// ```
+    pub fn write_regs<const N: usize>(regs: &[(u8, u32)], dest: Destination) -> Vec<[u8; 11], N> {
+        regs.iter()
+            .map(|&(reg_addr, reg_val)| Self::write_reg(reg_addr, reg_val, dest))
+            .collect()
+    }
+
     /// # Job with Midstates Command
     ///
     /// ## Example
@@ -237,6 +271,9 @@ impl Command {
 
     /// # Job with Header (for Hardware Version Rolling) Command
     ///
+    /// `n_bits`, `n_time` and `version` are sent little-endian. The hashes are sent as given,
+    /// see `HeaderFields` to build them from a block header or big-endian hashes.
+    ///
     /// ## Example
     /// ```
     /// use bm13xx_protocol::command::Command;
@@ -287,3 +324,208 @@ impl Command {
         data
     }
 }
+
+/// Block header fields of a `Command::job_header`, in the byte order the chips expect.
+///
+/// `Command::job_header` takes `n_bits`, `n_time` and `version` as values, and the
+/// merkle root and previous block hash as sent on the wire: the hash 32-bit words in
+/// reverse order. This builds them either from a serialized block header, or from
+/// hashes in big-endian order, as displayed by block explorers.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+pub struct HeaderFields {
+    version: u32,
+    prev_block_header_hash: [u8; 32],
+    full_merkle_root: [u8; 32],
+    n_time: u32,
+    n_bits: u32,
+}
+
+impl HeaderFields {
+    /// ## Get the fields of a serialized 80 bytes block header
+    ///
+    /// The trailing nonce is ignored, the chips roll it.
+    ///
+    /// ### Example
+    /// ```
+    /// use bm13xx_protocol::command::{Command, HeaderFields};
+    ///
+    /// let block_header = [
+    ///     0x00, 0x00, 0x00, 0x20, 0x38, 0xe1, 0x99, 0x1f, 0xd7, 0x76, 0xd8, 0x1a, 0xae, 0x0e,
+    ///     0xd5, 0x56, 0x55, 0xd3, 0x00, 0xfc, 0x5b, 0xa4, 0xa5, 0x0e, 0xff, 0x86, 0x02, 0x00,
+    ///     0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xed, 0xe9, 0x96, 0x71, 0xa8, 0x0e,
+    ///     0x70, 0x2e, 0xc0, 0xd8, 0x86, 0xda, 0xc0, 0x96, 0xf3, 0xa4, 0xfe, 0x83, 0xec, 0x35,
+    ///     0xb8, 0x71, 0x7a, 0x7e, 0x66, 0x63, 0x21, 0x46, 0x2d, 0x19, 0x75, 0x74, 0x83, 0xde,
+    ///     0x70, 0x65, 0x50, 0x24, 0x04, 0x17, 0x00, 0x00, 0x00, 0x00,
+    /// ];
+    /// let fields = HeaderFields::from_block_header(&block_header);
+    /// assert_eq!(fields.version(), 0x2000_0000);
+    /// assert_eq!(fields.n_time(), 0x6570_de83);
+    /// assert_eq!(fields.n_bits(), 0x1704_2450);
+    /// // same frame as the `Command::job_header` example
+    /// assert_eq!(
+    ///     fields.job_header(168),
+    ///     [
+    ///         0x55, 0xaa, 0x21, 0x36, 0xa8, 0x01, 0x00, 0x00, 0x00, 0x00, 0x50, 0x24, 0x04, 0x17,
+    ///         0x83, 0xde, 0x70, 0x65, 0x2d, 0x19, 0x75, 0x74, 0x66, 0x63, 0x21, 0x46, 0xb8, 0x71,
+    ///         0x7a, 0x7e, 0xfe, 0x83, 0xec, 0x35, 0xc0, 0x96, 0xf3, 0xa4, 0xc0, 0xd8, 0x86, 0xda,
+    ///         0xa8, 0x0e, 0x70, 0x2e, 0xed, 0xe9, 0x96, 0x71, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    ///         0x00, 0x00, 0xff, 0x86, 0x02, 0x00, 0x5b, 0xa4, 0xa5, 0x0e, 0x55, 0xd3, 0x00, 0xfc,
+    ///         0xae, 0x0e, 0xd5, 0x56, 0xd7, 0x76, 0xd8, 0x1a, 0x38, 0xe1, 0x99, 0x1f, 0x00, 0x00,
+    ///         0x00, 0x20, 0x30, 0xb9,
+    ///     ]
+    /// );
+    /// ```
+    pub fn from_block_header(block_header: &[u8; 80]) -> Self {
+        let le_u32 = |offset: usize| {
+            u32::from_le_bytes(block_header[offset..offset + 4].try_into().unwrap())
+        };
+        let mut fields = Self {
+            version: le_u32(0),
+            n_time: le_u32(68),
+            n_bits: le_u32(72),
+            ..Default::default()
+        };
+        for word in 0..8 {
+            let wire = (7 - word) * 4;
+            fields.prev_block_header_hash[wire..wire + 4]
+                .copy_from_slice(&block_header[4 + word * 4..8 + word * 4]);
+            fields.full_merkle_root[wire..wire + 4]
+                .copy_from_slice(&block_header[36 + word * 4..40 + word * 4]);
+        }
+        fields
+    }
+
+    /// ## Set the previous block hash, in big-endian order
+    ///
+    /// This is the order block explorers display the hashes in, leading zeros first.
+    ///
+    /// ### Example
+    /// ```
+    /// use bm13xx_protocol::command::{Command, HeaderFields};
+    ///
+    /// let prev_hash = [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x02, 0x86, 0xff,
+    ///         0x0e, 0xa5, 0xa4, 0x5b, 0xfc, 0x00, 0xd3, 0x55, 0x56, 0xd5, 0x0e, 0xae, 0x1a, 0xd8,
+    ///         0x76, 0xd7, 0x1f, 0x99, 0xe1, 0x38];
+    /// let merkle_root = [0x74, 0x75, 0x19, 0x2d, 0x46, 0x21, 0x63, 0x66, 0x7e, 0x7a, 0x71, 0xb8,
+    ///         0x35, 0xec, 0x83, 0xfe, 0xa4, 0xf3, 0x96, 0xc0, 0xda, 0x86, 0xd8, 0xc0, 0x2e, 0x70,
+    ///         0x0e, 0xa8, 0x71, 0x96, 0xe9, 0xed];
+    /// let fields = *HeaderFields::default()
+    ///     .set_version(0x2000_0000)
+    ///     .set_prev_block_hash(prev_hash)
+    ///     .set_merkle_root(merkle_root)
+    ///     .set_n_time(0x6570_de83)
+    ///     .set_n_bits(0x1704_2450);
+    /// let wire_prev_hash = [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xff, 0x86, 0x02,
+    ///         0x00, 0x5b, 0xa4, 0xa5, 0x0e, 0x55, 0xd3, 0x00, 0xfc, 0xae, 0x0e, 0xd5, 0x56, 0xd7,
+    ///         0x76, 0xd8, 0x1a, 0x38, 0xe1, 0x99, 0x1f];
+    /// assert_eq!(fields.prev_block_header_hash(), wire_prev_hash);
+    /// let wire_merkle_root = [0x2d, 0x19, 0x75, 0x74, 0x66, 0x63, 0x21, 0x46, 0xb8, 0x71, 0x7a,
+    ///         0x7e, 0xfe, 0x83, 0xec, 0x35, 0xc0, 0x96, 0xf3, 0xa4, 0xc0, 0xd8, 0x86, 0xda, 0xa8,
+    ///         0x0e, 0x70, 0x2e, 0xed, 0xe9, 0x96, 0x71];
+    /// assert_eq!(
+    ///     fields.job_header(168),
+    ///     Command::job_header(168, 0x1704_2450, 0x6570_de83, wire_merkle_root, wire_prev_hash, 0x2000_0000)
+    /// );
+    /// ```
+    pub fn set_prev_block_hash(&mut self, hash: [u8; 32]) -> &mut Self {
+        self.prev_block_header_hash = Self::be_to_wire(hash);
+        self
+    }
+
+    /// ## Set the merkle root, in big-endian order
+    ///
+    /// See `set_prev_block_hash`.
+    pub fn set_merkle_root(&mut self, root: [u8; 32]) -> &mut Self {
+        self.full_merkle_root = Self::be_to_wire(root);
+        self
+    }
+
+    /// ## Set the block version
+    pub fn set_version(&mut self, version: u32) -> &mut Self {
+        self.version = version;
+        self
+    }
+
+    /// ## Set the block time
+    pub fn set_n_time(&mut self, n_time: u32) -> &mut Self {
+        self.n_time = n_time;
+        self
+    }
+
+    /// ## Set the block target, in compact form
+    pub fn set_n_bits(&mut self, n_bits: u32) -> &mut Self {
+        self.n_bits = n_bits;
+        self
+    }
+
+    pub const fn version(&self) -> u32 {
+        self.version
+    }
+
+    pub const fn n_time(&self) -> u32 {
+        self.n_time
+    }
+
+    pub const fn n_bits(&self) -> u32 {
+        self.n_bits
+    }
+
+    /// ## Get the previous block hash, in the order `Command::job_header` takes it
+    pub const fn prev_block_header_hash(&self) -> [u8; 32] {
+        self.prev_block_header_hash
+    }
+
+    /// ## Get the merkle root, in the order `Command::job_header` takes it
+    pub const fn full_merkle_root(&self) -> [u8; 32] {
+        self.full_merkle_root
+    }
+
+    /// ## Build the `Command::job_header` of these fields
+    pub fn job_header(&self, job_id: u8) -> [u8; 88] {
+        Command::job_header(
+            job_id,
+            self.n_bits,
+            self.n_time,
+            self.full_merkle_root,
+            self.prev_block_header_hash,
+            self.version,
+        )
+    }
+
+    /// The wire order is the big-endian one with each 32-bit word byte-swapped.
+    fn be_to_wire(hash: [u8; 32]) -> [u8; 32] {
+        let mut wire = hash;
+        for word in wire.chunks_exact_mut(4) {
+            word.reverse();
+        }
+        wire
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Test each frame of a Write Registers batch is a valid Write Register command.
+    #[test]
+    fn write_regs_batch() {
+        let regs = [
+            (0xA8, 0x0007_0000),
+            (0x18, 0xF000_C100),
+            (0x3C, 0x8000_8540),
+            (0x3C, 0x8000_8020),
+        ];
+        let cmds: Vec<[u8; 11], 8> = Command::write_regs(&regs, Destination::Chip(0x12));
+        assert_eq!(cmds.len(), regs.len());
+        for (cmd, (reg_addr, reg_val)) in cmds.iter().zip(regs) {
+            assert_eq!(cmd[..4], [0x55, 0xAA, 0x41, 0x09]);
+            assert_eq!(cmd[4], 0x12);
+            assert_eq!(cmd[5], reg_addr);
+            assert_eq!(cmd[6..10], reg_val.to_be_bytes());
+            assert_eq!(cmd[10], crc5(&cmd[2..10]));
+        }
+        let cmds: Vec<[u8; 11], 8> = Command::write_regs(&[], Destination::All);
+        assert!(cmds.is_empty());
+    }
+}
@@ -1,3 +1,5 @@
+//! BM13xx Protocol CRCs.
+
 use crc::{Algorithm, Crc};
 
 const CRC5: Crc<u8> = Crc::<u8>::new(&Algorithm {
@@ -22,11 +24,24 @@ const CRC16: Crc<u16> = Crc::<u16>::new(&Algorithm {
     residue: 0x0000,
 });
 
+/// # CRC5
+///
+/// CRC used by BM13xx for commands and responses, except jobs.
+///
+/// Polynomial `x^5 + x^2 + 1` (0x05), initial value 0x1f, no reflection, no final xor.
+///
+/// ## Example
+/// ```
+/// use bm13xx_protocol::crc::crc5;
+///
+/// // Read Register ChipIdentification on All chips: [0x55, 0xAA, 0x52, 0x05, 0x00, 0x00, 0x0A]
+/// assert_eq!(crc5(&[0x52, 0x05, 0x00, 0x00]), 0x0A);
+/// ```
 pub const fn crc5(data: &[u8]) -> u8 {
     CRC5.checksum(data)
 }
 
-pub const fn crc5_bits(data: &[u8]) -> u8 {
+pub(crate) const fn crc5_bits(data: &[u8]) -> u8 {
     let mut var1;
     let mut var2 = true;
     let mut var3 = true;
@@ -105,6 +120,19 @@ pub const fn crc5_bits(data: &[u8]) -> u8 {
     crc5
 }
 
+/// # CRC16
+///
+/// CRC used by BM13xx for jobs.
+///
+/// Polynomial 0x1021 (CRC-16/CCITT-FALSE), initial value 0xffff, no reflection, no final xor.
+/// The CRC is appended big endian at the end of the job.
+///
+/// ## Example
+/// ```
+/// use bm13xx_protocol::crc::crc16;
+///
+/// assert_eq!(crc16(b"123456789"), 0x29B1);
+/// ```
 pub const fn crc16(data: &[u8]) -> u16 {
     CRC16.checksum(data)
 }
@@ -0,0 +1,257 @@
+//! BM13xx Protocol streaming Response decoder.
+
+use heapless::Vec;
+
+use crate::response::{Response, ResponseType};
+use crate::Result;
+
+/// Size of the `FrameDecoder` internal buffer.
+pub const FRAME_DECODER_BUF_SIZE: usize = 64;
+
+const PREAMBLE: [u8; 2] = [0xAA, 0x55];
+
+/// # Frame Decoder
+///
+/// Accumulate raw bytes from RO signal of BM13xx, as they come from the serial
+/// interface, and decode the `ResponseType` frames out of them.
+///
+/// Bytes preceding a `[0xAA, 0x55]` preamble are discarded, and a frame with an
+/// invalid CRC is reported then skipped, so the decoder resynchronizes by itself
+/// on the next preamble.
+///
+/// ## Example
+///
+/// ```
+/// use bm13xx_protocol::decoder::FrameDecoder;
+/// use bm13xx_protocol::response::ResponseType;
+///
+/// let mut decoder = FrameDecoder::new(false);
+/// decoder.push(&[0x00, 0xAA, 0x55, 0x97, 0xC3]);
+/// assert!(decoder.next_frame().is_none());
+/// decoder.push(&[0x28, 0xB6, 0x01, 0x63, 0x9C]);
+/// match decoder.next_frame() {
+///     Some(Ok(ResponseType::Job(j))) => assert_eq!(j.nonce, 0x97C3_28B6),
+///     _ => panic!(),
+/// };
+/// assert!(decoder.next_frame().is_none());
+/// ```
+#[derive(Debug, Default, PartialEq)]
+pub struct FrameDecoder {
+    buf: Vec<u8, FRAME_DECODER_BUF_SIZE>,
+    version_rolling: bool,
+    resyncs: u32,
+}
+
+impl FrameDecoder {
+    /// Create a decoder for 11 bytes frames if `version_rolling` is enabled, 9 bytes frames otherwise.
+    pub fn new(version_rolling: bool) -> Self {
+        FrameDecoder {
+            buf: Vec::new(),
+            version_rolling,
+            resyncs: 0,
+        }
+    }
+
+    /// Select the frame size according to the Version Rolling state of the chips.
+    pub fn set_version_rolling(&mut self, version_rolling: bool) {
+        self.version_rolling = version_rolling;
+    }
+
+    /// # Push raw bytes
+    ///
+    /// Append as many `bytes` as the internal buffer can hold and return how many were taken.
+    pub fn push(&mut self, bytes: &[u8]) -> usize {
+        let len = bytes.len().min(self.buf.capacity() - self.buf.len());
+        self.buf.extend_from_slice(&bytes[..len]).unwrap();
+        len
+    }
+
+    /// Number of bytes waiting to be decoded.
+    pub fn len(&self) -> usize {
+        self.buf.len()
+    }
+
+    /// Whether there is no byte waiting to be decoded.
+    pub fn is_empty(&self) -> bool {
+        self.buf.is_empty()
+    }
+
+    /// Drop all bytes waiting to be decoded.
+    pub fn clear(&mut self) {
+        self.buf.clear();
+    }
+
+    /// Number of times bytes were discarded to find the next preamble, wrapping around.
+    pub fn resyncs(&self) -> u32 {
+        self.resyncs
+    }
+
+    /// # Decode the next frame
+    ///
+    /// This is not an `Iterator`: `None` only means that no complete frame is available
+    /// yet, more frames can be decoded after pushing more bytes.
+    ///
+    /// ## Return
+    /// - `None` if no complete frame is available yet.
+    /// - `Some(Err(Error::InvalidCrc))` if the frame CRC5 is not valid, the frame is skipped.
+    /// - `Some(Ok(r))` with the decoded `ResponseType`.
+    pub fn next_frame(&mut self) -> Option<Result<ResponseType>> {
+        self.resync();
+        let len = self.frame_len();
+        if self.buf.len() < len {
+            return None;
+        }
+        match Response::parse_any(&self.buf, self.version_rolling) {
+            Ok((resp, len)) => {
+                self.consume(len);
+                Some(Ok(resp))
+            }
+            Err(e) => {
+                // only skip the preamble, a valid frame may start within the corrupted one
+                self.consume(PREAMBLE.len());
+                Some(Err(e))
+            }
+        }
+    }
+
+    fn frame_len(&self) -> usize {
+        if self.version_rolling {
+            11
+        } else {
+            9
+        }
+    }
+
+    fn consume(&mut self, n: usize) {
+        let len = self.buf.len();
+        self.buf.rotate_left(n);
+        self.buf.truncate(len - n);
+    }
+
+    /// Discard bytes up to the first preamble, keeping a trailing partial preamble.
+    fn resync(&mut self) {
+        let start = self.buf.windows(2).position(|w| w == PREAMBLE).unwrap_or(
+            if self.buf.last() == Some(&PREAMBLE[0]) {
+                self.buf.len() - 1
+            } else {
+                self.buf.len()
+            },
+        );
+        if start > 0 {
+            debug!("Discarding {} bytes before preamble", start);
+            self.consume(start);
+            self.resyncs = self.resyncs.wrapping_add(1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Error;
+
+    const JOB: [u8; 9] = [0xAA, 0x55, 0x97, 0xC3, 0x28, 0xB6, 0x01, 0x63, 0x9C];
+    const JOB_VER: [u8; 11] = [
+        0xAA, 0x55, 0x2F, 0xD5, 0x96, 0xCE, 0x02, 0x93, 0x94, 0xFB, 0x86,
+    ];
+    const CHIP_IDENT: [u8; 9] = [0xAA, 0x55, 0x13, 0x97, 0x18, 0x00, 0x00, 0x00, 0x06];
+
+    fn nonce(resp: Option<Result<ResponseType>>) -> u32 {
+        match resp {
+            Some(Ok(ResponseType::Job(j))) => j.nonce,
+            Some(Ok(ResponseType::JobVer(j))) => j.nonce,
+            r => panic!("unexpected {:?}", r),
+        }
+    }
+
+    /// Test frames fed one byte at a time.
+    #[test]
+    fn fragmented() {
+        let mut decoder = FrameDecoder::new(false);
+        for b in JOB[..8].iter() {
+            decoder.push(&[*b]);
+            assert!(decoder.next_frame().is_none());
+        }
+        decoder.push(&JOB[8..]);
+        assert_eq!(nonce(decoder.next_frame()), 0x97C3_28B6);
+        for b in CHIP_IDENT[..8].iter() {
+            decoder.push(&[*b]);
+            assert!(decoder.next_frame().is_none());
+        }
+        decoder.push(&CHIP_IDENT[8..]);
+        match decoder.next_frame() {
+            Some(Ok(ResponseType::Reg(r))) => assert_eq!(r.reg_value, 0x1397_1800),
+            r => panic!("unexpected {:?}", r),
+        };
+        assert!(decoder.is_empty());
+    }
+
+    /// Test version rolling frames split across pushes.
+    #[test]
+    fn fragmented_version() {
+        let mut decoder = FrameDecoder::new(true);
+        decoder.push(&JOB_VER[..4]);
+        assert!(decoder.next_frame().is_none());
+        decoder.push(&JOB_VER[4..]);
+        decoder.push(&JOB_VER);
+        assert_eq!(nonce(decoder.next_frame()), 0x2FD5_96CE);
+        assert_eq!(nonce(decoder.next_frame()), 0x2FD5_96CE);
+        assert!(decoder.next_frame().is_none());
+    }
+
+    /// Test garbage and partial preamble before a frame.
+    #[test]
+    fn garbage() {
+        let mut decoder = FrameDecoder::new(false);
+        decoder.push(&[0x12, 0x55, 0x34, 0xAA]);
+        assert!(decoder.next_frame().is_none());
+        assert_eq!(decoder.len(), 1);
+        decoder.push(&JOB[1..]);
+        assert_eq!(nonce(decoder.next_frame()), 0x97C3_28B6);
+        assert!(decoder.is_empty());
+        assert_eq!(decoder.resyncs(), 1);
+    }
+
+    /// Test a corrupted frame is reported then skipped.
+    #[test]
+    fn corrupted() {
+        let mut decoder = FrameDecoder::new(false);
+        let mut bad = JOB;
+        bad[4] ^= 0x01;
+        decoder.push(&bad);
+        decoder.push(&JOB);
+        assert!(matches!(
+            decoder.next_frame(),
+            Some(Err(Error::InvalidCrc { .. }))
+        ));
+        assert_eq!(nonce(decoder.next_frame()), 0x97C3_28B6);
+        assert!(decoder.next_frame().is_none());
+    }
+
+    /// Test a truncated frame followed by a valid one.
+    #[test]
+    fn truncated() {
+        let mut decoder = FrameDecoder::new(false);
+        decoder.push(&JOB[..5]);
+        decoder.push(&JOB);
+        assert!(matches!(
+            decoder.next_frame(),
+            Some(Err(Error::InvalidCrc { .. }))
+        ));
+        assert_eq!(nonce(decoder.next_frame()), 0x97C3_28B6);
+        assert!(decoder.next_frame().is_none());
+    }
+
+    /// Test bytes beyond the buffer capacity are not taken.
+    #[test]
+    fn full() {
+        let mut decoder = FrameDecoder::new(false);
+        assert_eq!(
+            decoder.push(&[0xAA; FRAME_DECODER_BUF_SIZE + 1]),
+            FRAME_DECODER_BUF_SIZE
+        );
+        assert_eq!(decoder.push(&[0xAA]), 0);
+        decoder.clear();
+        assert!(decoder.is_empty());
+    }
+}
@@ -8,6 +8,7 @@ pub enum Error {
     // -- response
     InvalidPreamble,
     InvalidCrc { expected: u8, actual: u8 },
+    InvalidLength { expected: usize, actual: usize },
 }
 
 #[rustversion::since(1.81)]
@@ -4,10 +4,11 @@
 #![macro_use]
 pub(crate) mod fmt;
 
-mod crc;
 mod error;
 
 pub mod command;
+pub mod crc;
+pub mod decoder;
 pub mod response;
 
 pub use self::error::{Error, Result};
@@ -11,7 +11,17 @@ pub struct RegisterResponse {
     pub reg_value: u32,
 }
 
-#[derive(Debug)]
+impl core::fmt::Display for RegisterResponse {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "Reg chip_addr={} reg_addr={:#04x} reg_value={:#010x}",
+            self.chip_addr, self.reg_addr, self.reg_value
+        )
+    }
+}
+
+#[derive(Debug, PartialEq)]
 #[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
 pub struct JobResponse {
     pub nonce: u32,
@@ -19,16 +29,106 @@ pub struct JobResponse {
     pub midstate_id: u8,
 }
 
-#[derive(Debug)]
+impl core::fmt::Display for JobResponse {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "Job job_id={:#04x} midstate_id={} nonce={:#010x}",
+            self.job_id, self.midstate_id, self.nonce
+        )
+    }
+}
+
+#[derive(Debug, PartialEq)]
 #[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
 pub struct JobVersionResponse {
     pub nonce: u32,
+    /// Raw job_id byte, the low bits carry the Small Core ID that found the nonce,
+    /// see [`JobVersionResponse::small_core_id`].
     pub job_id: u8,
+    /// Meaning still unknown on BM1366/BM1370, it does not seem to be a midstate index
+    /// as midstates are not used once the Hardware Version Rolling is enabled.
     pub midstate_id: u8,
+    /// Rolled version bits, already shifted in place in the 32 bits version,
+    /// see [`JobVersionResponse::full_version`].
     pub version_bit: u32,
 }
 
-#[derive(Debug)]
+impl core::fmt::Display for JobVersionResponse {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "JobVer job_id={:#04x} midstate_id={} nonce={:#010x} version_bit={:#010x}",
+            self.job_id, self.midstate_id, self.nonce, self.version_bit
+        )
+    }
+}
+
+impl JobVersionResponse {
+    /// # Small Core ID bit width
+    ///
+    /// Number of low bits of the raw `job_id` byte used to carry the Small Core ID,
+    /// given the number of Small Cores per Core of the chip.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use bm13xx_protocol::response::JobVersionResponse;
+    ///
+    /// assert_eq!(JobVersionResponse::small_core_id_bits(8), 3); // BM1366
+    /// assert_eq!(JobVersionResponse::small_core_id_bits(16), 4); // BM1370
+    /// ```
+    pub fn small_core_id_bits(core_small_core_cnt: usize) -> u32 {
+        core_small_core_cnt.next_power_of_two().trailing_zeros()
+    }
+
+    /// # Small Core ID
+    ///
+    /// Get the ID of the Small Core that found the nonce, carried in the low bits of `job_id`.
+    pub fn small_core_id(&self, core_small_core_cnt: usize) -> u8 {
+        self.job_id & Self::small_core_id_mask(core_small_core_cnt)
+    }
+
+    /// # Sent Job ID
+    ///
+    /// Get the `job_id` as it was sent in the job, without the Small Core ID.
+    pub fn sent_job_id(&self, core_small_core_cnt: usize) -> u8 {
+        self.job_id & !Self::small_core_id_mask(core_small_core_cnt)
+    }
+
+    /// # Full Version
+    ///
+    /// Get the 32 bits version the nonce was found with, to be submitted to the pool,
+    /// by ORing the rolled bits into the `base_version` of the job.
+    ///
+    /// The chip returns 16 rolled bits which are shifted by 13 to fill `version_bit`,
+    /// matching the usual 0x1fffe000 version mask: the rolled bits are within the mask
+    /// given to the chip, and the `base_version` bits under the mask are expected to be 0.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use bm13xx_protocol::response::{Response, ResponseType};
+    ///
+    /// let resp = Response::parse_version(&[0xAA,0x55,0x2F,0xD5,0x96,0xCE,0x02,0x93,0x94,0xFB,0x86]);
+    /// match resp.unwrap() {
+    ///     ResponseType::JobVer(j) => {
+    ///         assert_eq!(j.version_bit, 0x129F_6000);
+    ///         assert_eq!(j.full_version(0x2000_0000), 0x329F_6000);
+    ///     }
+    ///     _ => panic!(),
+    /// };
+    /// ```
+    pub fn full_version(&self, base_version: u32) -> u32 {
+        base_version | self.version_bit
+    }
+
+    fn small_core_id_mask(core_small_core_cnt: usize) -> u8 {
+        ((1u16 << Self::small_core_id_bits(core_small_core_cnt)) - 1) as u8
+    }
+}
+
+#[derive(Debug, PartialEq)]
 #[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
 pub enum ResponseType {
     Reg(RegisterResponse),
@@ -36,6 +136,39 @@ pub enum ResponseType {
     JobVer(JobVersionResponse),
 }
 
+impl core::fmt::Display for ResponseType {
+    /// Print the response fields on a single line, numbers in hexadecimal except the
+    /// chip address and the midstate ID.
+    ///
+    /// The Small Core ID carried by `job_id` depends on the chip, so the raw `job_id`
+    /// is printed, see [`JobVersionResponse::small_core_id`].
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use bm13xx_protocol::response::Response;
+    ///
+    /// let resp = Response::parse(&[0xAA,0x55,0x13,0x97,0x18,0x00,0x00,0x00,0x06]).unwrap();
+    /// assert_eq!(format!("{resp}"), "Reg chip_addr=0 reg_addr=0x00 reg_value=0x13971800");
+    ///
+    /// let resp = Response::parse(&[0xAA,0x55,0x97,0xC3,0x28,0xB6,0x01,0x63,0x9C]).unwrap();
+    /// assert_eq!(format!("{resp}"), "Job job_id=0x63 midstate_id=1 nonce=0x97c328b6");
+    ///
+    /// let resp = Response::parse_version(&[0xAA,0x55,0x2F,0xD5,0x96,0xCE,0x02,0x93,0x94,0xFB,0x86]).unwrap();
+    /// assert_eq!(
+    ///     format!("{resp}"),
+    ///     "JobVer job_id=0x93 midstate_id=2 nonce=0x2fd596ce version_bit=0x129f6000"
+    /// );
+    /// ```
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            ResponseType::Reg(r) => core::fmt::Display::fmt(r, f),
+            ResponseType::Job(j) => core::fmt::Display::fmt(j, f),
+            ResponseType::JobVer(j) => core::fmt::Display::fmt(j, f),
+        }
+    }
+}
+
 #[derive(Debug)]
 #[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
 pub struct Response;
@@ -180,6 +313,9 @@ impl Response {
     ///         assert_eq!(j.midstate_id, 2);
     ///         assert_eq!(j.job_id, 0x93);
     ///         assert_eq!(j.version_bit, 0x129F_6000);
+    ///         // BM1370 has 16 Small Cores per Core
+    ///         assert_eq!(j.small_core_id(16), 3);
+    ///         assert_eq!(j.sent_job_id(16), 0x90);
     ///     },
     ///     _ => panic!(),
     /// };
@@ -208,4 +344,60 @@ impl Response {
             reg_value: u32::from_be_bytes(data[2..6].try_into().unwrap()),
         }))
     }
+
+    /// # Parse Any Response
+    ///
+    /// Parse raw bytes from RO signal of BM13xx, the frame size being selected
+    /// according to the Version Rolling state: 11 bytes if enabled, 9 bytes otherwise.
+    ///
+    /// Only the first frame of `data` is parsed, so it can be used on a stream buffer.
+    ///
+    /// ## Return
+    /// - `Err(Error::InvalidLength)` if `data` is shorter than a frame.
+    /// - Any error from `Response::parse` or `Response::parse_version`.
+    /// - `Ok((response, len))` with the `ResponseType` and the number of bytes consumed.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use bm13xx_protocol::Error;
+    /// use bm13xx_protocol::response::{Response, ResponseType};
+    ///
+    /// let data = [0xAA,0x55,0x97,0xC3,0x28,0xB6,0x01,0x63,0x9C,0xAA,0x55];
+    /// match Response::parse_any(&data, false).unwrap() {
+    ///     (ResponseType::Job(j), len) => {
+    ///         assert_eq!(j.nonce, 0x97C3_28B6);
+    ///         assert_eq!(len, 9);
+    ///     },
+    ///     _ => panic!(),
+    /// };
+    ///
+    /// let data = [0xAA,0x55,0x2F,0xD5,0x96,0xCE,0x02,0x93,0x94,0xFB,0x86];
+    /// match Response::parse_any(&data, true).unwrap() {
+    ///     (ResponseType::JobVer(j), len) => {
+    ///         assert_eq!(j.nonce, 0x2FD5_96CE);
+    ///         assert_eq!(len, 11);
+    ///     },
+    ///     _ => panic!(),
+    /// };
+    ///
+    /// // Error::InvalidLength
+    /// let resp = Response::parse_any(&data[..9], true);
+    /// assert_eq!(resp.unwrap_err(), Error::InvalidLength { expected: 11, actual: 9 });
+    /// ```
+    pub fn parse_any(data: &[u8], version_rolling: bool) -> Result<(ResponseType, usize)> {
+        let len = if version_rolling { 11 } else { 9 };
+        if data.len() < len {
+            return Err(Error::InvalidLength {
+                expected: len,
+                actual: data.len(),
+            });
+        }
+        let resp = if version_rolling {
+            Self::parse_version(data[..len].try_into().unwrap())?
+        } else {
+            Self::parse(data[..len].try_into().unwrap())?
+        };
+        Ok((resp, len))
+    }
 }